@@ -0,0 +1,571 @@
+//! And-inverter graphs in the ASCII ('aag') and binary ('aig') AIGER format
+//! 1.9, including latches, bad state properties, invariant constraints,
+//! justice and fairness properties and the symbol table.
+//!
+//! AIGER literals are 'u32' values '2 * variable + sign', where '0' is the
+//! constant 'FALSE' and '1' the constant 'TRUE'.  The graph is translated
+//! into clauses through Tseitin encoding, either as one time frame with free
+//! latches or unrolled from the reset state for bounded model checking.
+//!
+//! ```rust
+//! use cadical_sys::{aiger::Aiger, CaDiCal, Status};
+//!
+//! // an output which is the conjunction of two inputs
+//! let aiger: Aiger = "aag 3 2 0 1 1\n2\n4\n6\n6 2 4\n".parse().unwrap();
+//! let mut solver = CaDiCal::new();
+//! let mapping = aiger.add_to(&mut solver);
+//! let output = mapping.lit(aiger.outputs[0]).unwrap();
+//! solver.assume(output);
+//! assert_eq!(solver.solve(), Status::SATISFIABLE);
+//! assert!(solver.value(mapping.lit(2).unwrap()));
+//! ```
+
+use crate::{dimacs::ParseError, CaDiCal, Error, Lit, Var};
+use std::{
+    collections::{BTreeMap, HashSet},
+    io::BufRead,
+    str::FromStr,
+};
+
+/// A latch with its next state function and reset value, which is '0',
+/// '1' or the latch literal itself if it is uninitialized.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Latch {
+    pub lit: u32,
+    pub next: u32,
+    pub reset: u32,
+}
+
+/// The gate 'lhs = rhs0 & rhs1'.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct And {
+    pub lhs: u32,
+    pub rhs0: u32,
+    pub rhs1: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SymbolKind {
+    Input,
+    Latch,
+    Output,
+    Bad,
+    Constraint,
+    Justice,
+    Fairness,
+}
+
+/// The name of the 'index'-th input, latch, output or property.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Symbol {
+    pub kind: SymbolKind,
+    pub index: usize,
+    pub name: String,
+}
+
+/// An owned and-inverter graph.  All literals are at most
+/// '2 * `max_var` + 1' and all variables in use are defined exactly once.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Aiger {
+    pub max_var: u32,
+    pub inputs: Vec<u32>,
+    pub latches: Vec<Latch>,
+    pub outputs: Vec<u32>,
+    pub bad: Vec<u32>,
+    pub constraints: Vec<u32>,
+    pub justice: Vec<Vec<u32>>,
+    pub fairness: Vec<u32>,
+    pub ands: Vec<And>,
+    pub symbols: Vec<Symbol>,
+    pub comments: Vec<String>,
+}
+
+/// The solver literals of the AIGER variables in one time frame.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Mapping {
+    // by variable, only the defined ones, whatever the header claims
+    lits: BTreeMap<u32, Lit>,
+}
+
+impl Mapping {
+    /// The solver literal of the AIGER literal 'lit', 'None' if its
+    /// variable is not defined.
+    #[must_use]
+    pub fn lit(&self, lit: u32) -> Option<Lit> {
+        let mapped = *self.lits.get(&(lit / 2))?;
+        Some(if lit % 2 == 1 { !mapped } else { mapped })
+    }
+}
+
+impl Aiger {
+    /// Parses a whole file in either format, told apart by its header.
+    ///
+    /// # Errors
+    ///
+    /// '`Error::Parse`' for the first syntax error or invalid literal and
+    /// '`Error::Io`' if reading fails.  Positions in the binary section of
+    /// the and gates refer to the byte where decoding failed.
+    ///
+    pub fn parse(mut reader: impl BufRead) -> Result<Self, Error> {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+        Scanner { data, pos: 0 }.aiger()
+    }
+
+    /// Adds the Tseitin encoding of the and gates to 'solver' where inputs
+    /// and latches are free.  Each invariant constraint is added as unit
+    /// clause.  All variables are fresh, following the maximum variable of
+    /// 'solver'.
+    ///
+    ///   require (VALID)
+    ///
+    /// # Panics
+    ///
+    /// If the fresh variables exceed '`i32::MAX`'.
+    ///
+    pub fn add_to(&self, solver: &mut CaDiCal) -> Mapping {
+        let mut translation = Translation::new(solver);
+        let mapping = translation.frame(self, None);
+        translation.reserve();
+        mapping
+    }
+
+    /// Unrolls the transition relation for 'frames' time frames where the
+    /// latches of the first frame are reset, those of the following frames
+    /// are the next state functions of the previous frame and the invariant
+    /// constraints hold in every frame.  A bad state is reachable in frame
+    /// 'k' if assuming its literal in that frame is satisfiable.
+    ///
+    ///   require (VALID)
+    ///
+    /// # Panics
+    ///
+    /// If the fresh variables exceed '`i32::MAX`'.
+    ///
+    pub fn unroll(&self, solver: &mut CaDiCal, frames: usize) -> Vec<Mapping> {
+        let mut translation = Translation::new(solver);
+        let mut mappings: Vec<Mapping> = Vec::with_capacity(frames);
+        for frame in 0..frames {
+            let state = mappings.last().map(|previous| {
+                self.latches
+                    .iter()
+                    .map(|latch| previous.lit(latch.next).expect("validated"))
+                    .collect::<Vec<_>>()
+            });
+            let mapping = translation.frame(self, state.as_deref());
+            if frame == 0 {
+                for latch in &self.latches {
+                    let lit = mapping.lit(latch.lit).expect("validated");
+                    match latch.reset {
+                        0 => translation.solver.clause1(!lit),
+                        1 => translation.solver.clause1(lit),
+                        _ => {}
+                    }
+                }
+            }
+            mappings.push(mapping);
+        }
+        translation.reserve();
+        mappings
+    }
+}
+
+/// Parses with the defaults of '`Aiger::parse`'.
+impl FromStr for Aiger {
+    type Err = Error;
+
+    fn from_str(aiger: &str) -> Result<Self, Error> {
+        Aiger::parse(aiger.as_bytes())
+    }
+}
+
+struct Translation<'a> {
+    solver: &'a mut CaDiCal,
+    next: i32,
+    constant: Lit,
+}
+
+impl<'a> Translation<'a> {
+    fn new(solver: &'a mut CaDiCal) -> Self {
+        let next = solver.vars() + 1;
+        let constant = Var::new(next).expect("too many variables").positive();
+        solver.clause1(!constant);
+        Translation {
+            solver,
+            next: next + 1,
+            constant,
+        }
+    }
+
+    /// Declares the fresh variables, also those of inputs without gates.
+    fn reserve(&mut self) {
+        self.solver.reserve(self.next - 1);
+    }
+
+    fn fresh(&mut self) -> Lit {
+        let var = Var::new(self.next).expect("too many variables");
+        self.next += 1;
+        var.positive()
+    }
+
+    /// One time frame, with fresh latches unless their 'state' is given.
+    fn frame(&mut self, aiger: &Aiger, state: Option<&[Lit]>) -> Mapping {
+        let mut mapping = Mapping {
+            lits: BTreeMap::new(),
+        };
+        mapping.lits.insert(0, self.constant);
+        for &input in &aiger.inputs {
+            mapping.lits.insert(input / 2, self.fresh());
+        }
+        for (i, latch) in aiger.latches.iter().enumerate() {
+            let lit = state.map_or_else(|| self.fresh(), |state| state[i]);
+            mapping.lits.insert(latch.lit / 2, lit);
+        }
+        for and in &aiger.ands {
+            mapping.lits.insert(and.lhs / 2, self.fresh());
+        }
+        for and in &aiger.ands {
+            let [lhs, rhs0, rhs1] =
+                [and.lhs, and.rhs0, and.rhs1].map(|lit| mapping.lit(lit).expect("validated"));
+            self.solver.clause2(!lhs, rhs0);
+            self.solver.clause2(!lhs, rhs1);
+            self.solver.clause3(lhs, !rhs0, !rhs1);
+        }
+        for &constraint in &aiger.constraints {
+            self.solver
+                .clause1(mapping.lit(constraint).expect("validated"));
+        }
+        mapping
+    }
+}
+
+struct Scanner {
+    data: Vec<u8>,
+    pos: usize,
+}
+
+/// The counts of the header 'M I L O A B C J F'.
+struct Header {
+    binary: bool,
+    max_var: u32,
+    counts: [usize; 8],
+}
+
+impl Scanner {
+    fn error_at(&self, pos: usize, message: impl Into<String>) -> Error {
+        let before = &self.data[..pos.min(self.data.len())];
+        let line = before.split(|&c| c == b'\n').count();
+        let start = before
+            .iter()
+            .rposition(|&c| c == b'\n')
+            .map_or(0, |i| i + 1);
+        Error::Parse(ParseError::new(line, pos - start + 1, message))
+    }
+
+    fn error(&self, message: impl Into<String>) -> Error {
+        self.error_at(self.pos, message)
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.data.get(self.pos).copied()
+    }
+
+    fn expect(&mut self, c: u8, expected: &str) -> Result<(), Error> {
+        if self.peek() == Some(c) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(self.error(expected))
+        }
+    }
+
+    fn number(&mut self) -> Result<u32, Error> {
+        let start = self.pos;
+        let mut value: u32 = 0;
+        while let Some(digit @ b'0'..=b'9') = self.peek() {
+            value = value
+                .checked_mul(10)
+                .and_then(|value| value.checked_add(u32::from(digit - b'0')))
+                .ok_or_else(|| self.error_at(start, "number too large"))?;
+            self.pos += 1;
+        }
+        if self.pos == start {
+            return Err(self.error("expected number"));
+        }
+        Ok(value)
+    }
+
+    /// A line with a single literal.
+    fn lit_line(&mut self) -> Result<u32, Error> {
+        let lit = self.number()?;
+        self.expect(b'\n', "expected new line")?;
+        Ok(lit)
+    }
+
+    fn header(&mut self) -> Result<Header, Error> {
+        let binary = if self.data.starts_with(b"aag ") {
+            false
+        } else if self.data.starts_with(b"aig ") {
+            true
+        } else {
+            return Err(self.error("expected 'aag' or 'aig' header"));
+        };
+        self.pos = 4;
+        let max_var = self.number()?;
+        if max_var > u32::MAX / 2 {
+            return Err(self.error_at(4, "maximum variable too large"));
+        }
+        let mut counts = [0; 8];
+        for (i, count) in counts.iter_mut().enumerate() {
+            if i >= 4 && self.peek() == Some(b'\n') {
+                break;
+            }
+            self.expect(b' ', "expected space")?;
+            *count = usize::try_from(self.number()?).expect("'u32' fits into 'usize'");
+        }
+        self.expect(b'\n', "expected new line")?;
+        Ok(Header {
+            binary,
+            max_var,
+            counts,
+        })
+    }
+
+    fn aiger(mut self) -> Result<Aiger, Error> {
+        let header = self.header()?;
+        let [inputs, latches, outputs, ands, bad, constraints, justice, fairness] = header.counts;
+        let mut checker = Checker::new(header.max_var);
+        let mut aiger = Aiger {
+            max_var: header.max_var,
+            ..Aiger::default()
+        };
+        let total = inputs
+            .checked_add(latches)
+            .and_then(|total| total.checked_add(ands));
+        if header.binary && total != usize::try_from(header.max_var).ok() {
+            return Err(self.error_at(4, "maximum variable does not match I + L + A"));
+        }
+        // the first variables of binary files are implicitly inputs, then
+        // latches and then and gates
+        let mut implicit = 0u32;
+        let mut implicit_lit = || {
+            implicit += 1;
+            implicit * 2
+        };
+        for _ in 0..inputs {
+            let start = self.pos;
+            let input = if header.binary {
+                implicit_lit()
+            } else {
+                self.lit_line()?
+            };
+            checker.define(input, start, &self)?;
+            aiger.inputs.push(input);
+        }
+        for _ in 0..latches {
+            let start = self.pos;
+            let lit = if header.binary {
+                implicit_lit()
+            } else {
+                let lit = self.number()?;
+                self.expect(b' ', "expected space")?;
+                lit
+            };
+            checker.define(lit, start, &self)?;
+            checker.uses.push((self.pos, self.number()?));
+            let next = checker.last();
+            let mut reset = 0;
+            if self.peek() == Some(b' ') {
+                self.pos += 1;
+                let start = self.pos;
+                reset = self.number()?;
+                if reset > 1 && reset != lit {
+                    return Err(self.error_at(start, "invalid reset value"));
+                }
+            }
+            self.expect(b'\n', "expected new line")?;
+            aiger.latches.push(Latch { lit, next, reset });
+        }
+        for (count, lits) in [
+            (outputs, &mut aiger.outputs),
+            (bad, &mut aiger.bad),
+            (constraints, &mut aiger.constraints),
+        ] {
+            for _ in 0..count {
+                checker.uses.push((self.pos, self.lit_line()?));
+                lits.push(checker.last());
+            }
+        }
+        let mut sizes = Vec::new();
+        for _ in 0..justice {
+            sizes.push(self.lit_line()?);
+        }
+        for size in sizes {
+            let mut property = Vec::new();
+            for _ in 0..size {
+                checker.uses.push((self.pos, self.lit_line()?));
+                property.push(checker.last());
+            }
+            aiger.justice.push(property);
+        }
+        for _ in 0..fairness {
+            checker.uses.push((self.pos, self.lit_line()?));
+            aiger.fairness.push(checker.last());
+        }
+        for _ in 0..ands {
+            let start = self.pos;
+            let and = if header.binary {
+                self.binary_and(implicit_lit())?
+            } else {
+                self.ascii_and()?
+            };
+            checker.define(and.lhs, start, &self)?;
+            checker.uses.push((start, and.rhs0));
+            checker.uses.push((start, and.rhs1));
+            aiger.ands.push(and);
+        }
+        checker.check(&self)?;
+        self.symbols(&mut aiger, &header.counts)?;
+        Ok(aiger)
+    }
+
+    fn ascii_and(&mut self) -> Result<And, Error> {
+        let lhs = self.number()?;
+        self.expect(b' ', "expected space")?;
+        let rhs0 = self.number()?;
+        self.expect(b' ', "expected space")?;
+        let rhs1 = self.number()?;
+        self.expect(b'\n', "expected new line")?;
+        Ok(And { lhs, rhs0, rhs1 })
+    }
+
+    fn binary_and(&mut self, lhs: u32) -> Result<And, Error> {
+        let delta0 = self.delta()?;
+        let rhs0 = lhs
+            .checked_sub(delta0)
+            .filter(|_| delta0 > 0)
+            .ok_or_else(|| self.error("invalid delta"))?;
+        let delta1 = self.delta()?;
+        let rhs1 = rhs0
+            .checked_sub(delta1)
+            .ok_or_else(|| self.error("invalid delta"))?;
+        Ok(And { lhs, rhs0, rhs1 })
+    }
+
+    /// A number in 7-bit groups, least significant first, where the most
+    /// significant bit of each byte tells whether more groups follow.
+    fn delta(&mut self) -> Result<u32, Error> {
+        let start = self.pos;
+        let mut value: u32 = 0;
+        for shift in (0..35).step_by(7) {
+            let byte = self
+                .peek()
+                .ok_or_else(|| self.error("unexpected end of file"))?;
+            self.pos += 1;
+            let bits = u32::from(byte & 0x7f);
+            if bits.checked_shl(shift).map(|shifted| shifted >> shift) != Some(bits) {
+                break;
+            }
+            value |= bits << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(self.error_at(start, "delta too large"))
+    }
+
+    fn symbols(&mut self, aiger: &mut Aiger, counts: &[usize; 8]) -> Result<(), Error> {
+        while let Some(kind) = self.peek() {
+            let start = self.pos;
+            if self.data[self.pos..].starts_with(b"c\n") || self.data[self.pos..] == *b"c" {
+                self.pos += 2;
+                let comments = self.data.get(self.pos..).unwrap_or_default();
+                aiger.comments = String::from_utf8_lossy(comments)
+                    .lines()
+                    .map(str::to_string)
+                    .collect();
+                return Ok(());
+            }
+            let (kind, count) = match kind {
+                b'i' => (SymbolKind::Input, counts[0]),
+                b'l' => (SymbolKind::Latch, counts[1]),
+                b'o' => (SymbolKind::Output, counts[2]),
+                b'b' => (SymbolKind::Bad, counts[4]),
+                b'c' => (SymbolKind::Constraint, counts[5]),
+                b'j' => (SymbolKind::Justice, counts[6]),
+                b'f' => (SymbolKind::Fairness, counts[7]),
+                _ => return Err(self.error("expected symbol or comment")),
+            };
+            self.pos += 1;
+            let index = usize::try_from(self.number()?).expect("'u32' fits into 'usize'");
+            if index >= count {
+                return Err(self.error_at(start, "symbol index out of range"));
+            }
+            self.expect(b' ', "expected space")?;
+            let end = self.data[self.pos..]
+                .iter()
+                .position(|&c| c == b'\n')
+                .map_or(self.data.len(), |end| self.pos + end);
+            let name = String::from_utf8_lossy(&self.data[self.pos..end]).into_owned();
+            self.pos = (end + 1).min(self.data.len());
+            aiger.symbols.push(Symbol { kind, index, name });
+        }
+        Ok(())
+    }
+}
+
+/// Tracks definitions and uses of variables, where uses are checked after
+/// all definitions since and gates follow the outputs.
+struct Checker {
+    max_var: u32,
+    // a set, as the header alone does not bound the memory needed
+    defined: HashSet<u32>,
+    /// The positions and literals of all uses.
+    uses: Vec<(usize, u32)>,
+}
+
+impl Checker {
+    fn new(max_var: u32) -> Self {
+        Checker {
+            max_var,
+            defined: HashSet::new(),
+            uses: Vec::new(),
+        }
+    }
+
+    /// The literal used last.
+    fn last(&self) -> u32 {
+        self.uses.last().expect("used before").1
+    }
+
+    fn define(&mut self, lit: u32, pos: usize, scanner: &Scanner) -> Result<(), Error> {
+        if lit < 2 || lit % 2 == 1 {
+            return Err(scanner.error_at(pos, format!("expected positive variable, got {lit}")));
+        }
+        if lit / 2 > self.max_var {
+            return Err(scanner.error_at(
+                pos,
+                format!("literal {lit} exceeds maximum variable {}", self.max_var),
+            ));
+        }
+        if !self.defined.insert(lit / 2) {
+            return Err(scanner.error_at(pos, format!("variable {} defined twice", lit / 2)));
+        }
+        Ok(())
+    }
+
+    fn check(&self, scanner: &Scanner) -> Result<(), Error> {
+        for &(pos, lit) in &self.uses {
+            if lit / 2 > self.max_var {
+                return Err(scanner.error_at(
+                    pos,
+                    format!("literal {lit} exceeds maximum variable {}", self.max_var),
+                ));
+            }
+            if lit >= 2 && !self.defined.contains(&(lit / 2)) {
+                return Err(scanner.error_at(pos, format!("undefined literal {lit}")));
+            }
+        }
+        Ok(())
+    }
+}
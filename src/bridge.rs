@@ -1,3 +1,4 @@
+#[allow(clippy::missing_errors_doc)] // the generated wrappers carry no docs
 #[cxx::bridge]
 pub mod ffi {
     unsafe extern "C++" {
@@ -23,6 +24,9 @@ pub mod ffi {
         type StatTracer;
         type FileTracer;
 
+        /// A proof file opened by 'trace_proof1', closed when dropped.
+        type ProofFile;
+
         /// Constructor and basic operations
         #[must_use]
         pub fn constructor() -> UniquePtr<Solver>;
@@ -551,10 +555,43 @@ pub mod ffi {
         ///   ensure (CONFIGURING)
         ///
         /// Write DRAT proof.
-        pub fn trace_proof1(solver: &mut UniquePtr<Solver>, file: String, name: String) -> bool;
+        ///
+        /// # Errors
+        ///
+        /// If the file cannot be opened.
+        pub fn trace_proof1(
+            solver: &mut UniquePtr<Solver>,
+            file: &[u8],
+            name: String,
+        ) -> Result<UniquePtr<ProofFile>>;
 
         /// Open & write proof.
-        pub fn trace_proof2(solver: &mut UniquePtr<Solver>, path: String) -> bool;
+        ///
+        /// # Errors
+        ///
+        /// If the file cannot be opened.
+        pub fn trace_proof2(solver: &mut UniquePtr<Solver>, path: &[u8]) -> Result<bool>;
+
+        /// Write DRAT proof to a 'FILE' which passes everything written to
+        /// 'write' and calls 'close' once the returned file is closed, also if
+        /// tracing fails.
+        ///
+        /// # Safety
+        ///
+        /// 's' has to remain valid until 'close' is called with it, which
+        /// does not happen if this function throws.
+        ///
+        /// # Errors
+        ///
+        /// If the file cannot be created.
+        #[allow(clippy::missing_safety_doc)]
+        pub unsafe fn trace_proof_stream(
+            solver: &mut UniquePtr<Solver>,
+            s: *mut u8,
+            write: unsafe fn(*mut u8, &[u8]) -> bool,
+            close: unsafe fn(*mut u8) -> bool,
+            name: String,
+        ) -> Result<UniquePtr<ProofFile>>;
 
         /// Flushing the proof trace file eventually calls 'fflush' on the actual
         /// file or pipe and thus if this function returns all the proof steps
@@ -589,6 +626,14 @@ pub mod ffi {
         ///
         pub fn close_proof_trace(solver: &mut UniquePtr<Solver>, print: bool);
 
+        /// Closes a file of 'trace_proof1' or 'trace_proof_stream' which the
+        /// solver no longer writes to.
+        ///
+        /// # Errors
+        ///
+        /// With 'errno' if closing, or finishing the stream, fails.
+        pub fn close_proof_file(proof: &mut UniquePtr<ProofFile>) -> Result<()>;
+
         /// Enables clausal proof tracing with or without antecedents using
         /// the Tracer interface defined in 'tracer.hpp'
         ///
@@ -601,6 +646,7 @@ pub mod ffi {
             solver: &mut UniquePtr<Solver>,
             tracer: &mut UniquePtr<Tracer>,
             antecedents: bool,
+            finalize_clauses: bool,
         );
         pub fn connect_proof_tracer2(
             solver: &mut UniquePtr<Solver>,
@@ -611,11 +657,13 @@ pub mod ffi {
             solver: &mut UniquePtr<Solver>,
             tracer: &mut UniquePtr<StatTracer>,
             antecedents: bool,
+            finalize_clauses: bool,
         );
         pub fn connect_proof_tracer4(
             solver: &mut UniquePtr<Solver>,
             tracer: &mut UniquePtr<FileTracer>,
             antecedents: bool,
+            finalize_clauses: bool,
         );
 
         /// Triggers the conclusion of incremental proofs.
@@ -653,6 +701,15 @@ pub mod ffi {
             tracer: &mut UniquePtr<FileTracer>,
         ) -> bool;
 
+        /// A stat tracer counting proof steps.
+        #[must_use]
+        pub fn new_stat_tracer() -> UniquePtr<StatTracer>;
+
+        /// The counts of a tracer made by 'new_stat_tracer' in the order of
+        /// the fields of 'ProofStatistics'.
+        #[must_use]
+        pub fn stat_tracer_statistics(tracer: &UniquePtr<StatTracer>) -> [u64; 6];
+
         /// print usage information for long options
         pub fn usage();
 
@@ -708,49 +765,92 @@ pub mod ffi {
         /// strict formatting of the header is required, i.e., single spaces
         /// everywhere and no trailing white space.
         ///
-        /// Returns zero if successful and otherwise an error message.
+        /// Returns an empty string if successful and otherwise an error message.
         ///
         ///   require (VALID)
         ///   ensure (VALID)
         ///
+        /// # Errors
+        ///
+        /// If the file cannot be opened.
         pub fn read_dimacs1(
             solver: &mut UniquePtr<Solver>,
-            file: String,
+            file: &[u8],
             name: String,
             vars: &mut i32,
             strict: i32,
-        ) -> String;
+        ) -> Result<String>;
 
+        /// Same as 'read_dimacs1' named by 'path', which is only opened by
+        /// `CaDiCaL` if it is decompressed through an external helper.
+        ///
+        /// # Errors
+        ///
+        /// If the file cannot be opened otherwise.
         pub fn read_dimacs2(
             solver: &mut UniquePtr<Solver>,
-            path: String,
+            path: &[u8],
             vars: &mut i32,
             strict: i32,
-        ) -> String;
+        ) -> Result<String>;
 
         /// The following routines work the same way but parse both DIMACS and
         /// INCCNF files (with 'p inccnf' header and 'a <cube>' lines).  If the
         /// parser finds and 'p inccnf' header or cubes then '*incremental' is set
         /// to true and the cubes are stored in the given vector (each cube
         /// terminated by a zero).
+        ///
+        /// # Errors
+        ///
+        /// If the file cannot be opened.
         pub fn read_dimacs3(
             solver: &mut UniquePtr<Solver>,
-            file: String,
+            file: &[u8],
             name: String,
             vars: &mut i32,
             strict: i32,
             incremental: &mut bool,
             cubes: &mut Vec<i32>,
-        ) -> String;
+        ) -> Result<String>;
 
+        /// # Errors
+        ///
+        /// If the file cannot be opened.
         pub fn read_dimacs4(
             solver: &mut UniquePtr<Solver>,
-            path: String,
+            path: &[u8],
+            vars: &mut i32,
+            strict: i32,
+            incremental: &mut bool,
+            cubes: &mut Vec<i32>,
+        ) -> Result<String>;
+
+        /// Same as 'read_dimacs1' and 'read_dimacs3' but parse the bytes in
+        /// 'buffer' as if they were the content of a file named 'name'.
+        ///
+        /// # Errors
+        ///
+        /// If the buffer cannot be opened as a file.
+        pub fn read_dimacs_buffer1(
+            solver: &mut UniquePtr<Solver>,
+            buffer: &[u8],
+            name: String,
+            vars: &mut i32,
+            strict: i32,
+        ) -> Result<String>;
+
+        /// # Errors
+        ///
+        /// If the buffer cannot be opened as a file.
+        pub fn read_dimacs_buffer2(
+            solver: &mut UniquePtr<Solver>,
+            buffer: &[u8],
+            name: String,
             vars: &mut i32,
             strict: i32,
             incremental: &mut bool,
             cubes: &mut Vec<i32>,
-        ) -> String;
+        ) -> Result<String>;
 
         //------------------------------------------------------------------------
         /// Write current irredundant clauses and all derived unit clauses
@@ -760,25 +860,34 @@ pub mod ffi {
         /// The 'min_max_var' parameter gives a lower bound on the number '<vars>'
         /// of variables used in the DIMACS 'p cnf <vars> ...' header.
         ///
-        /// Returns zero if successful and otherwise an error message.
+        /// Returns an empty string if successful and otherwise an error message.
         ///
         ///   require (VALID)
         ///   ensure (VALID)
         ///
+        /// # Errors
+        ///
+        /// If the file cannot be opened.
         pub fn write_dimacs(
             solver: &mut UniquePtr<Solver>,
-            path: String,
+            path: &[u8],
             min_max_var: i32,
-        ) -> String;
+        ) -> Result<String>;
 
         /// The extension stack for reconstruction a solution can be written too.
         ///
-        pub fn write_extension(solver: &mut UniquePtr<Solver>, path: String) -> String;
+        /// # Errors
+        ///
+        /// If the file cannot be opened.
+        pub fn write_extension(solver: &mut UniquePtr<Solver>, path: &[u8]) -> Result<String>;
 
         /// Print build configuration to a file with prefix 'c '.  If the file
         /// is '<stdout>' or '<stderr>' then terminal color codes might be used.
         ///
-        pub fn build(file: String, prefix: String);
+        /// # Errors
+        ///
+        /// If the file cannot be opened.
+        pub fn build(file: &[u8], prefix: String) -> Result<()>;
 
         // ****************************************************************************************
         // API for connected objects
@@ -0,0 +1,177 @@
+use crate::{
+    proof::{FileTracer, StatTracer},
+    CaDiCal, Configured, Error, ProofTracer,
+};
+use std::path::{Path, PathBuf};
+
+/// Option handling in the order it was requested, since a configuration
+/// overwrites the options set before it.
+enum Setting {
+    Configure(String),
+    Set(String, i32),
+    LongOption(String),
+}
+
+/// Proof tracers in the order they were given, so that their ids are in
+/// the same order.
+enum Tracer {
+    Rust(Box<dyn ProofTracer>, bool, bool),
+    Stat(StatTracer, bool),
+    File(FileTracer),
+}
+
+/// Collects everything which `CaDiCaL` only accepts in state 'CONFIGURING',
+/// that is options, configurations, the proof file and proof tracers, and
+/// then builds a '`Configured`' solver in state 'STEADY'.  Since the builder
+/// cannot add clauses and '`Configured`' has no functions requiring
+/// 'CONFIGURING', configuring a solver after it has seen clauses (and thus
+/// writing partial proofs or aborting) is not expressible.
+///
+/// ```rust
+/// use cadical_sys::{SolverBuilder, State, Status};
+///
+/// let mut solver = SolverBuilder::new()
+///     .configure("unsat".to_string())
+///     .set("verbose".to_string(), 0)
+///     .build()
+///     .expect("valid configuration");
+/// assert_eq!(solver.state(), State::STEADY);
+/// solver.clause2(1, 2);
+/// assert_eq!(solver.solve(), Status::SATISFIABLE);
+/// ```
+///
+#[derive(Default)]
+#[must_use]
+pub struct SolverBuilder {
+    settings: Vec<Setting>,
+    prefix: Option<String>,
+    proof: Option<(PathBuf, Option<String>)>,
+    tracers: Vec<Tracer>,
+}
+
+impl SolverBuilder {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// See '`CaDiCal::configure`'.
+    #[inline]
+    pub fn configure(mut self, name: String) -> Self {
+        self.settings.push(Setting::Configure(name));
+        self
+    }
+
+    /// See '`CaDiCal::set`'.
+    #[inline]
+    pub fn set(mut self, name: String, val: i32) -> Self {
+        self.settings.push(Setting::Set(name, val));
+        self
+    }
+
+    /// See '`CaDiCal::set_long_option`'.
+    #[inline]
+    pub fn long_option(mut self, arg: String) -> Self {
+        self.settings.push(Setting::LongOption(arg));
+        self
+    }
+
+    /// See '`CaDiCal::prefix`'.
+    #[inline]
+    pub fn prefix(mut self, verbose_message_prefix: String) -> Self {
+        self.prefix = Some(verbose_message_prefix);
+        self
+    }
+
+    /// Write a DRAT proof to 'path', see '`CaDiCal::trace_proof2`'.
+    #[inline]
+    pub fn trace_proof(mut self, path: impl AsRef<Path>) -> Self {
+        self.proof = Some((path.as_ref().to_path_buf(), None));
+        self
+    }
+
+    /// Write a DRAT proof to 'file' under 'name', see
+    /// '`CaDiCal::trace_proof1`'.  Replaces '`trace_proof`' and vice versa.
+    #[inline]
+    pub fn trace_proof1(mut self, file: impl AsRef<Path>, name: String) -> Self {
+        self.proof = Some((file.as_ref().to_path_buf(), Some(name)));
+        self
+    }
+
+    /// See '`CaDiCal::connect_proof_tracer1`'.  Tracers are connected in
+    /// the order they are given, so '`CaDiCal::proof_tracers`' lists their
+    /// ids for '`CaDiCal::disconnect_proof_tracer1`' in that order.
+    #[inline]
+    pub fn proof_tracer(
+        mut self,
+        tracer: Box<dyn ProofTracer>,
+        antecedents: bool,
+        finalize: bool,
+    ) -> Self {
+        self.tracers
+            .push(Tracer::Rust(tracer, antecedents, finalize));
+        self
+    }
+
+    /// See '`CaDiCal::connect_stat_tracer`'.  Its counts are read with
+    /// '`CaDiCal::proof_statistics`'.
+    #[inline]
+    pub fn stat_tracer(mut self, tracer: StatTracer, antecedents: bool) -> Self {
+        self.tracers.push(Tracer::Stat(tracer, antecedents));
+        self
+    }
+
+    /// See '`CaDiCal::connect_file_tracer`'.
+    #[inline]
+    pub fn file_tracer(mut self, tracer: FileTracer) -> Self {
+        self.tracers.push(Tracer::File(tracer));
+        self
+    }
+
+    /// Applies the collected configuration in order to a new solver.
+    ///
+    ///   ensure (STEADY)
+    ///
+    /// # Errors
+    ///
+    /// The first error of '`CaDiCal::try_configure`', '`CaDiCal::try_set`',
+    /// '`CaDiCal::set_long_option`' (as '`Error::InvalidOption`') and
+    /// '`CaDiCal::try_trace_proof1`' or '`CaDiCal::try_trace_proof2`'.
+    ///
+    pub fn build(self) -> Result<Configured, Error> {
+        let mut solver = CaDiCal::new();
+        for setting in self.settings {
+            match setting {
+                Setting::Configure(name) => solver.try_configure(name)?,
+                Setting::Set(name, val) => solver.try_set(name, val)?,
+                Setting::LongOption(arg) => {
+                    if !solver.set_long_option(arg.clone()) {
+                        return Err(Error::InvalidOption(arg));
+                    }
+                }
+            }
+        }
+        if let Some(prefix) = self.prefix {
+            solver.prefix(prefix);
+        }
+        match self.proof {
+            Some((file, Some(name))) => solver.try_trace_proof1(file, name)?,
+            Some((path, None)) => solver.try_trace_proof2(path)?,
+            None => {}
+        }
+        for tracer in self.tracers {
+            match tracer {
+                Tracer::Rust(tracer, antecedents, finalize) => {
+                    solver.connect_proof_tracer1(tracer, antecedents, finalize)
+                }
+                Tracer::Stat(tracer, antecedents) => {
+                    solver.connect_stat_tracer(tracer, antecedents)
+                }
+                Tracer::File(tracer) => solver.connect_file_tracer(tracer),
+            };
+        }
+        // leaves 'CONFIGURING' without adding clauses or variables
+        solver.reserve(0);
+        Ok(Configured::new(solver))
+    }
+}
@@ -5,7 +5,11 @@
 #pragma once
 #include "../cadical/src/cadical.hpp"
 #include "../cadical/src/tracer.hpp"
+#include <array>
+#include <cerrno>
+#include <cstdio>
 #include <memory>
+#include <stdexcept>
 #include <vector>
 #include <string>
 #include "rust/cxx.h"
@@ -37,13 +41,54 @@ using CaDiCaL::ConclusionType;
 // helper functions
 // ************************************************************************************************
 
-FILE *_read_file(rust::String file, const char *mode)
+// paths arrive as raw bytes, since they need not be valid UTF-8
+std::string _path(rust::Slice<const uint8_t> path)
 {
-    FILE *fptr = fopen(file.c_str(), mode);
+    return std::string(reinterpret_cast<const char *>(path.data()), path.size());
+}
+
+FILE *_read_file(rust::Slice<const uint8_t> file, const char *mode)
+{
+    FILE *fptr = fopen(_path(file).c_str(), mode);
     if (fptr == NULL)
     {
-        printf("Failed to open file\n");
-        exit(1);
+        // surfaces as the error of the Rust call, which rebuilds the 'io::Error' from 'errno'
+        throw std::runtime_error(std::to_string(errno));
+    }
+    return fptr;
+}
+
+// whether 'CaDiCaL' reads 'path' through an external decompressor, which it has to start itself
+bool _piped(const std::string &path)
+{
+    for (const std::string suffix : {".gz", ".bz2", ".xz", ".lzma", ".7z", ".zst"})
+    {
+        if (path.size() > suffix.size() && path.compare(path.size() - suffix.size(), suffix.size(), suffix) == 0)
+        {
+            return true;
+        }
+    }
+    return false;
+}
+
+// 'CaDiCaL' opens files for writing itself and only reports a failure as a message or 'false'.
+// The reason is left in 'errno', which is cleared before the call so that it can only come from
+// that failure, and is thrown like in '_read_file'.
+void _throw_errno_if(bool failed)
+{
+    if (failed && errno != 0)
+    {
+        throw std::runtime_error(std::to_string(errno));
+    }
+}
+
+FILE *_read_buffer(rust::Slice<const uint8_t> buffer)
+{
+    // 'fmemopen' may reject an empty buffer, an empty temporary file reads the same
+    FILE *fptr = buffer.empty() ? tmpfile() : fmemopen(const_cast<uint8_t *>(buffer.data()), buffer.size(), "r");
+    if (fptr == NULL)
+    {
+        throw std::runtime_error(std::to_string(errno));
     }
     return fptr;
 }
@@ -61,7 +106,7 @@ rust::String _convert_char_to_rust_string(const char *str)
 {
     if (str == NULL)
     {
-        return rust::String("Null");
+        return rust::String();
     }
     else
     {
@@ -464,17 +509,126 @@ void unphase(std::unique_ptr<Solver> &solver, int lit)
     return solver->unphase(lit);
 }
 
-bool trace_proof1(std::unique_ptr<Solver> &solver, rust::String file, rust::String name)
+// The solver writes to a proof file given as 'FILE' until it is deleted but
+// never closes it, so the file is handed to Rust, which closes it afterwards
+// or earlier through 'close_proof_file'.
+struct ProofFile
 {
-    auto fptr = _read_file(file, "w+");
-    auto r = solver->trace_proof(fptr, name.c_str());
-    fclose(fptr);
-    return r;
+    FILE *file;
+
+    ProofFile(FILE *file) : file(file) {}
+
+    ~ProofFile()
+    {
+        if (file != NULL)
+        {
+            fclose(file);
+        }
+    }
+};
+
+// Closes the file once the solver no longer writes to it.  Closing a file of 'trace_proof_stream'
+// finishes its Rust encoder, whose failure is reported like one of 'fclose'.
+void close_proof_file(std::unique_ptr<ProofFile> &proof)
+{
+    FILE *file = proof->file;
+    proof->file = NULL;
+    if (file != NULL && fclose(file) != 0)
+    {
+        throw std::runtime_error(std::to_string(errno));
+    }
+}
+
+std::unique_ptr<ProofFile> trace_proof1(std::unique_ptr<Solver> &solver, rust::Slice<const uint8_t> file,
+                                        rust::String name)
+{
+    std::unique_ptr<ProofFile> proof(new ProofFile(_read_file(file, "w+")));
+    if (!solver->trace_proof(proof->file, name.c_str()))
+    {
+        return nullptr;
+    }
+    return proof;
 }
 
-bool trace_proof2(std::unique_ptr<Solver> &solver, rust::String path)
+bool trace_proof2(std::unique_ptr<Solver> &solver, rust::Slice<const uint8_t> path)
 {
-    return solver->trace_proof(path.c_str());
+    errno = 0;
+    bool traced = solver->trace_proof(_path(path).c_str());
+    _throw_errno_if(!traced);
+    return traced;
+}
+
+// The 'FILE' of 'trace_proof_stream', where 's' is the Rust state.
+struct ProofStream
+{
+    uint8_t *s;
+    rust::Fn<bool(uint8_t *, rust::Slice<const uint8_t>)> write;
+    rust::Fn<bool(uint8_t *)> close;
+};
+
+static bool _proof_stream_write(void *cookie, const char *buffer, size_t size)
+{
+    auto stream = static_cast<ProofStream *>(cookie);
+    return stream->write(stream->s, rust::Slice<const uint8_t>(reinterpret_cast<const uint8_t *>(buffer), size));
+}
+
+static int _proof_stream_close(void *cookie)
+{
+    std::unique_ptr<ProofStream> stream(static_cast<ProofStream *>(cookie));
+    if (stream->close(stream->s))
+    {
+        return 0;
+    }
+    errno = EIO;
+    return EOF;
+}
+
+#if defined(__APPLE__) || defined(__FreeBSD__) || defined(__OpenBSD__) || defined(__NetBSD__)
+static int _proof_stream_funopen_write(void *cookie, const char *buffer, int size)
+{
+    if (_proof_stream_write(cookie, buffer, size))
+    {
+        return size;
+    }
+    errno = EIO;
+    return -1;
+}
+#else
+static ssize_t _proof_stream_cookie_write(void *cookie, const char *buffer, size_t size)
+{
+    if (_proof_stream_write(cookie, buffer, size))
+    {
+        return size;
+    }
+    errno = EIO;
+    return 0;
+}
+#endif
+
+std::unique_ptr<ProofFile> trace_proof_stream(std::unique_ptr<Solver> &solver, uint8_t *s,
+                                              rust::Fn<bool(uint8_t *, rust::Slice<const uint8_t>)> write,
+                                              rust::Fn<bool(uint8_t *)> close, rust::String name)
+{
+    ProofStream *stream = new ProofStream{s, write, close};
+#if defined(__APPLE__) || defined(__FreeBSD__) || defined(__OpenBSD__) || defined(__NetBSD__)
+    FILE *fptr = funopen(stream, NULL, _proof_stream_funopen_write, NULL, _proof_stream_close);
+#else
+    cookie_io_functions_t functions = {NULL, _proof_stream_cookie_write, NULL, _proof_stream_close};
+    FILE *fptr = fopencookie(stream, "w", functions);
+#endif
+    if (fptr == NULL)
+    {
+        int error = errno;
+        delete stream;
+        throw std::runtime_error(std::to_string(error));
+    }
+    // from here on closing the file calls 'close'
+    std::unique_ptr<ProofFile> proof(new ProofFile(fptr));
+    if (!solver->trace_proof(proof->file, name.c_str()))
+    {
+        return nullptr;
+    }
+    return proof;
 }
 
 void flush_proof_trace(std::unique_ptr<Solver> &solver, bool print)
@@ -487,9 +641,10 @@ void close_proof_trace(std::unique_ptr<Solver> &solver, bool print)
     return solver->close_proof_trace(print);
 }
 
-void connect_proof_tracer1(std::unique_ptr<Solver> &solver, std::unique_ptr<Tracer> &tracer, bool antecedents)
+void connect_proof_tracer1(std::unique_ptr<Solver> &solver, std::unique_ptr<Tracer> &tracer, bool antecedents,
+                           bool finalize_clauses)
 {
-    return solver->connect_proof_tracer(tracer.get(), antecedents);
+    return solver->connect_proof_tracer(tracer.get(), antecedents, finalize_clauses);
 }
 
 void connect_proof_tracer2(std::unique_ptr<Solver> &solver, std::unique_ptr<InternalTracer> &tracer, bool antecedents)
@@ -497,14 +652,16 @@ void connect_proof_tracer2(std::unique_ptr<Solver> &solver, std::unique_ptr<Inte
     return solver->connect_proof_tracer(tracer.get(), antecedents);
 }
 
-void connect_proof_tracer3(std::unique_ptr<Solver> &solver, std::unique_ptr<StatTracer> &tracer, bool antecedents)
+void connect_proof_tracer3(std::unique_ptr<Solver> &solver, std::unique_ptr<StatTracer> &tracer, bool antecedents,
+                           bool finalize_clauses)
 {
-    return solver->connect_proof_tracer(tracer.get(), antecedents);
+    return solver->connect_proof_tracer(tracer.get(), antecedents, finalize_clauses);
 }
 
-void connect_proof_tracer4(std::unique_ptr<Solver> &solver, std::unique_ptr<FileTracer> &tracer, bool antecedents)
+void connect_proof_tracer4(std::unique_ptr<Solver> &solver, std::unique_ptr<FileTracer> &tracer, bool antecedents,
+                           bool finalize_clauses)
 {
-    return solver->connect_proof_tracer(tracer.get(), antecedents);
+    return solver->connect_proof_tracer(tracer.get(), antecedents, finalize_clauses);
 }
 
 void conclude(std::unique_ptr<Solver> &solver)
@@ -527,6 +684,68 @@ bool disconnect_proof_tracer3(std::unique_ptr<Solver> &solver, std::unique_ptr<F
     return solver->disconnect_proof_tracer(tracer.get());
 }
 
+// Stat tracer counting the proof steps it sees.
+struct CountingTracer : public StatTracer
+{
+    uint64_t original = 0;
+    uint64_t derived = 0;
+    uint64_t deleted = 0;
+    uint64_t weakened = 0;
+    uint64_t strengthened = 0;
+    uint64_t finalized = 0;
+
+    void add_original_clause(uint64_t, bool, const std::vector<int> &, bool = false) override
+    {
+        original++;
+    }
+
+    void add_derived_clause(uint64_t, bool, const std::vector<int> &, const std::vector<uint64_t> &) override
+    {
+        derived++;
+    }
+
+    void add_assumption_clause(uint64_t, const std::vector<int> &, const std::vector<uint64_t> &) override
+    {
+        derived++;
+    }
+
+    void delete_clause(uint64_t, bool, const std::vector<int> &) override
+    {
+        deleted++;
+    }
+
+    void weaken_minus(uint64_t, const std::vector<int> &) override
+    {
+        weakened++;
+    }
+
+    void strengthen(uint64_t) override
+    {
+        strengthened++;
+    }
+
+    void finalize_clause(uint64_t, const std::vector<int> &) override
+    {
+        finalized++;
+    }
+
+    // the counts are read through 'stat_tracer_statistics' instead of printed
+    void print_stats() override {}
+};
+
+std::unique_ptr<StatTracer> new_stat_tracer()
+{
+    return std::unique_ptr<StatTracer>(new CountingTracer());
+}
+
+// In the order of the fields of the Rust 'ProofStatistics'.
+std::array<uint64_t, 6> stat_tracer_statistics(const std::unique_ptr<StatTracer> &tracer)
+{
+    auto counter = static_cast<const CountingTracer *>(tracer.get());
+    return {{counter->original, counter->derived, counter->deleted, counter->weakened, counter->strengthened,
+             counter->finalized}};
+}
+
 void usage()
 {
     return Solver::usage();
@@ -567,7 +786,8 @@ bool traverse_witnesses_forward(const std::unique_ptr<Solver> &solver, std::uniq
     return solver->traverse_witnesses_forward(*i.get());
 }
 
-rust::String read_dimacs1(std::unique_ptr<Solver> &solver, rust::String file, rust::String name, int &vars, int strict)
+rust::String read_dimacs1(std::unique_ptr<Solver> &solver, rust::Slice<const uint8_t> file, rust::String name,
+                          int &vars, int strict)
 {
     FILE *fptr = _read_file(file, "r");
     auto r = solver->read_dimacs(fptr, name.c_str(), vars, strict);
@@ -575,12 +795,17 @@ rust::String read_dimacs1(std::unique_ptr<Solver> &solver, rust::String file, ru
     return _convert_char_to_rust_string(r);
 }
 
-rust::String read_dimacs2(std::unique_ptr<Solver> &solver, rust::String path, int &vars, int strict)
+rust::String read_dimacs2(std::unique_ptr<Solver> &solver, rust::Slice<const uint8_t> path, int &vars, int strict)
 {
-    return _convert_char_to_rust_string(solver->read_dimacs(path.c_str(), vars, strict));
+    std::string name = _path(path);
+    if (_piped(name))
+    {
+        return _convert_char_to_rust_string(solver->read_dimacs(name.c_str(), vars, strict));
+    }
+    return read_dimacs1(solver, path, rust::String(name), vars, strict);
 }
 
-rust::String read_dimacs3(std::unique_ptr<Solver> &solver, rust::String file, rust::String name, int &vars,
+rust::String read_dimacs3(std::unique_ptr<Solver> &solver, rust::Slice<const uint8_t> file, rust::String name, int &vars,
                           int strict, bool &incremental,
                           rust::Vec<int> &cubes)
 {
@@ -593,26 +818,57 @@ rust::String read_dimacs3(std::unique_ptr<Solver> &solver, rust::String file, ru
     return _convert_char_to_rust_string(r);
 }
 
-rust::String read_dimacs4(std::unique_ptr<Solver> &solver, rust::String path, int &vars, int strict,
+rust::String read_dimacs4(std::unique_ptr<Solver> &solver, rust::Slice<const uint8_t> path, int &vars, int strict,
                           bool &incremental, rust::Vec<int> &cubes)
+{
+    std::string name = _path(path);
+    if (!_piped(name))
+    {
+        return read_dimacs3(solver, path, rust::String(name), vars, strict, incremental, cubes);
+    }
+    std::vector<int> internal_cubes;
+    auto r = solver->read_dimacs(name.c_str(), vars, strict, incremental, internal_cubes);
+    _copy_vec_from_cxx_to_rust(internal_cubes, cubes);
+    return _convert_char_to_rust_string(r);
+}
+
+rust::String read_dimacs_buffer1(std::unique_ptr<Solver> &solver, rust::Slice<const uint8_t> buffer, rust::String name,
+                                 int &vars, int strict)
+{
+    FILE *fptr = _read_buffer(buffer);
+    auto r = solver->read_dimacs(fptr, name.c_str(), vars, strict);
+    fclose(fptr);
+    return _convert_char_to_rust_string(r);
+}
+
+rust::String read_dimacs_buffer2(std::unique_ptr<Solver> &solver, rust::Slice<const uint8_t> buffer, rust::String name,
+                                 int &vars, int strict, bool &incremental, rust::Vec<int> &cubes)
 {
     std::vector<int> internal_cubes;
-    auto r = solver->read_dimacs(path.c_str(), vars, strict, incremental, internal_cubes);
+    FILE *fptr = _read_buffer(buffer);
+    auto r = solver->read_dimacs(fptr, name.c_str(), vars, strict, incremental, internal_cubes);
+    fclose(fptr);
     _copy_vec_from_cxx_to_rust(internal_cubes, cubes);
     return _convert_char_to_rust_string(r);
 }
 
-rust::String write_dimacs(std::unique_ptr<Solver> &solver, rust::String path, int min_max_var)
+rust::String write_dimacs(std::unique_ptr<Solver> &solver, rust::Slice<const uint8_t> path, int min_max_var)
 {
-    return _convert_char_to_rust_string(solver->write_dimacs(path.c_str(), min_max_var));
+    errno = 0;
+    const char *r = solver->write_dimacs(_path(path).c_str(), min_max_var);
+    _throw_errno_if(r != nullptr);
+    return _convert_char_to_rust_string(r);
 }
 
-rust::String write_extension(std::unique_ptr<Solver> &solver, rust::String path)
+rust::String write_extension(std::unique_ptr<Solver> &solver, rust::Slice<const uint8_t> path)
 {
-    return _convert_char_to_rust_string(solver->write_extension(path.c_str()));
+    errno = 0;
+    const char *r = solver->write_extension(_path(path).c_str());
+    _throw_errno_if(r != nullptr);
+    return _convert_char_to_rust_string(r);
 }
 
-void build(rust::String file, rust::String prefix)
+void build(rust::Slice<const uint8_t> file, rust::String prefix)
 {
     FILE *fptr = _read_file(file, "w+");
     Solver::build(fptr, prefix.c_str());
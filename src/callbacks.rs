@@ -0,0 +1,620 @@
+//! Plumbing between the Rust call-back traits and the C++ wrappers created by
+//! `ffi::new_*`.
+//!
+//! Every wrapper is handed a pointer to a [`Slot`] rather than to the Rust
+//! object itself.  The trampolines below resolve the slot on each call and
+//! fall back to a neutral answer once it was cleared, which is what allows a
+//! borrowed object to be cut off when its borrow ends.
+//!
+//! Unwinding into the solver is undefined behaviour, so the trampolines also
+//! catch panics.  The first panic is parked in the solver's [`Control`], every
+//! later call-back answers neutrally, the built-in terminator stops the search
+//! and the panic is raised again as soon as the call which ran the call-back
+//! returned to Rust, be it 'solve', 'add' or a 'disconnect_*', or at the
+//! latest when the solver is dropped.  The same terminator also answers the
+//! requests of a [`TerminateHandle`].
+
+use crate::bridge::ffi;
+use crate::{
+    CaDiCal, ClauseIterator, ExternalPropagator, FixedAssignmentListener, Learner, Lit,
+    ProofTracer, TerminateHandle, Terminator, WitnessIterator,
+};
+use cxx::{memory::UniquePtrTarget, UniquePtr};
+use std::{
+    any::{Any, TypeId},
+    panic::{self, AssertUnwindSafe},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex, MutexGuard, PoisonError,
+    },
+};
+
+type Payload = Box<dyn Any + Send>;
+
+/// Locks `mutex`, which is never poisoned since call-backs run inside
+/// '`Control::catch`'.
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// State shared by a solver and all of its call-back wrappers.
+#[derive(Default)]
+pub(crate) struct Control {
+    panic: Mutex<Option<Payload>>,
+    /// Whether 'panic' is set, so that it is checked without locking after
+    /// every call which may run call-backs.
+    panicked: AtomicBool,
+    stop: Arc<AtomicBool>,
+}
+
+impl Control {
+    pub(crate) fn panicked(&self) -> bool {
+        self.panicked.load(Ordering::Acquire)
+    }
+
+    /// Whether the search has to stop, after a panic or on request.
+    pub(crate) fn halted(&self) -> bool {
+        self.panicked() || self.stop.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn terminate_handle(&self) -> TerminateHandle {
+        TerminateHandle::new(Arc::clone(&self.stop))
+    }
+
+    /// Withdraws a termination request once the call it stopped returned.
+    pub(crate) fn clear_stop(&self) {
+        self.stop.store(false, Ordering::Relaxed);
+    }
+
+    /// Runs `f` unless an earlier call-back panicked, parking its panic
+    /// instead of letting it unwind.
+    pub(crate) fn catch<R>(&self, neutral: R, f: impl FnOnce() -> R) -> R {
+        if self.panicked() {
+            return neutral;
+        }
+        match panic::catch_unwind(AssertUnwindSafe(f)) {
+            Ok(result) => result,
+            Err(payload) => {
+                *lock(&self.panic) = Some(payload);
+                self.panicked.store(true, Ordering::Release);
+                neutral
+            }
+        }
+    }
+
+    pub(crate) fn take_panic(&self) -> Option<Payload> {
+        if !self.panicked() {
+            return None;
+        }
+        let mut panic = lock(&self.panic);
+        self.panicked.store(false, Ordering::Release);
+        panic.take()
+    }
+
+    /// Raises the parked panic, if any, now that no solver frames are left.
+    pub(crate) fn resume(&self) {
+        if let Some(payload) = self.take_panic() {
+            panic::resume_unwind(payload);
+        }
+    }
+}
+
+/// The object a binding was made for, null once it was released.
+struct Target(*mut u8);
+
+// SAFETY: The pointee is a call-back object, which is 'Send', and it is only
+// reached while the 'Mutex' of its 'Slot' is locked.
+unsafe impl Send for Target {}
+
+/// Where a C++ call-back wrapper reaches its Rust object.
+///
+/// Clearing the slot cuts the wrapper off, wherever the solver it is connected
+/// to ends up.  The target stays locked while it is called, so clearing it
+/// waits for a call-back running on another thread.
+pub(crate) struct Slot {
+    target: Mutex<Target>,
+    control: Arc<Control>,
+}
+
+impl Slot {
+    pub(crate) fn clear(&self) {
+        lock(&self.target).0 = std::ptr::null_mut();
+    }
+}
+
+pub(crate) type Binding = Arc<Slot>;
+
+pub(crate) fn bind<T>(control: &Arc<Control>, target: &mut T) -> Binding {
+    Arc::new(Slot {
+        target: Mutex::new(Target(std::ptr::from_mut(target).cast::<u8>())),
+        control: Arc::clone(control),
+    })
+}
+
+fn state_of(binding: &Binding) -> *mut u8 {
+    Arc::as_ptr(binding).cast_mut().cast::<u8>()
+}
+
+/// # Safety
+///
+/// `state` must come from `state_of` on a live binding.
+#[allow(clippy::cast_ptr_alignment)] // `state_of` hands out the aligned slot
+unsafe fn slot<'s>(state: *mut u8) -> &'s Slot {
+    unsafe { &*state.cast::<Slot>() }
+}
+
+/// Calls `f` on the object bound at `state`, answering `neutral` once it was
+/// released or after a call-back panicked.
+///
+/// # Safety
+///
+/// `state` must come from `state_of` on a live binding to a `T`.
+unsafe fn call<T, R>(state: *mut u8, neutral: R, f: impl FnOnce(&mut T) -> R) -> R {
+    let slot = unsafe { slot(state) };
+    let target = lock(&slot.target);
+    match unsafe { target.0.cast::<T>().as_mut() } {
+        Some(target) => slot.control.catch(neutral, || f(target)),
+        None => neutral,
+    }
+}
+
+/// Literal passed by the solver, which never passes '0' or '`INT_MIN`' where
+/// a literal is expected.  A violation panics inside '`call`' and is parked.
+fn from_solver(lit: i32) -> Lit {
+    Lit::from_dimacs(lit).expect("solver passes valid literals")
+}
+
+fn slice_from_solver(lits: &[i32]) -> &[Lit] {
+    Lit::slice_from_dimacs(lits).expect("solver passes valid literals")
+}
+
+/// Literal returned to the solver, where '0' stands for none.
+fn to_solver(lit: Option<Lit>) -> i32 {
+    lit.map_or(0, Lit::to_dimacs)
+}
+
+/// The terminator connected whenever no other one is, so that a parked panic
+/// or a termination request always stops the search.
+pub(crate) fn root_terminator(control: &Arc<Control>) -> UniquePtr<ffi::Terminator> {
+    #[allow(clippy::cast_ptr_alignment)] // made from the `Arc<Control>` below
+    fn terminated(state: *mut u8) -> bool {
+        unsafe { &*state.cast_const().cast::<Control>() }.halted()
+    }
+
+    let state = Arc::as_ptr(control).cast_mut().cast::<u8>();
+    unsafe { ffi::new_terminator(state, terminated) }
+}
+
+/// A call-back wrapper held by the solver together with its binding and, for
+/// owned call-backs, the boxed object the binding points into.
+pub(crate) struct Connection<H: UniquePtrTarget, O: ?Sized> {
+    pub(crate) hook: UniquePtr<H>,
+    binding: Binding,
+    owned: Option<Box<Box<O>>>,
+}
+
+impl<H: UniquePtrTarget, O: ?Sized> Connection<H, O> {
+    /// Connection to an object borrowed by the caller.
+    pub(crate) fn borrowed(binding: &Binding, hook: fn(&Binding) -> UniquePtr<H>) -> Self {
+        Self {
+            hook: hook(binding),
+            binding: Arc::clone(binding),
+            owned: None,
+        }
+    }
+
+    /// Connection owning `object`, which stays at a fixed heap address.
+    pub(crate) fn owned(
+        control: &Arc<Control>,
+        object: Box<O>,
+        hook: fn(&Binding) -> UniquePtr<H>,
+    ) -> Self {
+        let mut owned = Box::new(object);
+        let binding = bind(control, &mut *owned);
+        Self {
+            hook: hook(&binding),
+            binding,
+            owned: Some(owned),
+        }
+    }
+
+    pub(crate) fn is_bound_to(connection: Option<&Self>, binding: &Binding) -> bool {
+        connection.is_some_and(|c| Arc::ptr_eq(&c.binding, binding))
+    }
+
+    /// The owned object, which is only called while the solver is borrowed
+    /// mutably.
+    pub(crate) fn object(&self) -> Option<&O> {
+        self.owned.as_deref().map(|owned| &**owned)
+    }
+
+    /// Hands back the owned object, dropping the wrapper first.
+    pub(crate) fn into_owned(self) -> Option<Box<O>> {
+        let Self { hook, owned, .. } = self;
+        drop(hook);
+        owned.map(|owned| *owned)
+    }
+}
+
+/// Token of the hidden '`callback_type_id`' methods, which cannot be named
+/// outside of the crate, so that they are neither called nor overridden.
+#[derive(Debug)]
+pub struct Internal;
+
+/// Lets boxed call-backs handed back by the 'disconnect_*' functions be
+/// downcast to the type they were made from, like '`dyn Error`'.
+macro_rules! downcast {
+    ($($trait:ident),*) => {$(
+        impl dyn $trait {
+            /// Whether the call-back is a 'T'.
+            #[must_use]
+            pub fn is<T: $trait + 'static>(&self) -> bool {
+                $trait::callback_type_id(self, Internal) == TypeId::of::<T>()
+            }
+
+            /// The call-back as the 'T' it was made from, or itself if it
+            /// is not one.
+            ///
+            /// # Errors
+            ///
+            /// The call-back itself unless it is a 'T'.
+            ///
+            pub fn downcast<T: $trait + 'static>(self: Box<Self>) -> Result<Box<T>, Box<Self>> {
+                if self.is::<T>() {
+                    // SAFETY: the object is a 'T', as its type id tells
+                    Ok(unsafe { Box::from_raw(Box::into_raw(self).cast::<T>()) })
+                } else {
+                    Err(self)
+                }
+            }
+        }
+    )*};
+}
+
+downcast!(
+    Terminator,
+    Learner,
+    FixedAssignmentListener,
+    ExternalPropagator,
+    ProofTracer
+);
+
+/// Ends a scoped connection: releases the borrowed object and disconnects its
+/// wrapper, also when the scope unwinds.
+pub(crate) struct Lend<'s> {
+    pub(crate) solver: &'s mut CaDiCal,
+    pub(crate) binding: Binding,
+    pub(crate) release: fn(&mut CaDiCal, &Binding),
+}
+
+impl Drop for Lend<'_> {
+    fn drop(&mut self) {
+        self.binding.clear();
+        (self.release)(self.solver, &self.binding);
+    }
+}
+
+/// Terminator made from a closure by '`connect_terminator_fn`'.
+pub(crate) struct FnTerminator<F>(pub(crate) F);
+
+impl<F: FnMut() -> bool + Send> Terminator for FnTerminator<F> {
+    fn terminated(&mut self) -> bool {
+        (self.0)()
+    }
+}
+
+/// Learner made from a closure by '`connect_learner_fn`', which collects the
+/// literals of each accepted clause and passes the clause on at its end.
+pub(crate) struct FnLearner<F> {
+    max_size: i32,
+    clause: Vec<Lit>,
+    learn: F,
+}
+
+impl<F> FnLearner<F> {
+    pub(crate) fn new(max_size: i32, learn: F) -> Self {
+        Self {
+            max_size,
+            clause: Vec::new(),
+            learn,
+        }
+    }
+}
+
+impl<F: FnMut(&[Lit]) + Send> Learner for FnLearner<F> {
+    fn learning(&mut self, size: i32) -> bool {
+        size <= self.max_size
+    }
+
+    fn learn(&mut self, lit: Option<Lit>) {
+        if let Some(lit) = lit {
+            self.clause.push(lit);
+        } else {
+            (self.learn)(&self.clause);
+            self.clause.clear();
+        }
+    }
+}
+
+/// Listener made from a closure by '`connect_fixed_listener_fn`'.
+pub(crate) struct FnFixedListener<F>(pub(crate) F);
+
+impl<F: FnMut(Lit) + Send> FixedAssignmentListener for FnFixedListener<F> {
+    fn notify_fixed_assignment(&mut self, lit: Lit) {
+        (self.0)(lit);
+    }
+}
+
+pub(crate) fn terminator_hook<T: Terminator>(binding: &Binding) -> UniquePtr<ffi::Terminator> {
+    fn terminated<T: Terminator>(state: *mut u8) -> bool {
+        let terminated = unsafe { call(state, false, T::terminated) };
+        terminated || unsafe { slot(state) }.control.halted()
+    }
+
+    unsafe { ffi::new_terminator(state_of(binding), terminated::<T>) }
+}
+
+pub(crate) fn learner_hook<L: Learner>(binding: &Binding) -> UniquePtr<ffi::Learner> {
+    fn learning<L: Learner>(state: *mut u8, size: i32) -> bool {
+        unsafe { call(state, false, |l: &mut L| l.learning(size)) }
+    }
+
+    fn learn<L: Learner>(state: *mut u8, lit: i32) {
+        unsafe { call(state, (), |l: &mut L| l.learn(Lit::from_dimacs(lit))) };
+    }
+
+    unsafe { ffi::new_learner(state_of(binding), learning::<L>, learn::<L>) }
+}
+
+pub(crate) fn fixed_listener_hook<F: FixedAssignmentListener>(
+    binding: &Binding,
+) -> UniquePtr<ffi::FixedAssignmentListener> {
+    fn notify_fixed_assignment<F: FixedAssignmentListener>(state: *mut u8, lit: i32) {
+        unsafe {
+            call(state, (), |l: &mut F| {
+                l.notify_fixed_assignment(from_solver(lit));
+            });
+        };
+    }
+
+    unsafe { ffi::new_fixed_assignment_listener(state_of(binding), notify_fixed_assignment::<F>) }
+}
+
+/// The propagator's `is_lazy` and `are_reasons_forgettable` flags are read
+/// once, when the wrapper is made.
+pub(crate) fn external_propagator_hook<T: ExternalPropagator>(
+    binding: &Binding,
+) -> UniquePtr<ffi::ExternalPropagator> {
+    fn notify_assignment<T: ExternalPropagator>(state: *mut u8, x: &[i32]) {
+        unsafe {
+            call(state, (), |p: &mut T| {
+                p.notify_assignment(slice_from_solver(x));
+            });
+        };
+    }
+    fn notify_new_decision_level<T: ExternalPropagator>(state: *mut u8) {
+        unsafe { call(state, (), |p: &mut T| p.notify_new_decision_level()) };
+    }
+    fn notify_backtrack<T: ExternalPropagator>(state: *mut u8, x: usize) {
+        unsafe { call(state, (), |p: &mut T| p.notify_backtrack(x)) };
+    }
+    fn cb_check_found_model<T: ExternalPropagator>(state: *mut u8, x: &[i32]) -> bool {
+        unsafe {
+            call(state, true, |p: &mut T| {
+                p.cb_check_found_model(slice_from_solver(x))
+            })
+        }
+    }
+    fn cb_decide<T: ExternalPropagator>(state: *mut u8) -> i32 {
+        unsafe { call(state, 0, |p: &mut T| to_solver(p.cb_decide())) }
+    }
+    fn cb_propagate<T: ExternalPropagator>(state: *mut u8) -> i32 {
+        unsafe { call(state, 0, |p: &mut T| to_solver(p.cb_propagate())) }
+    }
+    fn cb_add_reason_clause_lit<T: ExternalPropagator>(state: *mut u8, x: i32) -> i32 {
+        unsafe {
+            call(state, 0, |p: &mut T| {
+                to_solver(p.cb_add_reason_clause_lit(from_solver(x)))
+            })
+        }
+    }
+    fn cb_has_external_clause<T: ExternalPropagator>(state: *mut u8, x: *mut bool) -> bool {
+        unsafe { call(state, false, |p: &mut T| p.cb_has_external_clause(&mut *x)) }
+    }
+    fn cb_add_external_clause_lit<T: ExternalPropagator>(state: *mut u8) -> i32 {
+        unsafe {
+            call(state, 0, |p: &mut T| {
+                to_solver(p.cb_add_external_clause_lit())
+            })
+        }
+    }
+
+    let state = state_of(binding);
+    let target = lock(&unsafe { slot(state) }.target);
+    let propagator = unsafe { target.0.cast::<T>().as_mut() };
+    let propagator = propagator.expect("binding is set while connecting");
+    let is_lazy = propagator.is_lazy();
+    let are_reasons_forgettable = propagator.are_reasons_forgettable();
+    drop(target);
+
+    unsafe {
+        ffi::new_external_propagator(
+            state,
+            is_lazy,
+            are_reasons_forgettable,
+            notify_assignment::<T>,
+            notify_new_decision_level::<T>,
+            notify_backtrack::<T>,
+            cb_check_found_model::<T>,
+            cb_decide::<T>,
+            cb_propagate::<T>,
+            cb_add_reason_clause_lit::<T>,
+            cb_has_external_clause::<T>,
+            cb_add_external_clause_lit::<T>,
+        )
+    }
+}
+
+#[allow(clippy::too_many_lines)]
+pub(crate) fn tracer_hook<T: ProofTracer>(binding: &Binding) -> UniquePtr<ffi::Tracer> {
+    fn add_original_clause<T: ProofTracer>(
+        state: *mut u8,
+        id: u64,
+        redundant: bool,
+        clause: &[i32],
+        restored: bool,
+    ) {
+        unsafe {
+            call(state, (), |t: &mut T| {
+                t.add_original_clause(id, redundant, slice_from_solver(clause), restored);
+            });
+        }
+    }
+
+    fn add_derived_clause<T: ProofTracer>(
+        state: *mut u8,
+        id: u64,
+        redundant: bool,
+        clause: &[i32],
+        antecedents: &[u64],
+    ) {
+        unsafe {
+            call(state, (), |t: &mut T| {
+                t.add_derived_clause(id, redundant, slice_from_solver(clause), antecedents);
+            });
+        }
+    }
+
+    fn delete_clause<T: ProofTracer>(state: *mut u8, id: u64, redundant: bool, clause: &[i32]) {
+        unsafe {
+            call(state, (), |t: &mut T| {
+                t.delete_clause(id, redundant, slice_from_solver(clause));
+            });
+        }
+    }
+
+    fn weaken_minus<T: ProofTracer>(state: *mut u8, id: u64, clause: &[i32]) {
+        unsafe {
+            call(state, (), |t: &mut T| {
+                t.weaken_minus(id, slice_from_solver(clause));
+            });
+        };
+    }
+
+    fn strengthen<T: ProofTracer>(state: *mut u8, id: u64) {
+        unsafe { call(state, (), |t: &mut T| t.strengthen(id)) };
+    }
+
+    fn finalize_clause<T: ProofTracer>(state: *mut u8, id: u64, clause: &[i32]) {
+        unsafe {
+            call(state, (), |t: &mut T| {
+                t.finalize_clause(id, slice_from_solver(clause));
+            });
+        };
+    }
+
+    fn add_assumption<T: ProofTracer>(state: *mut u8, lit: i32) {
+        unsafe { call(state, (), |t: &mut T| t.add_assumption(from_solver(lit))) };
+    }
+
+    fn add_constraint<T: ProofTracer>(state: *mut u8, clause: &[i32]) {
+        unsafe {
+            call(state, (), |t: &mut T| {
+                t.add_constraint(slice_from_solver(clause));
+            });
+        };
+    }
+
+    fn reset_assumptions<T: ProofTracer>(state: *mut u8) {
+        unsafe { call(state, (), |t: &mut T| t.reset_assumptions()) };
+    }
+
+    fn add_assumption_clause<T: ProofTracer>(
+        state: *mut u8,
+        id: u64,
+        clause: &[i32],
+        antecedents: &[u64],
+    ) {
+        unsafe {
+            call(state, (), |t: &mut T| {
+                t.add_assumption_clause(id, slice_from_solver(clause), antecedents);
+            });
+        }
+    }
+
+    fn conclude_sat<T: ProofTracer>(state: *mut u8, conclusion_type: i32, model: &[i32]) {
+        unsafe {
+            call(state, (), |t: &mut T| {
+                t.conclude_sat(conclusion_type, slice_from_solver(model));
+            });
+        }
+    }
+
+    fn conclude_unsat<T: ProofTracer>(state: *mut u8, conclusion_type: i32, clause_ids: &[u64]) {
+        unsafe {
+            call(state, (), |t: &mut T| {
+                t.conclude_unsat(conclusion_type, clause_ids);
+            });
+        }
+    }
+
+    fn conclude_unknown<T: ProofTracer>(state: *mut u8, trail: &[i32]) {
+        unsafe {
+            call(state, (), |t: &mut T| {
+                t.conclude_unknown(slice_from_solver(trail));
+            });
+        };
+    }
+
+    unsafe {
+        ffi::new_tracer(
+            state_of(binding),
+            add_original_clause::<T>,
+            add_derived_clause::<T>,
+            delete_clause::<T>,
+            weaken_minus::<T>,
+            strengthen::<T>,
+            finalize_clause::<T>,
+            add_assumption::<T>,
+            add_constraint::<T>,
+            reset_assumptions::<T>,
+            add_assumption_clause::<T>,
+            conclude_sat::<T>,
+            conclude_unsat::<T>,
+            conclude_unknown::<T>,
+        )
+    }
+}
+
+pub(crate) fn clause_iterator_hook<I: ClauseIterator>(
+    binding: &Binding,
+) -> UniquePtr<ffi::ClauseIterator> {
+    fn clause<I: ClauseIterator>(state: *mut u8, clause: &[i32]) -> bool {
+        unsafe {
+            call(state, false, |i: &mut I| {
+                i.clause(slice_from_solver(clause))
+            })
+        }
+    }
+
+    unsafe { ffi::new_clause_iterator(state_of(binding), clause::<I>) }
+}
+
+pub(crate) fn witness_iterator_hook<I: WitnessIterator>(
+    binding: &Binding,
+) -> UniquePtr<ffi::WitnessIterator> {
+    fn witness<I: WitnessIterator>(
+        state: *mut u8,
+        clause: &[i32],
+        witness: &[i32],
+        id: u64,
+    ) -> bool {
+        unsafe {
+            call(state, false, |i: &mut I| {
+                i.witness(slice_from_solver(clause), slice_from_solver(witness), id)
+            })
+        }
+    }
+
+    unsafe { ffi::new_witness_iterator(state_of(binding), witness::<I>) }
+}
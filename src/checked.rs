@@ -0,0 +1,406 @@
+use crate::{CaDiCal, Error, Literal, ProofTracer, State, Status, TracerId};
+use std::path::Path;
+
+/// View of a '`CaDiCal`' which checks the API contracts of `CaDiCaL` before
+/// each call.  Where `CaDiCaL` would abort the process, the functions here
+/// return '`Error::ContractViolation`', '`Error::InvalidLiteral`' or
+/// '`Error::NotFrozen`' and leave the solver untouched.
+///
+/// Obtained through '`CaDiCal::checked`'.
+pub struct Checked<'s> {
+    solver: &'s mut CaDiCal,
+}
+
+impl<'s> Checked<'s> {
+    pub(crate) fn new(solver: &'s mut CaDiCal) -> Self {
+        Self { solver }
+    }
+
+    fn require(&self, function: &'static str, required: State) -> Result<(), Error> {
+        let actual = self.solver.state();
+        if !actual.intersects(required) {
+            return Err(Error::ContractViolation {
+                function,
+                required,
+                actual,
+            });
+        }
+        Ok(())
+    }
+
+    /// Literals must be different from '`INT_MIN`' and, unless 'zero' is
+    /// allowed, from '0'.
+    fn require_lit(function: &'static str, lit: i32, zero: bool) -> Result<(), Error> {
+        if lit == i32::MIN || (lit == 0 && !zero) {
+            return Err(Error::InvalidLiteral { function, lit });
+        }
+        Ok(())
+    }
+
+    /// See '`CaDiCal::add`'.
+    ///
+    /// # Errors
+    ///
+    /// Unless in state 'VALID' and 'lit' is different from '`INT_MIN`'.
+    ///
+    #[inline]
+    pub fn add(&mut self, lit: impl Literal) -> Result<(), Error> {
+        let lit = lit.raw();
+        self.require("add", State::VALID)?;
+        Self::require_lit("add", lit, true)?;
+        self.solver.add(lit);
+        Ok(())
+    }
+
+    /// Adds all literals of 'clause' followed by a zero, see
+    /// '`CaDiCal::clause6`'.
+    ///
+    /// # Errors
+    ///
+    /// Unless in state 'VALID' and all literals are valid.  Nothing is added
+    /// then.
+    ///
+    #[inline]
+    pub fn clause<L: Literal>(&mut self, clause: &[L]) -> Result<(), Error> {
+        self.require("clause", State::VALID)?;
+        for &lit in clause {
+            Self::require_lit("clause", lit.raw(), false)?;
+        }
+        self.solver.clause6(clause);
+        Ok(())
+    }
+
+    /// See '`CaDiCal::assume`'.
+    ///
+    /// # Errors
+    ///
+    /// Unless in state 'READY' and 'lit' is valid.
+    ///
+    #[inline]
+    pub fn assume(&mut self, lit: impl Literal) -> Result<(), Error> {
+        let lit = lit.raw();
+        self.require("assume", State::READY)?;
+        Self::require_lit("assume", lit, false)?;
+        self.solver.assume(lit);
+        Ok(())
+    }
+
+    /// See '`CaDiCal::try_solve`'.
+    ///
+    /// # Errors
+    ///
+    /// Unless in state 'READY', and the errors of '`CaDiCal::try_solve`'.
+    ///
+    #[inline]
+    pub fn solve(&mut self) -> Result<Status, Error> {
+        self.require("solve", State::READY)?;
+        self.solver.try_solve()
+    }
+
+    /// See '`CaDiCal::try_simplify`'.
+    ///
+    /// # Errors
+    ///
+    /// Unless in state 'READY', and the errors of '`CaDiCal::try_simplify`'.
+    ///
+    #[inline]
+    pub fn simplify(&mut self, rounds: i32) -> Result<Status, Error> {
+        self.require("simplify", State::READY)?;
+        self.solver.try_simplify(rounds)
+    }
+
+    /// See '`CaDiCal::lookahead`'.
+    ///
+    /// # Errors
+    ///
+    /// Unless in state 'READY'.
+    ///
+    #[inline]
+    pub fn lookahead(&mut self) -> Result<i32, Error> {
+        self.require("lookahead", State::READY)?;
+        Ok(self.solver.lookahead())
+    }
+
+    /// See '`CaDiCal::val`'.
+    ///
+    /// # Errors
+    ///
+    /// Unless in state 'SATISFIED' and 'lit' is valid.
+    ///
+    #[inline]
+    pub fn val(&mut self, lit: impl Literal) -> Result<i32, Error> {
+        let lit = lit.raw();
+        self.require("val", State::SATISFIED)?;
+        Self::require_lit("val", lit, false)?;
+        Ok(self.solver.val(lit))
+    }
+
+    /// See '`CaDiCal::value`'.
+    ///
+    /// # Errors
+    ///
+    /// Unless in state 'SATISFIED' and 'lit' is valid.
+    ///
+    #[inline]
+    pub fn value(&mut self, lit: impl Literal) -> Result<bool, Error> {
+        let lit = lit.raw();
+        self.require("value", State::SATISFIED)?;
+        Self::require_lit("value", lit, false)?;
+        Ok(self.solver.value(lit))
+    }
+
+    /// See '`CaDiCal::flip`'.
+    ///
+    /// # Errors
+    ///
+    /// Unless in state 'SATISFIED' and 'lit' is valid.
+    ///
+    #[inline]
+    pub fn flip(&mut self, lit: impl Literal) -> Result<bool, Error> {
+        let lit = lit.raw();
+        self.require("flip", State::SATISFIED)?;
+        Self::require_lit("flip", lit, false)?;
+        Ok(self.solver.flip(lit))
+    }
+
+    /// See '`CaDiCal::flippable`'.
+    ///
+    /// # Errors
+    ///
+    /// Unless in state 'SATISFIED' and 'lit' is valid.
+    ///
+    #[inline]
+    pub fn flippable(&mut self, lit: impl Literal) -> Result<bool, Error> {
+        let lit = lit.raw();
+        self.require("flippable", State::SATISFIED)?;
+        Self::require_lit("flippable", lit, false)?;
+        Ok(self.solver.flippable(lit))
+    }
+
+    /// See '`CaDiCal::failed`'.
+    ///
+    /// # Errors
+    ///
+    /// Unless in state 'UNSATISFIED' and 'lit' is valid.
+    ///
+    #[inline]
+    pub fn failed(&mut self, lit: impl Literal) -> Result<bool, Error> {
+        let lit = lit.raw();
+        self.require("failed", State::UNSATISFIED)?;
+        Self::require_lit("failed", lit, false)?;
+        Ok(self.solver.failed(lit))
+    }
+
+    /// See '`CaDiCal::constrain`'.
+    ///
+    /// # Errors
+    ///
+    /// Unless in state 'VALID' and 'lit' is different from '`INT_MIN`'.
+    ///
+    #[inline]
+    pub fn constrain(&mut self, lit: impl Literal) -> Result<(), Error> {
+        let lit = lit.raw();
+        self.require("constrain", State::VALID)?;
+        Self::require_lit("constrain", lit, true)?;
+        self.solver.constrain(lit);
+        Ok(())
+    }
+
+    /// See '`CaDiCal::constraint_failed`'.
+    ///
+    /// # Errors
+    ///
+    /// Unless in state 'UNSATISFIED'.
+    ///
+    #[inline]
+    pub fn constraint_failed(&mut self) -> Result<bool, Error> {
+        self.require("constraint_failed", State::UNSATISFIED)?;
+        Ok(self.solver.constraint_failed())
+    }
+
+    /// See '`CaDiCal::frozen`'.
+    ///
+    /// # Errors
+    ///
+    /// Unless in state 'VALID' and 'lit' is valid.
+    ///
+    #[inline]
+    pub fn frozen(&self, lit: impl Literal) -> Result<bool, Error> {
+        let lit = lit.raw();
+        self.require("frozen", State::VALID)?;
+        Self::require_lit("frozen", lit, false)?;
+        Ok(self.solver.frozen(lit))
+    }
+
+    /// See '`CaDiCal::freeze`'.
+    ///
+    /// # Errors
+    ///
+    /// Unless in state 'VALID' and 'lit' is valid.
+    ///
+    #[inline]
+    pub fn freeze(&mut self, lit: impl Literal) -> Result<(), Error> {
+        let lit = lit.raw();
+        self.require("freeze", State::VALID)?;
+        Self::require_lit("freeze", lit, false)?;
+        self.solver.freeze(lit);
+        Ok(())
+    }
+
+    /// See '`CaDiCal::melt`'.
+    ///
+    /// # Errors
+    ///
+    /// Unless in state 'VALID', 'lit' is valid and frozen.
+    ///
+    #[inline]
+    pub fn melt(&mut self, lit: impl Literal) -> Result<(), Error> {
+        let lit = lit.raw();
+        self.require("melt", State::VALID)?;
+        Self::require_lit("melt", lit, false)?;
+        if !self.solver.frozen(lit) {
+            return Err(Error::NotFrozen(lit));
+        }
+        self.solver.melt(lit);
+        Ok(())
+    }
+
+    /// See '`CaDiCal::fixed`'.
+    ///
+    /// # Errors
+    ///
+    /// Unless in state 'VALID' and 'lit' is valid.
+    ///
+    #[inline]
+    pub fn fixed(&self, lit: impl Literal) -> Result<i32, Error> {
+        let lit = lit.raw();
+        self.require("fixed", State::VALID)?;
+        Self::require_lit("fixed", lit, false)?;
+        Ok(self.solver.fixed(lit))
+    }
+
+    /// See '`CaDiCal::phase`'.
+    ///
+    /// # Errors
+    ///
+    /// Unless in state 'VALID' and 'lit' is valid.
+    ///
+    #[inline]
+    pub fn phase(&mut self, lit: impl Literal) -> Result<(), Error> {
+        let lit = lit.raw();
+        self.require("phase", State::VALID)?;
+        Self::require_lit("phase", lit, false)?;
+        self.solver.phase(lit);
+        Ok(())
+    }
+
+    /// See '`CaDiCal::unphase`'.
+    ///
+    /// # Errors
+    ///
+    /// Unless in state 'VALID' and 'lit' is valid.
+    ///
+    #[inline]
+    pub fn unphase(&mut self, lit: impl Literal) -> Result<(), Error> {
+        let lit = lit.raw();
+        self.require("unphase", State::VALID)?;
+        Self::require_lit("unphase", lit, false)?;
+        self.solver.unphase(lit);
+        Ok(())
+    }
+
+    /// See '`CaDiCal::reserve`'.
+    ///
+    /// # Errors
+    ///
+    /// Unless in state 'READY'.
+    ///
+    #[inline]
+    pub fn reserve(&mut self, min_max_var: i32) -> Result<(), Error> {
+        self.require("reserve", State::READY)?;
+        self.solver.reserve(min_max_var);
+        Ok(())
+    }
+
+    /// See '`CaDiCal::try_set`'.
+    ///
+    /// # Errors
+    ///
+    /// Unless in state 'CONFIGURING', and the errors of '`CaDiCal::try_set`'.
+    ///
+    #[inline]
+    pub fn set(&mut self, name: String, val: i32) -> Result<(), Error> {
+        self.require("set", State::CONFIGURING)?;
+        self.solver.try_set(name, val)
+    }
+
+    /// See '`CaDiCal::try_configure`'.
+    ///
+    /// # Errors
+    ///
+    /// Unless in state 'CONFIGURING', and the errors of
+    /// '`CaDiCal::try_configure`'.
+    ///
+    #[inline]
+    pub fn configure(&mut self, name: String) -> Result<(), Error> {
+        self.require("configure", State::CONFIGURING)?;
+        self.solver.try_configure(name)
+    }
+
+    /// See '`CaDiCal::try_limit`'.
+    ///
+    /// # Errors
+    ///
+    /// Unless in state 'READY', and the errors of '`CaDiCal::try_limit`'.
+    ///
+    #[inline]
+    pub fn limit(&mut self, arg: String, val: i32) -> Result<(), Error> {
+        self.require("limit", State::READY)?;
+        self.solver.try_limit(arg, val)
+    }
+
+    /// See '`CaDiCal::try_trace_proof1`'.
+    ///
+    /// # Errors
+    ///
+    /// Unless in state 'CONFIGURING', and the errors of
+    /// '`CaDiCal::try_trace_proof1`'.
+    ///
+    #[inline]
+    pub fn trace_proof1(&mut self, file: impl AsRef<Path>, name: String) -> Result<(), Error> {
+        self.require("trace_proof", State::CONFIGURING)?;
+        self.solver.try_trace_proof1(file, name)
+    }
+
+    /// See '`CaDiCal::try_trace_proof2`'.
+    ///
+    /// # Errors
+    ///
+    /// Unless in state 'CONFIGURING', and the errors of
+    /// '`CaDiCal::try_trace_proof2`'.
+    ///
+    #[inline]
+    pub fn trace_proof2(&mut self, path: impl AsRef<Path>) -> Result<(), Error> {
+        self.require("trace_proof", State::CONFIGURING)?;
+        self.solver.try_trace_proof2(path)
+    }
+
+    /// See '`CaDiCal::connect_proof_tracer1`'.
+    ///
+    /// # Errors
+    ///
+    /// Unless in state 'CONFIGURING'.  The tracer is dropped then.
+    ///
+    #[inline]
+    pub fn connect_proof_tracer1(
+        &mut self,
+        tracer: Box<dyn ProofTracer>,
+        antecedents: bool,
+        finalize: bool,
+    ) -> Result<TracerId, Error> {
+        self.require("connect_proof_tracer", State::CONFIGURING)?;
+        Ok(self
+            .solver
+            .connect_proof_tracer1(tracer, antecedents, finalize))
+    }
+}
@@ -0,0 +1,217 @@
+//! Compressed files handled in Rust instead of through the external 'gzip',
+//! 'xz' and 'bzip2' helpers which `CaDiCaL` spawns through pipes.  Each codec
+//! is behind the cargo feature of the same name.
+
+use crate::Error;
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader, BufWriter, Read, Write},
+    path::Path,
+};
+
+/// The codec of a formula, extension or proof file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[non_exhaustive]
+pub enum Compression {
+    /// Plain text, or binary proofs, without compression.
+    #[default]
+    None,
+    #[cfg(feature = "gzip")]
+    Gzip,
+    #[cfg(feature = "xz")]
+    Xz,
+    #[cfg(feature = "bzip2")]
+    Bzip2,
+    #[cfg(feature = "zstd")]
+    Zstd,
+}
+
+impl Compression {
+    /// The codec of an enabled feature matching the extension of 'path',
+    /// that is '.gz', '.xz', '.bz2' or '.zst', and '`Compression::None`'
+    /// otherwise.
+    #[must_use]
+    pub fn from_path(path: &Path) -> Self {
+        let Some(extension) = path.extension().and_then(|extension| extension.to_str()) else {
+            return Compression::None;
+        };
+        match extension {
+            #[cfg(feature = "gzip")]
+            "gz" => Compression::Gzip,
+            #[cfg(feature = "xz")]
+            "xz" => Compression::Xz,
+            #[cfg(feature = "bzip2")]
+            "bz2" => Compression::Bzip2,
+            #[cfg(feature = "zstd")]
+            "zst" => Compression::Zstd,
+            _ => Compression::None,
+        }
+    }
+
+    /// Decompresses 'reader', accepting concatenated streams.
+    ///
+    /// # Errors
+    ///
+    /// If the decoder cannot be initialized.
+    ///
+    pub fn decoder<'a, R: Read + 'a>(self, reader: R) -> io::Result<Box<dyn Read + 'a>> {
+        Ok(match self {
+            Compression::None => Box::new(reader),
+            #[cfg(feature = "gzip")]
+            Compression::Gzip => Box::new(flate2::read::MultiGzDecoder::new(reader)),
+            #[cfg(feature = "xz")]
+            Compression::Xz => Box::new(xz2::read::XzDecoder::new_multi_decoder(reader)),
+            #[cfg(feature = "bzip2")]
+            Compression::Bzip2 => Box::new(bzip2::read::MultiBzDecoder::new(reader)),
+            #[cfg(feature = "zstd")]
+            Compression::Zstd => Box::new(zstd::stream::read::Decoder::new(reader)?),
+        })
+    }
+
+    /// Compresses into 'writer' with the default level of the codec.
+    ///
+    /// # Errors
+    ///
+    /// If the encoder cannot be initialized.
+    ///
+    pub fn encoder<W: Write>(self, writer: W) -> io::Result<Encoder<W>> {
+        Ok(Encoder(match self {
+            Compression::None => Inner::None(writer),
+            #[cfg(feature = "gzip")]
+            Compression::Gzip => Inner::Gzip(flate2::write::GzEncoder::new(
+                writer,
+                flate2::Compression::default(),
+            )),
+            #[cfg(feature = "xz")]
+            Compression::Xz => Inner::Xz(xz2::write::XzEncoder::new(writer, 6)),
+            #[cfg(feature = "bzip2")]
+            Compression::Bzip2 => Inner::Bzip2(bzip2::write::BzEncoder::new(
+                writer,
+                bzip2::Compression::default(),
+            )),
+            #[cfg(feature = "zstd")]
+            Compression::Zstd => Inner::Zstd(zstd::stream::write::Encoder::new(writer, 0)?),
+        }))
+    }
+}
+
+/// A writer compressing with a '`Compression`'.  The compressed stream is
+/// only complete after '`Encoder::finish`'.
+pub struct Encoder<W: Write>(Inner<W>);
+
+enum Inner<W: Write> {
+    None(W),
+    #[cfg(feature = "gzip")]
+    Gzip(flate2::write::GzEncoder<W>),
+    #[cfg(feature = "xz")]
+    Xz(xz2::write::XzEncoder<W>),
+    #[cfg(feature = "bzip2")]
+    Bzip2(bzip2::write::BzEncoder<W>),
+    #[cfg(feature = "zstd")]
+    Zstd(zstd::stream::write::Encoder<'static, W>),
+}
+
+impl<W: Write> Encoder<W> {
+    fn writer(&mut self) -> &mut dyn Write {
+        match &mut self.0 {
+            Inner::None(writer) => writer,
+            #[cfg(feature = "gzip")]
+            Inner::Gzip(encoder) => encoder,
+            #[cfg(feature = "xz")]
+            Inner::Xz(encoder) => encoder,
+            #[cfg(feature = "bzip2")]
+            Inner::Bzip2(encoder) => encoder,
+            #[cfg(feature = "zstd")]
+            Inner::Zstd(encoder) => encoder,
+        }
+    }
+
+    /// Writes the end of the compressed stream and flushes the writer.
+    ///
+    /// # Errors
+    ///
+    /// If writing fails.
+    ///
+    // without features only plain writers remain
+    #[allow(clippy::infallible_destructuring_match)]
+    pub fn finish(self) -> io::Result<W> {
+        let mut writer = match self.0 {
+            Inner::None(writer) => writer,
+            #[cfg(feature = "gzip")]
+            Inner::Gzip(encoder) => encoder.finish()?,
+            #[cfg(feature = "xz")]
+            Inner::Xz(encoder) => encoder.finish()?,
+            #[cfg(feature = "bzip2")]
+            Inner::Bzip2(encoder) => encoder.finish()?,
+            #[cfg(feature = "zstd")]
+            Inner::Zstd(encoder) => encoder.finish()?,
+        };
+        writer.flush()?;
+        Ok(writer)
+    }
+}
+
+impl<W: Write> Write for Encoder<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.writer().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer().flush()
+    }
+}
+
+/// The decompressed contents of 'path', 'None' if its extension does not
+/// call for one of the enabled codecs and `CaDiCaL` should read it.
+pub(crate) fn decompressed(path: &Path) -> Result<Option<Vec<u8>>, Error> {
+    let compression = Compression::from_path(path);
+    if compression == Compression::None {
+        Ok(None)
+    } else {
+        read(path, compression).map(Some)
+    }
+}
+
+/// A reader of 'path' which decompresses as '`decompressed`'.
+pub(crate) fn reader(path: &Path) -> Result<Box<dyn BufRead>, Error> {
+    if let Some(buffer) = decompressed(path)? {
+        return Ok(Box::new(io::Cursor::new(buffer)));
+    }
+    let file = File::open(path).map_err(|source| Error::open(path, source))?;
+    Ok(Box::new(BufReader::new(file)))
+}
+
+pub(crate) fn read(path: &Path, compression: Compression) -> Result<Vec<u8>, Error> {
+    let file = File::open(path).map_err(|source| Error::open(path, source))?;
+    let mut buffer = Vec::new();
+    compression
+        .decoder(BufReader::new(file))?
+        .read_to_end(&mut buffer)?;
+    Ok(buffer)
+}
+
+/// Creates 'path' for writing through 'compression'.
+pub(crate) fn create(
+    path: &Path,
+    compression: Compression,
+) -> Result<Encoder<BufWriter<File>>, Error> {
+    let file = File::create(path).map_err(|source| Error::open(path, source))?;
+    Ok(compression.encoder(BufWriter::new(file))?)
+}
+
+/// The state of a proof written by `CaDiCaL` to a 'FILE' whose writes end
+/// up in an '`Encoder`', see '`ffi::trace_proof_stream`'.  The state is
+/// created by '`Box::into_raw`' and freed by '`proof_stream_close`'.
+pub(crate) type ProofStream = Encoder<BufWriter<File>>;
+
+pub(crate) fn proof_stream_write(s: *mut u8, data: &[u8]) -> bool {
+    #[allow(clippy::cast_ptr_alignment)]
+    let stream = unsafe { &mut *s.cast::<ProofStream>() };
+    stream.write_all(data).is_ok()
+}
+
+pub(crate) fn proof_stream_close(s: *mut u8) -> bool {
+    #[allow(clippy::cast_ptr_alignment)]
+    let stream = unsafe { Box::from_raw(s.cast::<ProofStream>()) };
+    stream.finish().is_ok()
+}
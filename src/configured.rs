@@ -0,0 +1,361 @@
+use crate::{
+    proof::{FileTracer, StatTracer},
+    CaDiCal, Checked, Compression, Error, ExternalPropagator, FixedAssignmentListener, Learner,
+    Lit, Literal, ProofTracer, Status, Terminator, TracerId, Typed, Variable,
+};
+use std::{
+    io::{Read, Write},
+    ops::Deref,
+    path::Path,
+};
+
+/// Forwards functions which `CaDiCaL` accepts after 'CONFIGURING' to the
+/// wrapped solver.
+macro_rules! forward {
+    ($(
+        $(#[$attr:meta])*
+        fn $name:ident $([$($generics:tt)*])? (&mut self $(, $arg:ident: $ty:ty)*) $(-> $ret:ty)?;
+    )*) => {$(
+        #[doc = concat!("See '`CaDiCal::", stringify!($name), "`'.")]
+        $(#[$attr])*
+        #[inline]
+        pub fn $name $(<$($generics)*>)? (&mut self $(, $arg: $ty)*) $(-> $ret)? {
+            self.solver.$name($($arg),*)
+        }
+    )*};
+}
+
+/// A '`CaDiCal`' which has left state 'CONFIGURING', as built by
+/// '`SolverBuilder::build`'.  It offers everything of '`CaDiCal`' except
+/// the functions which require 'CONFIGURING', that is setting options and
+/// configurations, tracing proofs and connecting proof tracers, so that
+/// calling them too late (and thus writing partial proofs or aborting) does
+/// not type check.  Functions taking '`&CaDiCal`' are reached through
+/// 'Deref', the scoped call-backs lend the '`Configured`' itself.
+///
+/// ```rust
+/// use cadical_sys::{SolverBuilder, Status};
+///
+/// let mut solver = SolverBuilder::new().build().expect("valid configuration");
+/// solver.clause2(1, 2);
+/// assert_eq!(solver.solve(), Status::SATISFIABLE);
+/// ```
+///
+/// ```compile_fail
+/// let mut solver = cadical_sys::SolverBuilder::new().build().unwrap();
+/// solver.set("elim".to_string(), 0);
+/// ```
+///
+#[repr(transparent)]
+pub struct Configured {
+    solver: CaDiCal,
+}
+
+impl Deref for Configured {
+    type Target = CaDiCal;
+
+    fn deref(&self) -> &CaDiCal {
+        &self.solver
+    }
+}
+
+impl Configured {
+    pub(crate) fn new(solver: CaDiCal) -> Self {
+        Self { solver }
+    }
+
+    fn view<R>(solver: &mut CaDiCal, f: impl FnOnce(&mut Configured) -> R) -> R {
+        // SAFETY: 'Configured' is a transparent wrapper of 'CaDiCal'
+        f(unsafe { &mut *std::ptr::from_mut(solver).cast::<Configured>() })
+    }
+
+    /// Hands back the solver, for instance to pass it to functions taking
+    /// '`&mut CaDiCal`'.  The functions requiring 'CONFIGURING' can be
+    /// called again then and abort as described there.
+    #[must_use]
+    #[inline]
+    pub fn into_inner(self) -> CaDiCal {
+        self.solver
+    }
+
+    /// See '`CaDiCal::with_terminator`'.
+    pub fn with_terminator<T: Terminator, R>(
+        &mut self,
+        terminator: &mut T,
+        f: impl FnOnce(&mut Self) -> R,
+    ) -> R {
+        self.solver
+            .with_terminator(terminator, |solver| Self::view(solver, f))
+    }
+
+    /// See '`CaDiCal::with_learner`'.
+    pub fn with_learner<L: Learner, R>(
+        &mut self,
+        learner: &mut L,
+        f: impl FnOnce(&mut Self) -> R,
+    ) -> R {
+        self.solver
+            .with_learner(learner, |solver| Self::view(solver, f))
+    }
+
+    /// See '`CaDiCal::with_fixed_listener`'.
+    pub fn with_fixed_listener<F: FixedAssignmentListener, R>(
+        &mut self,
+        fixed_listener: &mut F,
+        f: impl FnOnce(&mut Self) -> R,
+    ) -> R {
+        self.solver
+            .with_fixed_listener(fixed_listener, |solver| Self::view(solver, f))
+    }
+
+    /// See '`CaDiCal::with_external_propagator`'.
+    pub fn with_external_propagator<T: ExternalPropagator, R>(
+        &mut self,
+        propagator: &mut T,
+        f: impl FnOnce(&mut Self) -> R,
+    ) -> R {
+        self.solver
+            .with_external_propagator(propagator, |solver| Self::view(solver, f))
+    }
+
+    forward! {
+        fn add(&mut self, lit: impl Literal);
+        fn clause1[L: Literal](&mut self, l1: L);
+        fn clause2[L: Literal](&mut self, l1: L, l2: L);
+        fn clause3[L: Literal](&mut self, l1: L, l2: L, l3: L);
+        fn clause4[L: Literal](&mut self, l1: L, l2: L, l3: L, l4: L);
+        fn clause5[L: Literal](&mut self, l1: L, l2: L, l3: L, l4: L, l5: L);
+        fn clause6[L: Literal](&mut self, v: &[L]);
+        fn inconsistent(&mut self) -> bool;
+        fn assume(&mut self, lit: impl Literal);
+        fn solve(&mut self) -> Status;
+        ///
+        /// # Errors
+        ///
+        /// Those of '`CaDiCal::try_solve`'.
+        ///
+        fn try_solve(&mut self) -> Result<Status, Error>;
+        fn val(&mut self, lit: impl Literal) -> i32;
+        fn value(&mut self, lit: impl Literal) -> bool;
+        fn flip(&mut self, lit: impl Literal) -> bool;
+        fn flippable(&mut self, lit: impl Literal) -> bool;
+        fn failed(&mut self, lit: impl Literal) -> bool;
+        fn connect_terminator(&mut self, terminator: Box<dyn Terminator>);
+        fn connect_terminator_fn(&mut self, terminated: impl FnMut() -> bool + Send + 'static);
+        fn disconnect_terminator(&mut self) -> Option<Box<dyn Terminator>>;
+        fn connect_learner(&mut self, learner: Box<dyn Learner>);
+        fn connect_learner_fn(
+            &mut self,
+            max_size: i32,
+            learn: impl FnMut(&[Lit]) + Send + 'static
+        );
+        fn disconnect_learner(&mut self) -> Option<Box<dyn Learner>>;
+        fn connect_fixed_listener(&mut self, fixed_listener: Box<dyn FixedAssignmentListener>);
+        fn connect_fixed_listener_fn(&mut self, notify: impl FnMut(Lit) + Send + 'static);
+        fn disconnect_fixed_listener(&mut self) -> Option<Box<dyn FixedAssignmentListener>>;
+        fn connect_external_propagator(&mut self, propagator: Box<dyn ExternalPropagator>);
+        fn disconnect_external_propagator(&mut self) -> Option<Box<dyn ExternalPropagator>>;
+        fn add_observed_var(&mut self, var: impl Variable);
+        fn remove_observed_var(&mut self, var: impl Variable);
+        fn reset_observed_vars(&mut self);
+        fn is_decision(&mut self, lit: impl Literal) -> bool;
+        fn force_backtrack(&mut self, new_level: usize);
+        fn constrain(&mut self, lit: impl Literal);
+        fn constraint_failed(&mut self) -> bool;
+        fn lookahead(&mut self) -> i32;
+        fn generate_cubes(
+            &mut self,
+            x: i32,
+            min_depth: i32,
+            result_cubes: &mut Vec<i32>
+        ) -> i32;
+        fn reset_assumptions(&mut self);
+        fn reset_constraint(&mut self);
+        fn checked(&mut self) -> Checked<'_>;
+        fn typed(&mut self) -> Typed<'_>;
+        fn vars(&mut self) -> i32;
+        fn reserve(&mut self, min_max_var: i32);
+        fn get(&mut self, name: String) -> i32;
+        fn prefix(&mut self, verbose_message_prefix: String);
+        fn optimize(&mut self, val: i32);
+        fn limit(&mut self, arg: String, val: i32) -> bool;
+        ///
+        /// # Errors
+        ///
+        /// Those of '`CaDiCal::try_limit`'.
+        ///
+        fn try_limit(&mut self, arg: String, val: i32) -> Result<(), Error>;
+        fn is_valid_limit(&mut self, arg: String) -> bool;
+        fn simplify(&mut self, rounds: i32) -> Status;
+        ///
+        /// # Errors
+        ///
+        /// Those of '`CaDiCal::try_simplify`'.
+        ///
+        fn try_simplify(&mut self, rounds: i32) -> Result<Status, Error>;
+        fn terminate(&mut self);
+        fn freeze(&mut self, lit: impl Literal);
+        fn melt(&mut self, lit: impl Literal);
+        fn phase(&mut self, lit: impl Literal);
+        fn unphase(&mut self, lit: impl Literal);
+        fn flush_proof_trace(&mut self, print: bool);
+        fn close_proof_trace(&mut self, print: bool);
+        ///
+        /// # Errors
+        ///
+        /// Those of '`CaDiCal::try_close_proof_trace`'.
+        ///
+        fn try_close_proof_trace(&mut self, print: bool) -> Result<(), Error>;
+        fn conclude(&mut self);
+        fn disconnect_proof_tracer1(&mut self, id: TracerId) -> Option<Box<dyn ProofTracer>>;
+        fn disconnect_stat_tracer(&mut self, id: TracerId) -> Option<StatTracer>;
+        fn disconnect_file_tracer(&mut self, id: TracerId) -> Option<FileTracer>;
+        fn statistics(&mut self);
+        fn resources(&mut self);
+        fn options(&mut self);
+        ///
+        /// # Errors
+        ///
+        /// Those of '`CaDiCal::read_dimacs1`'.
+        ///
+        fn read_dimacs1(
+            &mut self,
+            file: impl AsRef<Path>,
+            name: String,
+            vars: &mut i32,
+            strict: i32
+        ) -> Result<(), Error>;
+        ///
+        /// # Errors
+        ///
+        /// Those of '`CaDiCal::read_dimacs2`'.
+        ///
+        fn read_dimacs2(
+            &mut self,
+            path: impl AsRef<Path>,
+            vars: &mut i32,
+            strict: i32
+        ) -> Result<(), Error>;
+        ///
+        /// # Errors
+        ///
+        /// Those of '`CaDiCal::read_dimacs_compressed`'.
+        ///
+        fn read_dimacs_compressed(
+            &mut self,
+            path: impl AsRef<Path>,
+            compression: Compression,
+            vars: &mut i32,
+            strict: i32
+        ) -> Result<(), Error>;
+        ///
+        /// # Errors
+        ///
+        /// Those of '`CaDiCal::read_dimacs3`'.
+        ///
+        fn read_dimacs3(
+            &mut self,
+            file: impl AsRef<Path>,
+            name: String,
+            vars: &mut i32,
+            strict: i32,
+            incremental: &mut bool,
+            cubes: &mut Vec<i32>
+        ) -> Result<(), Error>;
+        ///
+        /// # Errors
+        ///
+        /// Those of '`CaDiCal::read_dimacs4`'.
+        ///
+        fn read_dimacs4(
+            &mut self,
+            path: impl AsRef<Path>,
+            vars: &mut i32,
+            strict: i32,
+            incremental: &mut bool,
+            cubes: &mut Vec<i32>
+        ) -> Result<(), Error>;
+        ///
+        /// # Errors
+        ///
+        /// Those of '`CaDiCal::read_dimacs_from`'.
+        ///
+        fn read_dimacs_from(
+            &mut self,
+            reader: impl Read,
+            vars: &mut i32,
+            strict: i32
+        ) -> Result<(), Error>;
+        ///
+        /// # Errors
+        ///
+        /// Those of '`CaDiCal::read_dimacs_str`'.
+        ///
+        fn read_dimacs_str(&mut self, dimacs: &str, vars: &mut i32, strict: i32) -> Result<(), Error>;
+        ///
+        /// # Errors
+        ///
+        /// Those of '`CaDiCal::read_inccnf_from`'.
+        ///
+        fn read_inccnf_from(
+            &mut self,
+            reader: impl Read,
+            vars: &mut i32,
+            strict: i32,
+            incremental: &mut bool,
+            cubes: &mut Vec<i32>
+        ) -> Result<(), Error>;
+        ///
+        /// # Errors
+        ///
+        /// Those of '`CaDiCal::read_inccnf_str`'.
+        ///
+        fn read_inccnf_str(
+            &mut self,
+            inccnf: &str,
+            vars: &mut i32,
+            strict: i32,
+            incremental: &mut bool,
+            cubes: &mut Vec<i32>
+        ) -> Result<(), Error>;
+        ///
+        /// # Errors
+        ///
+        /// Those of '`CaDiCal::write_dimacs`'.
+        ///
+        fn write_dimacs(&mut self, path: impl AsRef<Path>, min_max_var: i32) -> Result<(), Error>;
+        ///
+        /// # Errors
+        ///
+        /// Those of '`CaDiCal::write_dimacs_compressed`'.
+        ///
+        fn write_dimacs_compressed(
+            &mut self,
+            path: impl AsRef<Path>,
+            compression: Compression,
+            min_max_var: i32
+        ) -> Result<(), Error>;
+        ///
+        /// # Errors
+        ///
+        /// Those of '`CaDiCal::write_dimacs_to`'.
+        ///
+        fn write_dimacs_to(&mut self, writer: impl Write, min_max_var: i32) -> Result<(), Error>;
+        ///
+        /// # Errors
+        ///
+        /// Those of '`CaDiCal::write_extension`'.
+        ///
+        fn write_extension(&mut self, path: impl AsRef<Path>) -> Result<(), Error>;
+        ///
+        /// # Errors
+        ///
+        /// Those of '`CaDiCal::write_extension_compressed`'.
+        ///
+        fn write_extension_compressed(
+            &mut self,
+            path: impl AsRef<Path>,
+            compression: Compression
+        ) -> Result<(), Error>;
+    }
+}
@@ -0,0 +1,841 @@
+//! A DIMACS CNF parser and writer in Rust, for inspecting and validating
+//! formulas before (or without) handing them to '`CaDiCal`'.
+//!
+//! The parser accepts the same 'strict' levels as '`CaDiCal::read_dimacs1`'
+//! but reports errors with their line and column and keeps comment lines.
+//!
+//! ```rust
+//! use cadical_sys::{dimacs::Cnf, CaDiCal, Status};
+//!
+//! let cnf: Cnf = "c example\np cnf 2 2\n1 -2 0\n2 0\n".parse().unwrap();
+//! assert_eq!(cnf.vars, 2);
+//! assert_eq!(cnf.comments, ["example"]);
+//!
+//! let mut solver = CaDiCal::new();
+//! cnf.add_to(&mut solver);
+//! assert_eq!(solver.solve(), Status::SATISFIABLE);
+//! ```
+
+use crate::{CaDiCal, Error, Lit, Status, Var};
+use std::{
+    fmt,
+    io::{BufRead, Write},
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+/// The counts given in the 'p cnf' header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Header {
+    pub vars: i32,
+    pub clauses: u64,
+}
+
+/// One piece of a DIMACS file in the order it appears.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Item {
+    /// Text following the 'c' of a comment, without the separating space.
+    Comment(String),
+    Header(Header),
+    /// The 'p inccnf' header of an incremental file, which has no counts.
+    IncrementalHeader,
+    /// A clause without its terminating '0'.
+    Clause(Vec<Lit>),
+    /// The literals of an 'a <cube> 0' line without the 'a' and the '0'.
+    Cube(Vec<Lit>),
+}
+
+/// A syntax or consistency error of a DIMACS file.  Lines and columns start
+/// at '1', columns count bytes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    line: usize,
+    column: usize,
+    message: String,
+}
+
+impl ParseError {
+    pub(crate) fn new(line: usize, column: usize, message: impl Into<String>) -> Self {
+        Self {
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {} column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// The current line of a DIMACS file and the position within it.
+pub(crate) struct Cursor<R> {
+    reader: R,
+    buffer: Vec<u8>,
+    line: usize,
+    pos: usize,
+}
+
+impl<R: BufRead> Cursor<R> {
+    pub(crate) fn new(reader: R) -> Self {
+        Self {
+            reader,
+            buffer: Vec::new(),
+            line: 0,
+            pos: 0,
+        }
+    }
+
+    /// Reads the next line without its line ending, 'false' at the end,
+    /// where the position stays behind the last line.
+    pub(crate) fn next_line(&mut self) -> Result<bool, Error> {
+        let end = self.buffer.len();
+        self.buffer.clear();
+        self.pos = 0;
+        if self.reader.read_until(b'\n', &mut self.buffer)? == 0 {
+            self.pos = end;
+            return Ok(false);
+        }
+        self.line += 1;
+        if self.buffer.last() == Some(&b'\n') {
+            self.buffer.pop();
+            if self.buffer.last() == Some(&b'\r') {
+                self.buffer.pop();
+            }
+        }
+        Ok(true)
+    }
+
+    pub(crate) fn line(&self) -> usize {
+        self.line
+    }
+
+    pub(crate) fn column(&self) -> usize {
+        self.pos + 1
+    }
+
+    pub(crate) fn peek(&self) -> Option<u8> {
+        self.buffer.get(self.pos).copied()
+    }
+
+    pub(crate) fn at_end(&self) -> bool {
+        self.pos >= self.buffer.len()
+    }
+
+    pub(crate) fn error(&self, message: impl Into<String>) -> Error {
+        Error::Parse(ParseError::new(self.line, self.column(), message))
+    }
+
+    /// Skips spaces and tabs, returning whether there were any.
+    pub(crate) fn skip_space(&mut self) -> bool {
+        let start = self.pos;
+        while matches!(self.peek(), Some(b' ' | b'\t')) {
+            self.pos += 1;
+        }
+        self.pos > start
+    }
+
+    /// The rest of the line after the current character and an optional
+    /// space, for comments.
+    pub(crate) fn rest(&mut self) -> String {
+        let mut start = self.pos + 1;
+        if self.buffer.get(start) == Some(&b' ') {
+            start += 1;
+        }
+        let rest = String::from_utf8_lossy(self.buffer.get(start..).unwrap_or_default());
+        self.pos = self.buffer.len();
+        rest.into_owned()
+    }
+
+    /// Expects exactly 'word' at the current position.
+    pub(crate) fn keyword(&mut self, word: &str) -> Result<(), Error> {
+        if self.buffer[self.pos..].starts_with(word.as_bytes()) {
+            self.pos += word.len();
+            Ok(())
+        } else {
+            Err(self.error(format!("expected '{word}'")))
+        }
+    }
+
+    /// Takes characters from the current position on as long as 'accept'
+    /// holds for them.
+    pub(crate) fn take_while(&mut self, accept: impl Fn(u8) -> bool) -> String {
+        let start = self.pos;
+        while self.peek().is_some_and(&accept) {
+            self.pos += 1;
+        }
+        String::from_utf8_lossy(&self.buffer[start..self.pos]).into_owned()
+    }
+
+    /// The end of a header line, without trailing white space if
+    /// 'pedantic'.
+    pub(crate) fn end_of_header(&mut self, pedantic: bool) -> Result<(), Error> {
+        if pedantic {
+            if !self.at_end() {
+                return Err(self.error("trailing white space after header"));
+            }
+        } else {
+            self.skip_space();
+            if !self.at_end() {
+                return Err(self.error("unexpected character after header"));
+            }
+        }
+        Ok(())
+    }
+
+    /// A single space if 'pedantic', otherwise any spaces and tabs.
+    pub(crate) fn separator(&mut self, pedantic: bool) -> Result<(), Error> {
+        if pedantic {
+            self.keyword(" ")?;
+            if matches!(self.peek(), Some(b' ' | b'\t')) {
+                return Err(self.error("expected single space in header"));
+            }
+        } else if !self.skip_space() {
+            return Err(self.error("expected white space"));
+        }
+        Ok(())
+    }
+
+    /// Parses an unsigned decimal number up to 'max'.
+    pub(crate) fn unsigned(&mut self, what: &str, max: u64) -> Result<u64, Error> {
+        self.digits(what, max, self.column())
+    }
+
+    /// Parses the digits of a number starting in column 'start'.
+    fn digits(&mut self, what: &str, max: u64, start: usize) -> Result<u64, Error> {
+        if !matches!(self.peek(), Some(b'0'..=b'9')) {
+            return Err(self.error(format!("expected {what}")));
+        }
+        let mut value: u64 = 0;
+        while let Some(digit @ b'0'..=b'9') = self.peek() {
+            value = value
+                .checked_mul(10)
+                .and_then(|value| value.checked_add(u64::from(digit - b'0')))
+                .filter(|&value| value <= max)
+                .ok_or_else(|| {
+                    Error::Parse(ParseError::new(
+                        self.line,
+                        start,
+                        format!("{what} too large"),
+                    ))
+                })?;
+            self.pos += 1;
+        }
+        if !matches!(self.peek(), None | Some(b' ' | b'\t')) {
+            return Err(self.error(format!("unexpected character after {what}")));
+        }
+        Ok(value)
+    }
+
+    /// Parses a signed decimal integer, that is a literal or the terminating
+    /// '0', and returns it with its column.
+    pub(crate) fn integer(&mut self) -> Result<(i32, usize), Error> {
+        let column = self.column();
+        let negative = self.peek() == Some(b'-');
+        if negative {
+            self.pos += 1;
+            if !matches!(self.peek(), Some(b'1'..=b'9')) {
+                return Err(self.error("expected non-zero digit after '-'"));
+            }
+        }
+        let value = self.digits("literal", i32::MAX.unsigned_abs().into(), column)?;
+        let value = i32::try_from(value).expect("bounded by 'i32::MAX'");
+        Ok((if negative { -value } else { value }, column))
+    }
+}
+
+/// Streams the comments, header and clauses of a DIMACS CNF file.  With
+/// 'strict' zero the counts of the header are ignored, with '1' variables
+/// beyond the header and a wrong number of clauses are errors, and with '2'
+/// (or larger) the header has to be formatted exactly as 'p cnf V C' with
+/// single spaces and no trailing white space.  The iterator stops after the
+/// first error.
+pub struct Parser<R> {
+    cursor: Cursor<R>,
+    strict: i32,
+    /// Whether 'p inccnf' headers and cubes are accepted.
+    incremental: bool,
+    /// Whether a 'p inccnf' header or a cube was found.
+    found_incremental: bool,
+    section: Section,
+    header: Option<Header>,
+    clause: Vec<Lit>,
+    /// Whether 'clause' holds a cube.
+    cube: bool,
+    clause_start: (usize, usize),
+    parsed: u64,
+    max_var: i32,
+}
+
+/// Where the parser is in the file.
+#[derive(PartialEq, Eq)]
+enum Section {
+    /// Comments before the header.
+    Preamble,
+    Body,
+    /// After the end of the file or an error.
+    Done,
+}
+
+impl<R: BufRead> Parser<R> {
+    pub fn new(reader: R, strict: i32) -> Self {
+        Self {
+            cursor: Cursor::new(reader),
+            strict,
+            incremental: false,
+            found_incremental: false,
+            section: Section::Preamble,
+            header: None,
+            clause: Vec::new(),
+            cube: false,
+            clause_start: (0, 0),
+            parsed: 0,
+            max_var: 0,
+        }
+    }
+
+    /// A parser which also accepts the INCCNF format of
+    /// '`CaDiCal::read_dimacs3`', that is a 'p inccnf' header instead of
+    /// 'p cnf' and 'a <cube> 0' lines after the header.  Incremental files
+    /// have no counts to check.
+    pub fn incremental(reader: R, strict: i32) -> Self {
+        Self {
+            incremental: true,
+            ..Self::new(reader, strict)
+        }
+    }
+
+    /// Whether a 'p inccnf' header or a cube has been parsed, which is the
+    /// value '`CaDiCal::read_dimacs3`' returns in 'incremental'.
+    #[must_use]
+    #[inline]
+    pub fn is_incremental(&self) -> bool {
+        self.found_incremental
+    }
+
+    /// The header once it has been parsed.
+    #[must_use]
+    #[inline]
+    pub fn header(&self) -> Option<Header> {
+        self.header
+    }
+
+    /// The largest variable of the header and the clauses parsed so far,
+    /// which is the value '`CaDiCal::read_dimacs1`' returns in 'vars'.
+    #[must_use]
+    #[inline]
+    pub fn vars(&self) -> i32 {
+        self.header
+            .map_or(0, |header| header.vars)
+            .max(self.max_var)
+    }
+
+    /// Adds all clauses to 'solver' while parsing and reserves the variables
+    /// of the header, returning the header with 'vars' set to '`Parser::vars`'.
+    /// Cubes are skipped.
+    ///
+    /// # Errors
+    ///
+    /// The first error of the parser.  Clauses before the error have been
+    /// added to 'solver'.
+    ///
+    pub fn feed(mut self, solver: &mut CaDiCal) -> Result<Header, Error> {
+        for item in &mut self {
+            if let Item::Clause(clause) = item? {
+                solver.clause6(&clause);
+            }
+        }
+        solver.reserve(self.vars());
+        let mut header = self.header.unwrap_or_default();
+        header.vars = self.vars();
+        Ok(header)
+    }
+
+    fn parse(&mut self) -> Result<Option<Item>, Error> {
+        loop {
+            if self.cursor.at_end() {
+                if !self.cursor.next_line()? {
+                    return self.finish().map(|()| None);
+                }
+                if self.cursor.at_end() && self.section == Section::Preamble && self.strict < 2 {
+                    continue;
+                }
+            }
+            if self.section == Section::Preamble {
+                return self.parse_preamble().map(Some);
+            }
+            self.cursor.skip_space();
+            match self.cursor.peek() {
+                None => {}
+                Some(b'c') => return Ok(Some(Item::Comment(self.cursor.rest()))),
+                Some(b'a') if self.incremental => self.start_cube()?,
+                Some(b'-' | b'0'..=b'9') => {
+                    if let Some(item) = self.parse_literal()? {
+                        return Ok(Some(item));
+                    }
+                }
+                Some(_) => return Err(self.cursor.error("expected literal or comment")),
+            }
+        }
+    }
+
+    /// Comments and the header at the start of the file.
+    fn parse_preamble(&mut self) -> Result<Item, Error> {
+        match self.cursor.peek() {
+            Some(b'c') => Ok(Item::Comment(self.cursor.rest())),
+            Some(b'p') => {
+                let item = self.parse_header()?;
+                self.section = Section::Body;
+                Ok(item)
+            }
+            _ => Err(self.cursor.error("expected 'c' or 'p cnf' header")),
+        }
+    }
+
+    fn parse_header(&mut self) -> Result<Item, Error> {
+        let pedantic = self.strict >= 2;
+        self.cursor.keyword("p")?;
+        self.cursor.separator(pedantic)?;
+        if self.incremental && self.cursor.peek() == Some(b'i') {
+            self.cursor.keyword("inccnf")?;
+            self.cursor.end_of_header(pedantic)?;
+            self.found_incremental = true;
+            return Ok(Item::IncrementalHeader);
+        }
+        self.cursor.keyword("cnf")?;
+        self.cursor.separator(pedantic)?;
+        let max_vars = i32::MAX.unsigned_abs().into();
+        let vars = self.cursor.unsigned("number of variables", max_vars)?;
+        self.cursor.separator(pedantic)?;
+        let clauses = self.cursor.unsigned("number of clauses", u64::MAX)?;
+        self.cursor.end_of_header(pedantic)?;
+        let header = Header {
+            vars: i32::try_from(vars).expect("bounded by 'i32::MAX'"),
+            clauses,
+        };
+        self.header = Some(header);
+        Ok(Item::Header(header))
+    }
+
+    /// Starts a cube at an 'a', which cannot be part of a clause.
+    fn start_cube(&mut self) -> Result<(), Error> {
+        if !self.clause.is_empty() {
+            return Err(self.cursor.error("cube within clause"));
+        }
+        self.clause_start = (self.cursor.line(), self.cursor.column());
+        self.cursor.keyword("a")?;
+        self.cube = true;
+        self.found_incremental = true;
+        Ok(())
+    }
+
+    /// Adds the next literal to the current clause or cube, returning it
+    /// once its '0' is reached.
+    fn parse_literal(&mut self) -> Result<Option<Item>, Error> {
+        if self.clause.is_empty() && !self.cube {
+            self.clause_start = (self.cursor.line(), self.cursor.column());
+        }
+        let (lit, column) = self.cursor.integer()?;
+        let Some(lit) = Lit::from_dimacs(lit) else {
+            let lits = std::mem::take(&mut self.clause);
+            if self.cube {
+                self.cube = false;
+                return Ok(Some(Item::Cube(lits)));
+            }
+            match self.header {
+                Some(header) if self.strict >= 1 && self.parsed == header.clauses => {
+                    let (line, column) = self.clause_start;
+                    return Err(Error::Parse(ParseError::new(
+                        line,
+                        column,
+                        format!("too many clauses, header specifies {}", header.clauses),
+                    )));
+                }
+                _ => {}
+            }
+            self.parsed += 1;
+            return Ok(Some(Item::Clause(lits)));
+        };
+        let var = lit.var().index();
+        match self.header {
+            Some(header) if self.strict >= 1 && var > header.vars => {
+                return Err(Error::Parse(ParseError::new(
+                    self.cursor.line(),
+                    column,
+                    format!("literal {lit} exceeds maximum variable {}", header.vars),
+                )));
+            }
+            _ => {}
+        }
+        self.max_var = self.max_var.max(var);
+        self.clause.push(lit);
+        Ok(None)
+    }
+
+    /// Checks the end of the file.
+    fn finish(&mut self) -> Result<(), Error> {
+        if self.section == Section::Preamble {
+            return Err(self.cursor.error("missing 'p cnf' header"));
+        }
+        if self.cube {
+            return Err(self.cursor.error("last cube without terminating '0'"));
+        }
+        if !self.clause.is_empty() {
+            return Err(self.cursor.error("last clause without terminating '0'"));
+        }
+        let Some(header) = self.header else {
+            return Ok(());
+        };
+        if self.strict >= 1 && self.parsed < header.clauses {
+            let missing = header.clauses - self.parsed;
+            return Err(self.cursor.error(if missing == 1 {
+                "one clause missing".to_string()
+            } else {
+                format!("{missing} clauses missing")
+            }));
+        }
+        Ok(())
+    }
+}
+
+impl<R: BufRead> Iterator for Parser<R> {
+    type Item = Result<Item, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.section == Section::Done {
+            return None;
+        }
+        let item = self.parse().transpose();
+        if !matches!(item, Some(Ok(_))) {
+            self.section = Section::Done;
+        }
+        item
+    }
+}
+
+/// An owned DIMACS formula.  The comments of the file are kept in order,
+/// but not their positions relative to the clauses.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Cnf {
+    pub vars: i32,
+    pub clauses: Vec<Vec<Lit>>,
+    pub comments: Vec<String>,
+}
+
+impl Cnf {
+    /// Parses a whole file, see '`Parser`' for 'strict'.  'vars' is the
+    /// maximum of the header and the variables used.
+    ///
+    /// # Errors
+    ///
+    /// '`Error::Parse`' for the first syntax error and '`Error::Io`' if
+    /// reading fails.
+    ///
+    pub fn parse(reader: impl BufRead, strict: i32) -> Result<Self, Error> {
+        let mut parser = Parser::new(reader, strict);
+        let mut cnf = Cnf::default();
+        for item in &mut parser {
+            match item? {
+                Item::Comment(comment) => cnf.comments.push(comment),
+                Item::Clause(clause) => cnf.clauses.push(clause),
+                Item::Header(_) | Item::IncrementalHeader | Item::Cube(_) => {}
+            }
+        }
+        cnf.vars = parser.vars();
+        Ok(cnf)
+    }
+
+    /// Adds the clauses to 'solver' and reserves all variables.
+    ///
+    ///   require (VALID)
+    ///
+    pub fn add_to(&self, solver: &mut CaDiCal) {
+        for clause in &self.clauses {
+            solver.clause6(clause);
+        }
+        solver.reserve(self.vars);
+    }
+
+    /// Writes the comments, the header and then the clauses.
+    ///
+    /// # Errors
+    ///
+    /// '`Error::Io`' if writing fails.
+    ///
+    pub fn write(&self, mut writer: impl Write) -> Result<(), Error> {
+        write!(writer, "{self}")?;
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+/// Parses with 'strict' set to '1', the default of `CaDiCaL`.
+impl FromStr for Cnf {
+    type Err = Error;
+
+    fn from_str(dimacs: &str) -> Result<Self, Error> {
+        Cnf::parse(dimacs.as_bytes(), 1)
+    }
+}
+
+/// The DIMACS text of the formula, as written by '`Cnf::write`'.
+impl fmt::Display for Cnf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for comment in &self.comments {
+            if comment.is_empty() {
+                writeln!(f, "c")?;
+            } else {
+                writeln!(f, "c {comment}")?;
+            }
+        }
+        writeln!(f, "p cnf {} {}", self.vars, self.clauses.len())?;
+        for clause in &self.clauses {
+            for lit in clause {
+                write!(f, "{lit} ")?;
+            }
+            writeln!(f, "0")?;
+        }
+        Ok(())
+    }
+}
+
+/// An owned incremental formula, that is the clauses and cubes of a
+/// 'p inccnf' file or of a 'p cnf' file with cubes.
+///
+/// ```rust
+/// use cadical_sys::{dimacs::{CubeOutcome, IncrementalCnf}, CaDiCal};
+///
+/// let icnf: IncrementalCnf = "p inccnf\n1 2 0\na -1 0\na -1 -2 0\n".parse().unwrap();
+/// let mut solver = CaDiCal::new();
+/// let results: Vec<_> = icnf.runner(&mut solver).collect::<Result<_, _>>().unwrap();
+/// assert!(matches!(results[0].outcome, CubeOutcome::Satisfiable { .. }));
+/// assert!(matches!(results[1].outcome, CubeOutcome::Unsatisfiable { .. }));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct IncrementalCnf {
+    pub vars: i32,
+    pub clauses: Vec<Vec<Lit>>,
+    pub cubes: Vec<Vec<Lit>>,
+    pub comments: Vec<String>,
+}
+
+impl IncrementalCnf {
+    /// Parses a whole file with '`Parser::incremental`'.
+    ///
+    /// # Errors
+    ///
+    /// '`Error::Parse`' for the first syntax error and '`Error::Io`' if
+    /// reading fails.
+    ///
+    pub fn parse(reader: impl BufRead, strict: i32) -> Result<Self, Error> {
+        let mut parser = Parser::incremental(reader, strict);
+        let mut icnf = IncrementalCnf::default();
+        for item in &mut parser {
+            match item? {
+                Item::Comment(comment) => icnf.comments.push(comment),
+                Item::Clause(clause) => icnf.clauses.push(clause),
+                Item::Cube(cube) => icnf.cubes.push(cube),
+                Item::Header(_) | Item::IncrementalHeader => {}
+            }
+        }
+        icnf.vars = parser.vars();
+        Ok(icnf)
+    }
+
+    /// Splits the zero terminated cubes returned by '`CaDiCal::read_dimacs3`'
+    /// and '`CaDiCal::read_dimacs4`', 'None' if the last cube is not
+    /// terminated or a literal is '`INT_MIN`'.
+    #[must_use]
+    pub fn split_cubes(cubes: &[i32]) -> Option<Vec<Vec<Lit>>> {
+        let Some((&0, cubes)) = cubes.split_last() else {
+            return cubes.is_empty().then(Vec::new);
+        };
+        cubes
+            .split(|&lit| lit == 0)
+            .map(|cube| Lit::slice_from_dimacs(cube).map(<[Lit]>::to_vec))
+            .collect()
+    }
+
+    /// Adds the clauses to 'solver' and returns a runner solving them under
+    /// each cube in order.
+    ///
+    ///   require (VALID)
+    ///
+    pub fn runner<'a>(&'a self, solver: &'a mut CaDiCal) -> CubeRunner<'a> {
+        for clause in &self.clauses {
+            solver.clause6(clause);
+        }
+        solver.reserve(self.vars);
+        CubeRunner::new(solver, &self.cubes)
+    }
+}
+
+/// Parses with 'strict' set to '1', the default of `CaDiCaL`.
+impl FromStr for IncrementalCnf {
+    type Err = Error;
+
+    fn from_str(dimacs: &str) -> Result<Self, Error> {
+        IncrementalCnf::parse(dimacs.as_bytes(), 1)
+    }
+}
+
+/// The INCCNF text of the formula with a 'p inccnf' header.
+impl fmt::Display for IncrementalCnf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for comment in &self.comments {
+            if comment.is_empty() {
+                writeln!(f, "c")?;
+            } else {
+                writeln!(f, "c {comment}")?;
+            }
+        }
+        writeln!(f, "p inccnf")?;
+        for clause in &self.clauses {
+            for lit in clause {
+                write!(f, "{lit} ")?;
+            }
+            writeln!(f, "0")?;
+        }
+        for cube in &self.cubes {
+            f.write_str("a ")?;
+            for lit in cube {
+                write!(f, "{lit} ")?;
+            }
+            writeln!(f, "0")?;
+        }
+        Ok(())
+    }
+}
+
+/// The answer of the solver under one cube.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum CubeOutcome {
+    /// The values of all variables of the solver as literals.
+    Satisfiable { model: Vec<Lit> },
+    /// The literals of the cube which '`CaDiCal::failed`' reports.
+    Unsatisfiable { failed: Vec<Lit> },
+    /// The solver was terminated or hit a limit.
+    Unknown,
+}
+
+/// The result of solving under the cube at 'index'.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CubeResult {
+    pub index: usize,
+    pub outcome: CubeOutcome,
+    pub time: Duration,
+}
+
+impl CubeResult {
+    #[must_use]
+    #[inline]
+    pub fn status(&self) -> Status {
+        match self.outcome {
+            CubeOutcome::Satisfiable { .. } => Status::SATISFIABLE,
+            CubeOutcome::Unsatisfiable { .. } => Status::UNSATISFIABLE,
+            CubeOutcome::Unknown => Status::UNKNOWN,
+        }
+    }
+}
+
+/// Solves under each cube in order by assuming its literals, as the
+/// `cadical` binary does for incremental files.  Each step is one call to
+/// '`CaDiCal::try_solve`', so the runner can be stopped between cubes and
+/// stops after the first error.
+pub struct CubeRunner<'a> {
+    solver: &'a mut CaDiCal,
+    cubes: std::iter::Enumerate<std::slice::Iter<'a, Vec<Lit>>>,
+    stop_at_first_sat: bool,
+    stopped: bool,
+}
+
+impl<'a> CubeRunner<'a> {
+    /// Runs 'cubes' on the clauses already in 'solver', for instance those
+    /// read by '`CaDiCal::read_dimacs3`'.
+    pub fn new(solver: &'a mut CaDiCal, cubes: &'a [Vec<Lit>]) -> Self {
+        Self {
+            solver,
+            cubes: cubes.iter().enumerate(),
+            stop_at_first_sat: false,
+            stopped: false,
+        }
+    }
+
+    /// Stops after the first satisfiable cube.
+    #[must_use]
+    #[inline]
+    pub fn stop_at_first_sat(mut self, stop: bool) -> Self {
+        self.stop_at_first_sat = stop;
+        self
+    }
+
+    fn solve(&mut self, cube: &[Lit]) -> Result<CubeOutcome, Error> {
+        for &lit in cube {
+            self.solver.assume(lit);
+        }
+        Ok(match self.solver.try_solve()? {
+            Status::SATISFIABLE => {
+                self.stopped = self.stop_at_first_sat;
+                let model = (1..=self.solver.vars())
+                    .filter_map(Var::new)
+                    .map(|var| var.lit(self.solver.value(var.positive())))
+                    .collect();
+                CubeOutcome::Satisfiable { model }
+            }
+            Status::UNSATISFIABLE => {
+                let failed = cube
+                    .iter()
+                    .copied()
+                    .filter(|&lit| self.solver.failed(lit))
+                    .collect();
+                CubeOutcome::Unsatisfiable { failed }
+            }
+            Status::UNKNOWN => CubeOutcome::Unknown,
+        })
+    }
+}
+
+impl Iterator for CubeRunner<'_> {
+    type Item = Result<CubeResult, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.stopped {
+            return None;
+        }
+        let (index, cube) = self.cubes.next()?;
+        let start = Instant::now();
+        let result = self.solve(cube).map(|outcome| CubeResult {
+            index,
+            outcome,
+            time: start.elapsed(),
+        });
+        self.stopped |= result.is_err();
+        Some(result)
+    }
+}
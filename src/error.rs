@@ -0,0 +1,143 @@
+use crate::{
+    dimacs::ParseError,
+    proof::{ProofError, ProofFormat},
+    State,
+};
+use std::{
+    any::Any,
+    fmt, io,
+    path::{Path, PathBuf},
+};
+
+/// Errors reported by the fallible ('`try_`' and file) functions of
+/// '`CaDiCal`'.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// 'solve' or 'status' returned something other than 0, 10 or 20.
+    InvalidStatus(i32),
+    /// An integer with bits not used by '`State`'.
+    InvalidState(i32),
+    /// A file could not be opened, with the reason given by the system.
+    Open { path: PathBuf, source: io::Error },
+    /// Reading from or writing to a stream failed.
+    Io(io::Error),
+    /// Error message of `CaDiCaL`, for instance when parsing DIMACS.
+    Solver(String),
+    /// Error of the Rust parsers with its position.
+    Parse(ParseError),
+    /// A proof step rejected by a checker of '`proof`'.
+    Proof(ProofError),
+    /// A '`FileTracer`' cannot write proofs in this format.
+    UnsupportedFormat(ProofFormat),
+    /// 'set' does not know the option or cannot parse its value.
+    InvalidOption(String),
+    /// 'configure' does not know the configuration.
+    InvalidConfiguration(String),
+    /// 'limit' does not know the limit.
+    InvalidLimit(String),
+    /// A function of '`Checked`' was called in a state its contract forbids.
+    ContractViolation {
+        function: &'static str,
+        required: State,
+        actual: State,
+    },
+    /// A function of '`Checked`' was given '0' or '`INT_MIN`' as literal.
+    InvalidLiteral { function: &'static str, lit: i32 },
+    /// '`Checked::melt`' was called on a literal which is not frozen.
+    NotFrozen(i32),
+    /// A connected call-back panicked; holds the panic payload.
+    Panic(Box<dyn Any + Send>),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::InvalidStatus(status) => write!(f, "invalid solver status {status}"),
+            Error::InvalidState(state) => write!(f, "invalid solver state {state}"),
+            Error::Open { path, source } => {
+                write!(f, "could not open '{}': {source}", path.display())
+            }
+            Error::Io(error) => error.fmt(f),
+            Error::Solver(message) => f.write_str(message),
+            Error::Parse(error) => write!(f, "parse error at {error}"),
+            Error::Proof(error) => write!(f, "invalid proof at {error}"),
+            Error::UnsupportedFormat(format) => {
+                write!(
+                    f,
+                    "proof format {format:?} is only written by 'trace_proof'"
+                )
+            }
+            Error::InvalidOption(name) => write!(f, "invalid option '{name}'"),
+            Error::InvalidConfiguration(name) => write!(f, "invalid configuration '{name}'"),
+            Error::InvalidLimit(name) => write!(f, "invalid limit '{name}'"),
+            Error::ContractViolation {
+                function,
+                required,
+                actual,
+            } => write!(
+                f,
+                "'{function}' requires state {required} but the solver is {actual}"
+            ),
+            Error::InvalidLiteral { function, lit } => {
+                write!(f, "'{function}' called with invalid literal {lit}")
+            }
+            Error::NotFrozen(lit) => write!(f, "can not melt completely melted literal {lit}"),
+            Error::Panic(payload) => {
+                let message = payload
+                    .downcast_ref::<&str>()
+                    .copied()
+                    .or_else(|| payload.downcast_ref::<String>().map(String::as_str));
+                match message {
+                    Some(message) => write!(f, "call-back panicked: {message}"),
+                    None => f.write_str("call-back panicked"),
+                }
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Open { source, .. } | Error::Io(source) => Some(source),
+            Error::Parse(error) => Some(error),
+            Error::Proof(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Self {
+        Error::Io(error)
+    }
+}
+
+impl Error {
+    pub(crate) fn open(path: &Path, source: io::Error) -> Self {
+        Error::Open {
+            path: path.to_path_buf(),
+            source,
+        }
+    }
+
+    /// Rebuilds the error of a bridge function which opens a file itself and
+    /// throws 'errno' if that fails.
+    pub(crate) fn os(exception: &cxx::Exception) -> io::Error {
+        match exception.what().parse() {
+            Ok(errno) => io::Error::from_raw_os_error(errno),
+            Err(_) => io::Error::other(exception.what()),
+        }
+    }
+
+    /// Converts the message returned by the file functions of the bridge,
+    /// which is empty on success.
+    pub(crate) fn reported(message: String) -> Result<(), Self> {
+        if message.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::Solver(message))
+        }
+    }
+}
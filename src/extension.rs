@@ -0,0 +1,198 @@
+//! The reconstruction stack of eliminated clauses and their witnesses, as
+//! written by '`CaDiCal::write_extension`' or collected through
+//! '`CaDiCal::traverse_witnesses_backward`'.  Each line of the file is a
+//! clause and its witness, both terminated by '0', in the order in which
+//! they are applied.
+//!
+//! ```text
+//! 1 -2 0 1 0
+//! ```
+//!
+//! A model of the simplified formula is extended to a model of the original
+//! formula by going through the entries in order and, whenever the clause of
+//! an entry is not satisfied, setting the literals of its witness to true.
+//!
+//! ```rust
+//! use cadical_sys::{extension::Extension, Lit};
+//!
+//! let extension: Extension = "1 -2 0 1 0\n".parse().unwrap();
+//! let satisfied = Lit::slice_from_dimacs(&[-1, -2]).unwrap();
+//! assert_eq!(extension.extend(satisfied), satisfied);
+//! let falsified = Lit::slice_from_dimacs(&[-1, 2]).unwrap();
+//! let extended = extension.extend(falsified);
+//! assert_eq!(Lit::slice_to_dimacs(&extended), [1, 2]);
+//! ```
+
+use crate::{compress, dimacs::Cursor, CaDiCal, Error, Lit, Var, WitnessIterator};
+use std::{
+    fmt,
+    io::{BufRead, Write},
+    path::Path,
+    str::FromStr,
+};
+
+/// A removed clause with the literals which satisfy it if flipped.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Entry {
+    pub clause: Vec<Lit>,
+    pub witness: Vec<Lit>,
+}
+
+/// The entries of the reconstruction stack in the order of application.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Extension {
+    pub entries: Vec<Entry>,
+}
+
+impl Extension {
+    /// Collects the reconstruction stack of 'solver'.
+    ///
+    ///   require (VALID)
+    ///
+    #[must_use]
+    pub fn from_solver(solver: &CaDiCal) -> Self {
+        let mut extension = Extension::default();
+        solver.traverse_witnesses_backward(&mut extension);
+        extension
+    }
+
+    /// Parses the format of '`CaDiCal::write_extension`'.  Lines starting
+    /// with 'c' are skipped.
+    ///
+    /// # Errors
+    ///
+    /// '`Error::Parse`' for the first syntax error and '`Error::Io`' if
+    /// reading fails.
+    ///
+    pub fn parse(reader: impl BufRead) -> Result<Self, Error> {
+        let mut cursor = Cursor::new(reader);
+        let mut extension = Extension::default();
+        while cursor.next_line()? {
+            cursor.skip_space();
+            match cursor.peek() {
+                None | Some(b'c') => continue,
+                Some(b'-' | b'0'..=b'9') => {}
+                Some(_) => return Err(cursor.error("expected literal or comment")),
+            }
+            let clause = Self::lits(&mut cursor)?;
+            let witness = Self::lits(&mut cursor)?;
+            cursor.skip_space();
+            if !cursor.at_end() {
+                return Err(cursor.error("unexpected character after witness"));
+            }
+            extension.entries.push(Entry { clause, witness });
+        }
+        Ok(extension)
+    }
+
+    /// Literals up to the terminating '0' within the current line.
+    fn lits<R: BufRead>(cursor: &mut Cursor<R>) -> Result<Vec<Lit>, Error> {
+        let mut lits = Vec::new();
+        loop {
+            cursor.skip_space();
+            if cursor.at_end() {
+                return Err(cursor.error("missing terminating '0'"));
+            }
+            match Lit::from_dimacs(cursor.integer()?.0) {
+                Some(lit) => lits.push(lit),
+                None => return Ok(lits),
+            }
+        }
+    }
+
+    /// Reads the file at 'path', decompressed as in
+    /// '`CaDiCal::read_dimacs2`'.
+    ///
+    /// # Errors
+    ///
+    /// '`Error::Open`' if the file cannot be opened and otherwise the
+    /// errors of '`Extension::parse`'.
+    ///
+    pub fn read(path: impl AsRef<Path>) -> Result<Self, Error> {
+        Self::parse(compress::reader(path.as_ref())?)
+    }
+
+    /// Extends the partial 'model' of the simplified formula to a model of
+    /// the original formula, with a literal for every variable up to the
+    /// maximum of 'model' and the entries.  Unassigned variables are false,
+    /// as in `CaDiCaL`.
+    #[must_use]
+    pub fn extend(&self, model: &[Lit]) -> Vec<Lit> {
+        let entries = self.entries.iter();
+        let lits = entries.flat_map(|entry| entry.clause.iter().chain(&entry.witness));
+        let vars = model.iter().chain(lits).map(|lit| index(*lit)).max();
+        let mut values = vec![false; vars.map_or(0, |vars| vars + 1)];
+        for &lit in model {
+            values[index(lit)] = lit.is_positive();
+        }
+        let value = |values: &[bool], lit: Lit| values[index(lit)] == lit.is_positive();
+        for entry in &self.entries {
+            if !entry.clause.iter().any(|&lit| value(&values, lit)) {
+                for &lit in &entry.witness {
+                    values[index(lit)] = lit.is_positive();
+                }
+            }
+        }
+        values
+            .iter()
+            .enumerate()
+            .skip(1)
+            .filter_map(|(index, &value)| {
+                let var = Var::new(i32::try_from(index).ok()?)?;
+                Some(var.lit(value))
+            })
+            .collect()
+    }
+
+    /// Writes the entries as '`CaDiCal::write_extension`' does.
+    ///
+    /// # Errors
+    ///
+    /// '`Error::Io`' if writing fails.
+    ///
+    pub fn write(&self, mut writer: impl Write) -> Result<(), Error> {
+        write!(writer, "{self}")?;
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+fn index(lit: Lit) -> usize {
+    lit.var().index().unsigned_abs() as usize
+}
+
+/// Appends the traversed entries.
+impl WitnessIterator for Extension {
+    fn witness(&mut self, clause: &[Lit], witness: &[Lit], _id: u64) -> bool {
+        self.entries.push(Entry {
+            clause: clause.to_vec(),
+            witness: witness.to_vec(),
+        });
+        true
+    }
+}
+
+/// Parses with the defaults of '`Extension::parse`'.
+impl FromStr for Extension {
+    type Err = Error;
+
+    fn from_str(extension: &str) -> Result<Self, Error> {
+        Extension::parse(extension.as_bytes())
+    }
+}
+
+impl fmt::Display for Extension {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for entry in &self.entries {
+            for lit in &entry.clause {
+                write!(f, "{lit} ")?;
+            }
+            write!(f, "0")?;
+            for lit in &entry.witness {
+                write!(f, " {lit}")?;
+            }
+            writeln!(f, " 0")?;
+        }
+        Ok(())
+    }
+}
@@ -42,6 +42,16 @@
 //! cadical-sys = "0.1.0"  # Replace with most recent version
 //! ```
 //!
+//! Compressed formulas, extensions and proofs are handled by `CaDiCaL` through
+//! external `gzip`, `xz` and `bzip2` commands.  The cargo features `gzip`, `xz`,
+//! `bzip2` and `zstd` compress and decompress files with the matching extension
+//! (`.gz`, `.xz`, `.bz2`, `.zst`) in Rust instead:
+//!
+//! ```toml
+//! [dependencies]
+//! cadical-sys = { version = "0.1.0", features = ["gzip", "xz"] }
+//! ```
+//!
 //! # Usage Examples
 //!
 //! ## Basic SAT solving example
@@ -114,19 +124,19 @@
 //!    let mut var_count = 0;
 //!
 //!    // Read a DIMACS CNF file
-//!    let result = solver.read_dimacs1(
-//!        "./tests/problem.cnf".to_string(),
+//!    solver.read_dimacs1(
+//!        "./tests/problem.cnf",
 //!        "my_problem".to_string(),
 //!        &mut var_count,
 //!        0
-//!    );
+//!    ).expect("problem.cnf is valid DIMACS");
 //!
 //!    // Solve the problem from the file
 //!    let status = solver.solve();
 //!
 //!    // Write out results or extension
 //!    if status == Status::SATISFIABLE {
-//!        solver.write_extension("/tmp/solution.ext".to_string());
+//!        solver.write_extension("/tmp/solution.ext").unwrap();
 //!    }
 //! ```
 //!
@@ -181,12 +191,50 @@
 //! Special thanks to Armin Biere for developing and maintaining `CaDiCaL`.
 
 use bridge::ffi;
+use callbacks::{Binding, Connection, Control, Lend};
 use cxx::UniquePtr;
+use proof::{FileTracer, ProofFormat, ProofStatistics, StatTracer};
+use std::{
+    any::TypeId,
+    io::{self, Read, Write},
+    path::Path,
+    sync::Arc,
+};
 
 /// This module contains the FFI bindings to the `CaDiCaL` SAT solver.
 /// Some functions are unsafe due to necessity.
 pub mod bridge;
 
+pub mod aiger;
+mod builder;
+mod callbacks;
+mod checked;
+mod compress;
+mod configured;
+pub mod dimacs;
+mod error;
+pub mod extension;
+mod lit;
+pub mod opb;
+mod path;
+pub mod proof;
+pub mod solution;
+mod state;
+mod stream;
+mod terminate;
+mod typed;
+pub mod wcnf;
+
+pub use builder::SolverBuilder;
+pub use checked::Checked;
+pub use compress::{Compression, Encoder};
+pub use configured::Configured;
+pub use error::Error;
+pub use lit::{Lit, Literal, Var, Variable};
+pub use state::State;
+pub use terminate::TerminateHandle;
+pub use typed::Typed;
+
 /// The SAT competition standardized the exit code of SAT solvers to the
 /// following which then is also used return code for 'solve' functions.
 /// In the following example we use those constants for brevity though.
@@ -197,74 +245,70 @@ pub enum Status {
     UNKNOWN = 0,
 }
 
-impl From<i32> for Status {
-    fn from(val: i32) -> Self {
+impl TryFrom<i32> for Status {
+    type Error = Error;
+
+    fn try_from(val: i32) -> Result<Self, Error> {
         match val {
-            10 => Status::SATISFIABLE,
-            20 => Status::UNSATISFIABLE,
-            0 => Status::UNKNOWN,
-            _ => unreachable!(),
+            10 => Ok(Status::SATISFIABLE),
+            20 => Ok(Status::UNSATISFIABLE),
+            0 => Ok(Status::UNKNOWN),
+            _ => Err(Error::InvalidStatus(val)),
         }
     }
 }
 
-/// States are represented by a bit-set in order to combine them.
-#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
-pub enum State {
-    /// during initialization (invalid)
-    INITIALIZING = 1,
-    /// configure options (with 'set')
-    CONFIGURING = 2,
-    /// ready to call 'solve'
-    STEADY = 4,
-    /// adding clause literals (zero missing)
-    ADDING = 8,
-    /// while solving (within 'solve')
-    SOLVING = 16,
-    /// satisfiable allows 'val'
-    SATISFIED = 32,
-    /// unsatisfiable allows 'failed'
-    UNSATISFIED = 64,
-    /// during and after deletion (invalid)
-    DELETING = 128,
-
-    /// These combined states are used to check contracts.
-    /// CONFIGURING | STEADY | SATISFIED | UNSATISFIED,
-    READY = 102,
-    /// READY | ADDING
-    VALID = 110,
-    /// INITIALIZING | DELETING
-    INVALID = 129,
+/// A `CaDiCaL` solver together with the call-backs connected to it.
+///
+/// The solver can be moved to another thread, which is why all call-backs
+/// have to be 'Send'.  Use a '`TerminateHandle`' to stop it from elsewhere.
+///
+/// A panic of a call-back is raised again by the method which ran it, once
+/// the solver returned, for instance by 'add' for a proof tracer or by
+/// 'solve' for a learner.  A panic which is still parked when the solver is
+/// dropped is raised by 'drop'.
+pub struct CaDiCal {
+    solver: UniquePtr<ffi::Solver>,
+    root_terminator: UniquePtr<ffi::Terminator>,
+    control: Arc<Control>,
+    last_terminator: Option<Connection<ffi::Terminator, dyn Terminator>>,
+    last_learner: Option<Connection<ffi::Learner, dyn Learner>>,
+    last_external_propagator: Option<Connection<ffi::ExternalPropagator, dyn ExternalPropagator>>,
+    last_fixed_listener:
+        Option<Connection<ffi::FixedAssignmentListener, dyn FixedAssignmentListener>>,
+    /// Connected proof tracers of each kind in the order of connection.
+    tracers: Vec<(TracerId, Connection<ffi::Tracer, dyn ProofTracer>)>,
+    stat_tracers: Vec<(TracerId, StatTracer)>,
+    file_tracers: Vec<(TracerId, Connection<ffi::Tracer, FileTracer>)>,
+    last_tracer_id: u64,
+    /// Files of '`trace_proof1`', closed only after the solver is deleted.
+    proof_files: Vec<UniquePtr<ffi::ProofFile>>,
 }
 
-impl From<i32> for State {
-    fn from(val: i32) -> Self {
-        match val {
-            1 => State::INITIALIZING,
-            2 => State::CONFIGURING,
-            4 => State::STEADY,
-            8 => State::ADDING,
-            16 => State::SOLVING,
-            32 => State::SATISFIED,
-            64 => State::UNSATISFIED,
-            128 => State::DELETING,
-            102 => State::READY,
-            110 => State::VALID,
-            129 => State::INVALID,
-            _ => unreachable!(),
+// SAFETY: A `CaDiCaL` solver is not tied to the thread that created it.  The
+// call-back objects it reaches through its wrappers are 'Send'.  Its 'Control'
+// and bindings are shared through 'Arc's and only hold atomics and mutexes,
+// since a solver swapped out of a scoped connection may move to another thread
+// while the 'Lend' releasing the binding stays behind.  The target of a
+// binding stays locked while it is called, so releasing it waits for a
+// running call-back.  Proof files are plain 'FILE' handles, which may be
+// closed on any thread.
+unsafe impl Send for CaDiCal {}
+
+/// The solver deletes the stat tracers still connected to it, so the ones
+/// owned here are disconnected first.  A panic still parked by a
+/// call-back is raised, unless the thread is already unwinding.
+impl Drop for CaDiCal {
+    fn drop(&mut self) {
+        for (_, mut tracer) in std::mem::take(&mut self.stat_tracers) {
+            ffi::disconnect_proof_tracer2(&mut self.solver, &mut tracer.tracer);
+        }
+        if !std::thread::panicking() {
+            self.control.resume();
         }
     }
 }
 
-pub struct CaDiCal {
-    solver: UniquePtr<ffi::Solver>,
-    last_terminator: Option<UniquePtr<ffi::Terminator>>,
-    last_learner: Option<UniquePtr<ffi::Learner>>,
-    last_external_propagator: Option<UniquePtr<ffi::ExternalPropagator>>,
-    last_fixed_listener: Option<UniquePtr<ffi::FixedAssignmentListener>>,
-    last_tracer: Option<UniquePtr<ffi::Tracer>>,
-}
-
 impl Clone for CaDiCal {
     fn clone(&self) -> Self {
         let mut r = Self::new();
@@ -283,20 +327,36 @@ impl CaDiCal {
     #[must_use]
     #[inline]
     pub fn new() -> Self {
-        Self {
+        let control = Arc::new(Control::default());
+        let mut solver = Self {
             solver: ffi::constructor(),
+            root_terminator: callbacks::root_terminator(&control),
+            control,
             last_terminator: None,
             last_learner: None,
             last_external_propagator: None,
             last_fixed_listener: None,
-            last_tracer: None,
-        }
+            tracers: Vec::new(),
+            stat_tracers: Vec::new(),
+            file_tracers: Vec::new(),
+            last_tracer_id: 0,
+            proof_files: Vec::new(),
+        };
+        ffi::connect_terminator(&mut solver.solver, &mut solver.root_terminator);
+        solver
+    }
+
+    /// Configures a new solver, see '`SolverBuilder`'.
+    #[inline]
+    pub fn builder() -> SolverBuilder {
+        SolverBuilder::new()
     }
 
     /// Core functionality as in the IPASIR incremental SAT solver interface.
     /// (recall 'READY = CONFIGURING | STEADY  | SATISFIED | UNSATISFIED').
     /// Further note that 'lit' is required to be different from '`INT_MIN`' and
-    /// different from '0' except for 'add'.
+    /// different from '0' except for 'add'.  Raw integers violating this panic
+    /// before they reach `CaDiCaL`, see '`Literal`'.
     ///
     /// Add valid literal to clause or zero to terminate clause.
     ///
@@ -307,8 +367,9 @@ impl CaDiCal {
     ///   if (!lit) ensure (STEADY )        and thus READY
     ///
     #[inline]
-    pub fn add(&mut self, lit: i32) {
-        ffi::add(&mut self.solver, lit);
+    pub fn add(&mut self, lit: impl Literal) {
+        ffi::add(&mut self.solver, lit::clause_item(lit));
+        self.control.resume();
     }
 
     /// Here are functions simplifying clause addition. The given literals
@@ -318,33 +379,52 @@ impl CaDiCal {
     ///   ensure (STEADY )
     ///
     #[inline]
-    pub fn clause1(&mut self, l1: i32) {
-        ffi::clause1(&mut self.solver, l1);
+    pub fn clause1<L: Literal>(&mut self, l1: L) {
+        ffi::clause1(&mut self.solver, l1.dimacs());
+        self.control.resume();
     }
 
     #[inline]
-    pub fn clause2(&mut self, l1: i32, l2: i32) {
-        ffi::clause2(&mut self.solver, l1, l2);
+    pub fn clause2<L: Literal>(&mut self, l1: L, l2: L) {
+        ffi::clause2(&mut self.solver, l1.dimacs(), l2.dimacs());
+        self.control.resume();
     }
 
     #[inline]
-    pub fn clause3(&mut self, l1: i32, l2: i32, l3: i32) {
-        ffi::clause3(&mut self.solver, l1, l2, l3);
+    pub fn clause3<L: Literal>(&mut self, l1: L, l2: L, l3: L) {
+        ffi::clause3(&mut self.solver, l1.dimacs(), l2.dimacs(), l3.dimacs());
+        self.control.resume();
     }
 
     #[inline]
-    pub fn clause4(&mut self, l1: i32, l2: i32, l3: i32, l4: i32) {
-        ffi::clause4(&mut self.solver, l1, l2, l3, l4);
+    pub fn clause4<L: Literal>(&mut self, l1: L, l2: L, l3: L, l4: L) {
+        ffi::clause4(
+            &mut self.solver,
+            l1.dimacs(),
+            l2.dimacs(),
+            l3.dimacs(),
+            l4.dimacs(),
+        );
+        self.control.resume();
     }
 
     #[inline]
-    pub fn clause5(&mut self, l1: i32, l2: i32, l3: i32, l4: i32, l5: i32) {
-        ffi::clause5(&mut self.solver, l1, l2, l3, l4, l5);
+    pub fn clause5<L: Literal>(&mut self, l1: L, l2: L, l3: L, l4: L, l5: L) {
+        ffi::clause5(
+            &mut self.solver,
+            l1.dimacs(),
+            l2.dimacs(),
+            l3.dimacs(),
+            l4.dimacs(),
+            l5.dimacs(),
+        );
+        self.control.resume();
     }
 
     #[inline]
-    pub fn clause6(&mut self, v: &[i32]) {
-        ffi::clause6(&mut self.solver, v);
+    pub fn clause6<L: Literal>(&mut self, v: &[L]) {
+        ffi::clause6(&mut self.solver, L::dimacs_slice(v));
+        self.control.resume();
     }
 
     /// This function can be used to check if the formula is already
@@ -363,8 +443,8 @@ impl CaDiCal {
     ///   ensure (STEADY )
     ///
     #[inline]
-    pub fn assume(&mut self, lit: i32) {
-        ffi::assume(&mut self.solver, lit);
+    pub fn assume(&mut self, lit: impl Literal) {
+        ffi::assume(&mut self.solver, lit.dimacs());
     }
 
     /// Try to solve the current formula.  Returns
@@ -381,9 +461,32 @@ impl CaDiCal {
     /// i.e., from a different thread or from a signal handler.  Only right
     /// before returning from this call it goes into a 'READY' state.
     ///
+    /// A panic raised by a connected call-back stops the search and is raised
+    /// again when this call returns.  Panics raised while adding clauses or
+    /// during other calls are raised by the next call to 'solve'.  A status
+    /// other than 0, 10 or 20 is returned as 'UNKNOWN'.
+    ///
+    /// # Panics
+    ///
+    /// If a connected call-back panicked, see '`try_solve`'.
+    ///
     #[inline]
     pub fn solve(&mut self) -> Status {
-        ffi::solve(&mut self.solver).into()
+        settled(self.try_solve())
+    }
+
+    /// Same as 'solve' but returns the panic of a call-back as
+    /// '`Error::Panic`' instead of raising it.
+    ///
+    /// # Errors
+    ///
+    /// '`Error::Panic`' if a connected call-back panicked and
+    /// '`Error::InvalidStatus`' if the solver returned an unknown status.
+    ///
+    #[inline]
+    pub fn try_solve(&mut self) -> Result<Status, Error> {
+        let status = ffi::solve(&mut self.solver);
+        self.settle(status)
     }
 
     /// Get value (-lit=false, lit=true) of valid non-zero literal.
@@ -392,8 +495,19 @@ impl CaDiCal {
     ///   ensure (SATISFIED)
     ///
     #[inline]
-    pub fn val(&mut self, lit: i32) -> i32 {
-        ffi::val(&mut self.solver, lit)
+    pub fn val(&mut self, lit: impl Literal) -> i32 {
+        ffi::val(&mut self.solver, lit.dimacs())
+    }
+
+    /// Same as '`val`' but returns whether 'lit' is true in the model.
+    ///
+    ///   require (SATISFIED)
+    ///   ensure (SATISFIED)
+    ///
+    #[inline]
+    pub fn value(&mut self, lit: impl Literal) -> bool {
+        let lit = lit.dimacs();
+        ffi::val(&mut self.solver, lit) == lit
     }
 
     /// Try to flip the value of the given literal without falsifying the
@@ -425,8 +539,8 @@ impl CaDiCal {
     ///   ensure (SATISFIED)
     ///
     #[inline]
-    pub fn flip(&mut self, lit: i32) -> bool {
-        ffi::flip(&mut self.solver, lit)
+    pub fn flip(&mut self, lit: impl Literal) -> bool {
+        ffi::flip(&mut self.solver, lit.dimacs())
     }
 
     /// Same as 'flip' without actually flipping it. This functionality is
@@ -436,8 +550,8 @@ impl CaDiCal {
     ///   ensure (SATISFIED)
     ///
     #[inline]
-    pub fn flippable(&mut self, lit: i32) -> bool {
-        ffi::flippable(&mut self.solver, lit)
+    pub fn flippable(&mut self, lit: impl Literal) -> bool {
+        ffi::flippable(&mut self.solver, lit.dimacs())
     }
 
     /// Determine whether the valid non-zero literal is in the core.
@@ -448,106 +562,170 @@ impl CaDiCal {
     ///   ensure (UNSATISFIED)
     ///
     #[inline]
-    pub fn failed(&mut self, lit: i32) -> bool {
-        ffi::failed(&mut self.solver, lit)
+    pub fn failed(&mut self, lit: impl Literal) -> bool {
+        ffi::failed(&mut self.solver, lit.dimacs())
     }
 
     /// Add call-back which is checked regularly for termination.  There can
     /// only be one terminator connected.  If a second (non-zero) one is added
     /// the first one is implicitly disconnected.
     ///
+    /// The terminator stays connected while `f` runs and is disconnected when
+    /// `f` returns (or unwinds), so it is borrowed for exactly as long as the
+    /// solver may call it.
+    ///
     ///   require (VALID)
     ///   ensure (VALID)
     ///
-    #[inline]
-    #[allow(clippy::missing_panics_doc)]
-    pub fn connect_terminator<'a, 'b: 'a, T: Terminator>(&'a mut self, terminator: &'b mut T) {
-        fn f<T: Terminator>(state: *mut u8) -> bool {
-            let ptr: *mut T = state.cast::<T>();
-            let i = unsafe { &mut *ptr };
-            i.terminated()
-        }
-        let terminator =
-            unsafe { ffi::new_terminator(std::ptr::from_mut(terminator).cast::<u8>(), f::<T>) };
-        self.last_terminator = Some(terminator);
-        ffi::connect_terminator(&mut self.solver, self.last_terminator.as_mut().unwrap());
+    pub fn with_terminator<T: Terminator, R>(
+        &mut self,
+        terminator: &mut T,
+        f: impl FnOnce(&mut Self) -> R,
+    ) -> R {
+        let binding = callbacks::bind(&self.control, terminator);
+        let connection = Connection::borrowed(&binding, callbacks::terminator_hook::<T>);
+        self.attach_terminator(connection);
+        self.lend(binding, Self::release_terminator, f)
+    }
+
+    /// Same as '`with_terminator`' but the solver takes ownership of the
+    /// terminator, which stays connected until it is disconnected or replaced.
+    ///
+    ///   require (VALID)
+    ///   ensure (VALID)
+    ///
+    pub fn connect_terminator(&mut self, terminator: Box<dyn Terminator>) {
+        let connection = Connection::owned(
+            &self.control,
+            terminator,
+            callbacks::terminator_hook::<Box<dyn Terminator>>,
+        );
+        self.attach_terminator(connection);
+    }
+
+    /// Connects a closure as terminator, for instance a deadline check.
+    /// Solving stops once it returns true.
+    ///
+    ///   require (VALID)
+    ///   ensure (VALID)
+    ///
+    pub fn connect_terminator_fn(&mut self, terminated: impl FnMut() -> bool + Send + 'static) {
+        self.connect_terminator(Box::new(callbacks::FnTerminator(terminated)));
     }
 
+    /// Disconnects the current terminator and hands back the box given to
+    /// '`connect_terminator`', if it was connected that way.  The box can be
+    /// downcast to the type it was made from, see '`dyn Terminator::downcast`'.
     #[inline]
-    pub fn disconnect_terminator(&mut self) {
-        ffi::disconnect_terminator(&mut self.solver);
-        self.last_terminator = None;
+    pub fn disconnect_terminator(&mut self) -> Option<Box<dyn Terminator>> {
+        ffi::connect_terminator(&mut self.solver, &mut self.root_terminator);
+        self.control.resume();
+        self.last_terminator.take().and_then(Connection::into_owned)
     }
 
     /// Add call-back which allows to export learned clauses.
     ///
+    /// The learner is connected only while `f` runs.
+    ///
     ///   require (VALID)
     ///   ensure (VALID)
     ///
-    #[inline]
-    #[allow(clippy::missing_panics_doc)]
-    pub fn connect_learner<'a, 'b: 'a, T: Learner>(&'a mut self, learner: &'b mut T) {
-        fn learning<T: Learner>(state: *mut u8, size: i32) -> bool {
-            let ptr: *mut T = state.cast::<T>();
-            let i = unsafe { &mut *ptr };
-            i.learning(size)
-        }
+    pub fn with_learner<L: Learner, R>(
+        &mut self,
+        learner: &mut L,
+        f: impl FnOnce(&mut Self) -> R,
+    ) -> R {
+        let binding = callbacks::bind(&self.control, learner);
+        let connection = Connection::borrowed(&binding, callbacks::learner_hook::<L>);
+        self.attach_learner(connection);
+        self.lend(binding, Self::release_learner, f)
+    }
 
-        fn learn<T: Learner>(state: *mut u8, lit: i32) {
-            let ptr: *mut T = state.cast::<T>();
-            let i = unsafe { &mut *ptr };
-            i.learn(lit);
-        }
+    /// Owned version of '`with_learner`'.
+    ///
+    ///   require (VALID)
+    ///   ensure (VALID)
+    ///
+    pub fn connect_learner(&mut self, learner: Box<dyn Learner>) {
+        let connection = Connection::owned(
+            &self.control,
+            learner,
+            callbacks::learner_hook::<Box<dyn Learner>>,
+        );
+        self.attach_learner(connection);
+    }
 
-        let learner = unsafe {
-            ffi::new_learner(
-                std::ptr::from_mut(learner).cast::<u8>(),
-                learning::<T>,
-                learn::<T>,
-            )
-        };
-        self.last_learner = Some(learner);
-        ffi::connect_learner(&mut self.solver, self.last_learner.as_mut().unwrap());
+    /// Connects a closure which is given every learned clause with at most
+    /// `max_size` literals.
+    ///
+    ///   require (VALID)
+    ///   ensure (VALID)
+    ///
+    pub fn connect_learner_fn(
+        &mut self,
+        max_size: i32,
+        learn: impl FnMut(&[Lit]) + Send + 'static,
+    ) {
+        self.connect_learner(Box::new(callbacks::FnLearner::new(max_size, learn)));
     }
 
+    /// Same as '`disconnect_terminator`' for the learner.
     #[inline]
-    pub fn disconnect_learner(&mut self) {
+    pub fn disconnect_learner(&mut self) -> Option<Box<dyn Learner>> {
         ffi::disconnect_learner(&mut self.solver);
-        self.last_learner = None;
+        self.control.resume();
+        self.last_learner.take().and_then(Connection::into_owned)
     }
 
     /// Add call-back which allows to observe when a variable is fixed.
     ///
+    /// The listener is connected only while `f` runs.
+    ///
     ///   require (VALID)
     ///   ensure (VALID)
     ///
-    #[allow(clippy::missing_panics_doc)]
-    pub fn connect_fixed_listener<'a, 'b: 'a, F: FixedAssignmentListener>(
-        &'a mut self,
-        fixed_listener: &'b mut F,
-    ) {
-        fn notify_fixed_assignment<F: FixedAssignmentListener>(state: *mut u8, lit: i32) {
-            let ptr: *mut F = state.cast::<F>();
-            let i = unsafe { &mut *ptr };
-            i.notify_fixed_assignment(lit);
-        }
-
-        let s = std::ptr::from_mut(fixed_listener).cast::<u8>();
-
-        let listener =
-            unsafe { ffi::new_fixed_assignment_listener(s, notify_fixed_assignment::<F>) };
+    pub fn with_fixed_listener<F: FixedAssignmentListener, R>(
+        &mut self,
+        fixed_listener: &mut F,
+        f: impl FnOnce(&mut Self) -> R,
+    ) -> R {
+        let binding = callbacks::bind(&self.control, fixed_listener);
+        let connection = Connection::borrowed(&binding, callbacks::fixed_listener_hook::<F>);
+        self.attach_fixed_listener(connection);
+        self.lend(binding, Self::release_fixed_listener, f)
+    }
 
-        // Store the listener in self to prevent it from being dropped
-        self.last_fixed_listener = Some(listener);
+    /// Owned version of '`with_fixed_listener`'.
+    ///
+    ///   require (VALID)
+    ///   ensure (VALID)
+    ///
+    pub fn connect_fixed_listener(&mut self, fixed_listener: Box<dyn FixedAssignmentListener>) {
+        let connection = Connection::owned(
+            &self.control,
+            fixed_listener,
+            callbacks::fixed_listener_hook::<Box<dyn FixedAssignmentListener>>,
+        );
+        self.attach_fixed_listener(connection);
+    }
 
-        // Use the stored listener
-        ffi::connect_fixed_listener(&mut self.solver, self.last_fixed_listener.as_mut().unwrap());
+    /// Connects a closure which is given every literal that becomes fixed.
+    ///
+    ///   require (VALID)
+    ///   ensure (VALID)
+    ///
+    pub fn connect_fixed_listener_fn(&mut self, notify: impl FnMut(Lit) + Send + 'static) {
+        self.connect_fixed_listener(Box::new(callbacks::FnFixedListener(notify)));
     }
 
+    /// Same as '`disconnect_terminator`' for the fixed listener.
     #[inline]
-    pub fn disconnect_fixed_listener(&mut self) {
+    pub fn disconnect_fixed_listener(&mut self) -> Option<Box<dyn FixedAssignmentListener>> {
         ffi::disconnect_fixed_listener(&mut self.solver);
-        self.last_fixed_listener = None;
+        self.control.resume();
+        self.last_fixed_listener
+            .take()
+            .and_then(Connection::into_owned)
     }
 
     /// Add call-back which allows to learn, propagate and backtrack based on
@@ -557,88 +735,43 @@ impl CaDiCal {
     /// Disconnection of the external propagator resets all the observed
     /// variables.
     ///
+    /// The propagator is connected only while `f` runs.
+    ///
     ///   require (VALID)
     ///   ensure (VALID)
     ///
-    #[allow(clippy::missing_panics_doc)]
-    pub fn connect_external_propagator<'a, 'b: 'a, T: ExternalPropagator>(
-        &'a mut self,
-        propagator: &'b mut T,
-    ) {
-        fn notify_assignment<T: ExternalPropagator>(state: *mut u8, x: &[i32]) {
-            let ptr: *mut T = state.cast::<T>();
-            let i = unsafe { &mut *ptr };
-            i.notify_assignment(x);
-        }
-        fn notify_new_decision_level<T: ExternalPropagator>(state: *mut u8) {
-            let ptr: *mut T = state.cast::<T>();
-            let i = unsafe { &mut *ptr };
-            i.notify_new_decision_level();
-        }
-        fn notify_backtrack<T: ExternalPropagator>(state: *mut u8, x: usize) {
-            let ptr: *mut T = state.cast::<T>();
-            let i = unsafe { &mut *ptr };
-            i.notify_backtrack(x);
-        }
-        fn cb_check_found_model<T: ExternalPropagator>(state: *mut u8, x: &[i32]) -> bool {
-            let ptr: *mut T = state.cast::<T>();
-            let i = unsafe { &mut *ptr };
-            i.cb_check_found_model(x)
-        }
-        fn cb_decide<T: ExternalPropagator>(state: *mut u8) -> i32 {
-            let ptr: *mut T = state.cast::<T>();
-            let i = unsafe { &mut *ptr };
-            i.cb_decide()
-        }
-        fn cb_propagate<T: ExternalPropagator>(state: *mut u8) -> i32 {
-            let ptr: *mut T = state.cast::<T>();
-            let i = unsafe { &mut *ptr };
-            i.cb_propagate()
-        }
-        fn cb_add_reason_clause_lit<T: ExternalPropagator>(state: *mut u8, x: i32) -> i32 {
-            let ptr: *mut T = state.cast::<T>();
-            let i = unsafe { &mut *ptr };
-            i.cb_add_reason_clause_lit(x)
-        }
-        fn cb_has_external_clause<T: ExternalPropagator>(state: *mut u8, x: *mut bool) -> bool {
-            let ptr: *mut T = state.cast::<T>();
-            let i = unsafe { &mut *ptr };
-            i.cb_has_external_clause(unsafe { &mut *x })
-        }
-        fn cb_add_external_clause_lit<T: ExternalPropagator>(state: *mut u8) -> i32 {
-            let ptr: *mut T = state.cast::<T>();
-            let i = unsafe { &mut *ptr };
-            i.cb_add_external_clause_lit()
-        }
-        let is_lazy = propagator.is_lazy();
-        let are_reasons_forgettable = propagator.are_reasons_forgettable();
-
-        let external_propagator = unsafe {
-            ffi::new_external_propagator(
-                std::ptr::from_mut(propagator).cast::<u8>(),
-                is_lazy,
-                are_reasons_forgettable,
-                notify_assignment::<T>,
-                notify_new_decision_level::<T>,
-                notify_backtrack::<T>,
-                cb_check_found_model::<T>,
-                cb_decide::<T>,
-                cb_propagate::<T>,
-                cb_add_reason_clause_lit::<T>,
-                cb_has_external_clause::<T>,
-                cb_add_external_clause_lit::<T>,
-            )
-        };
+    pub fn with_external_propagator<T: ExternalPropagator, R>(
+        &mut self,
+        propagator: &mut T,
+        f: impl FnOnce(&mut Self) -> R,
+    ) -> R {
+        let binding = callbacks::bind(&self.control, propagator);
+        let connection = Connection::borrowed(&binding, callbacks::external_propagator_hook::<T>);
+        self.attach_external_propagator(connection);
+        self.lend(binding, Self::release_external_propagator, f)
+    }
 
-        self.last_external_propagator = Some(external_propagator);
-        ffi::connect_external_propagator(
-            &mut self.solver,
-            self.last_external_propagator.as_mut().unwrap(),
+    /// Owned version of '`with_external_propagator`'.
+    ///
+    ///   require (VALID)
+    ///   ensure (VALID)
+    ///
+    pub fn connect_external_propagator(&mut self, propagator: Box<dyn ExternalPropagator>) {
+        let connection = Connection::owned(
+            &self.control,
+            propagator,
+            callbacks::external_propagator_hook::<Box<dyn ExternalPropagator>>,
         );
+        self.attach_external_propagator(connection);
     }
-    pub fn disconnect_external_propagator(&mut self) {
+
+    /// Same as '`disconnect_terminator`' for the external propagator.
+    pub fn disconnect_external_propagator(&mut self) -> Option<Box<dyn ExternalPropagator>> {
         ffi::disconnect_external_propagator(&mut self.solver);
-        self.last_external_propagator = None;
+        self.control.resume();
+        self.last_external_propagator
+            .take()
+            .and_then(Connection::into_owned)
     }
 
     /// Mark as 'observed' those variables that are relevant to the external
@@ -654,8 +787,9 @@ impl CaDiCal {
     ///   ensure (`VALID_OR_SOLVING`)
     ///
     #[inline]
-    pub fn add_observed_var(&mut self, var: i32) {
-        ffi::add_observed_var(&mut self.solver, var);
+    pub fn add_observed_var(&mut self, var: impl Variable) {
+        ffi::add_observed_var(&mut self.solver, var.dimacs());
+        self.control.resume();
     }
 
     /// Removes the 'observed' flag from the given variable. A variable can be
@@ -666,8 +800,9 @@ impl CaDiCal {
     ///   ensure (VALID)
     ///
     #[inline]
-    pub fn remove_observed_var(&mut self, var: i32) {
-        ffi::remove_observed_var(&mut self.solver, var);
+    pub fn remove_observed_var(&mut self, var: impl Variable) {
+        ffi::remove_observed_var(&mut self.solver, var.dimacs());
+        self.control.resume();
     }
 
     /// Removes all the 'observed' flags from the variables. Disconnecting the
@@ -679,6 +814,7 @@ impl CaDiCal {
     #[inline]
     pub fn reset_observed_vars(&mut self) {
         ffi::reset_observed_vars(&mut self.solver);
+        self.control.resume();
     }
 
     /// Get reason of valid observed literal (true = it is an observed variable
@@ -689,8 +825,8 @@ impl CaDiCal {
     ///   ensure (`VALID_OR_SOLVING`)
     ///
     #[inline]
-    pub fn is_decision(&mut self, lit: i32) -> bool {
-        ffi::is_decision(&mut self.solver, lit)
+    pub fn is_decision(&mut self, lit: impl Literal) -> bool {
+        ffi::is_decision(&mut self.solver, lit.dimacs())
     }
 
     /// Force solve to backtrack to certain decision level. Can be called only
@@ -705,6 +841,7 @@ impl CaDiCal {
     #[inline]
     pub fn force_backtrack(&mut self, new_level: usize) {
         ffi::force_backtrack(&mut self.solver, new_level);
+        self.control.resume();
     }
 
     //------------------------------------------------------------------------
@@ -722,8 +859,8 @@ impl CaDiCal {
     ///   READY if (!lit) && !`adding_clause` ensure (STEADY ) // and thus READY
     ///
     #[inline]
-    pub fn constrain(&mut self, lit: i32) {
-        ffi::constrain(&mut self.solver, lit);
+    pub fn constrain(&mut self, lit: impl Literal) {
+        ffi::constrain(&mut self.solver, lit::clause_item(lit));
     }
 
     /// Determine whether the constraint was used to proof the
@@ -750,39 +887,77 @@ impl CaDiCal {
     ///
     #[inline]
     pub fn lookahead(&mut self) -> i32 {
-        ffi::lookahead(&mut self.solver)
+        let lit = ffi::lookahead(&mut self.solver);
+        self.control.clear_stop();
+        self.control.resume();
+        lit
     }
 
     #[inline]
     pub fn generate_cubes(&mut self, x: i32, min_depth: i32, result_cubes: &mut Vec<i32>) -> i32 {
-        ffi::generate_cubes(&mut self.solver, x, min_depth, result_cubes)
+        let status = ffi::generate_cubes(&mut self.solver, x, min_depth, result_cubes);
+        self.control.clear_stop();
+        self.control.resume();
+        status
     }
 
     #[inline]
     pub fn reset_assumptions(&mut self) {
         ffi::reset_assumptions(&mut self.solver);
+        self.control.resume();
     }
 
     #[inline]
     pub fn reset_constraint(&mut self) {
         ffi::reset_constraint(&mut self.solver);
+        self.control.resume();
     }
 
-    /// Return the current state of the solver as defined above.
+    /// Return the current state of the solver as defined in '`State`'.
     ///
     #[must_use]
     #[inline]
     pub fn state(&self) -> State {
-        ffi::state(&self.solver).into()
+        State::from_bits_retain(ffi::state(&self.solver))
     }
 
     /// Similar to 'state ()' but using the staddard competition exit codes of
     /// '10' for 'SATISFIABLE', '20' for 'UNSATISFIABLE' and '0' otherwise.
+    /// A status other than these is returned as 'UNKNOWN'.
     ///
     #[must_use]
     #[inline]
     pub fn status(&self) -> Status {
-        ffi::status(&self.solver).into()
+        self.try_status().unwrap_or(Status::UNKNOWN)
+    }
+
+    /// Same as 'status' but reports unknown statuses.
+    ///
+    /// # Errors
+    ///
+    /// '`Error::InvalidStatus`' if `CaDiCaL` reports a status other than 0, 10
+    /// or 20.
+    ///
+    #[inline]
+    pub fn try_status(&self) -> Result<Status, Error> {
+        Status::try_from(ffi::status(&self.solver))
+    }
+
+    /// Returns a view of the solver which checks the 'require' contracts
+    /// documented here before each call and returns an error where `CaDiCaL`
+    /// would abort the process.
+    ///
+    #[inline]
+    pub fn checked(&mut self) -> Checked<'_> {
+        Checked::new(self)
+    }
+
+    /// Returns a view of the solver which only takes '`Lit`' arguments, so
+    /// that neither raw integers nor variables can be passed as literals.
+    ///
+    #[inline]
+    pub fn typed(&mut self) -> Typed<'_> {
+        Typed::new(self)
     }
 
     /// return version string
@@ -897,6 +1072,21 @@ impl CaDiCal {
         ffi::set(&mut self.solver, name, val)
     }
 
+    /// Same as 'set' but reports an unknown option as an error.
+    ///
+    /// # Errors
+    ///
+    /// '`Error::InvalidOption`' if 'set' returned 'false'.
+    ///
+    #[inline]
+    pub fn try_set(&mut self, name: String, val: i32) -> Result<(), Error> {
+        if ffi::set(&mut self.solver, name.clone(), val) {
+            Ok(())
+        } else {
+            Err(Error::InvalidOption(name))
+        }
+    }
+
     /// This function accepts options in command line syntax:
     ///
     ///   '--<name>=<val>', '--<name>', or '--no-<name>'
@@ -934,6 +1124,21 @@ impl CaDiCal {
         ffi::configure(&mut self.solver, name)
     }
 
+    /// Same as 'configure' but reports an unknown configuration as an error.
+    ///
+    /// # Errors
+    ///
+    /// '`Error::InvalidConfiguration`' if 'configure' returned 'false'.
+    ///
+    #[inline]
+    pub fn try_configure(&mut self, name: String) -> Result<(), Error> {
+        if ffi::configure(&mut self.solver, name.clone()) {
+            Ok(())
+        } else {
+            Err(Error::InvalidConfiguration(name))
+        }
+    }
+
     /// Increase preprocessing and inprocessing limits by '10^<val>'.  Values
     /// below '0' are ignored and values above '9' are reduced to '9'.
     ///
@@ -966,6 +1171,21 @@ impl CaDiCal {
     pub fn limit(&mut self, arg: String, val: i32) -> bool {
         ffi::limit(&mut self.solver, arg, val)
     }
+
+    /// Same as 'limit' but reports an unknown limit as an error.
+    ///
+    /// # Errors
+    ///
+    /// '`Error::InvalidLimit`' if 'limit' returned 'false'.
+    ///
+    #[inline]
+    pub fn try_limit(&mut self, arg: String, val: i32) -> Result<(), Error> {
+        if ffi::limit(&mut self.solver, arg.clone(), val) {
+            Ok(())
+        } else {
+            Err(Error::InvalidLimit(arg))
+        }
+    }
     #[inline]
     pub fn is_valid_limit(&mut self, arg: String) -> bool {
         ffi::is_valid_limit(&mut self.solver, arg)
@@ -1015,9 +1235,26 @@ impl CaDiCal {
     ///   require (READY)
     ///   ensure (STEADY  | SATISFIED | UNSATISFIED)
     ///
+    /// # Panics
+    ///
+    /// If a connected call-back panicked, see '`try_simplify`'.
+    ///
     #[inline]
     pub fn simplify(&mut self, rounds: i32) -> Status {
-        ffi::simplify(&mut self.solver, rounds).into()
+        settled(self.try_simplify(rounds))
+    }
+
+    /// Same as 'simplify' but returns the panic of a call-back as
+    /// '`Error::Panic`' instead of raising it.
+    ///
+    /// # Errors
+    ///
+    /// Those of '`try_solve`'.
+    ///
+    #[inline]
+    pub fn try_simplify(&mut self, rounds: i32) -> Result<Status, Error> {
+        let status = ffi::simplify(&mut self.solver, rounds);
+        self.settle(status)
     }
 
     //------------------------------------------------------------------------
@@ -1031,6 +1268,16 @@ impl CaDiCal {
         ffi::terminate(&mut self.solver);
     }
 
+    /// Handle which forces termination from any thread, see
+    /// '`TerminateHandle`'.  It keeps working while this solver is inside
+    /// 'solve' and regardless of which terminator is connected.
+    ///
+    #[must_use]
+    #[inline]
+    pub fn terminate_handle(&self) -> TerminateHandle {
+        self.control.terminate_handle()
+    }
+
     //------------------------------------------------------------------------
 
     /// We have the following common reference counting functions, which avoid
@@ -1063,18 +1310,18 @@ impl CaDiCal {
     ///
     #[must_use]
     #[inline]
-    pub fn frozen(&self, lit: i32) -> bool {
-        ffi::frozen(&self.solver, lit)
+    pub fn frozen(&self, lit: impl Literal) -> bool {
+        ffi::frozen(&self.solver, lit.dimacs())
     }
 
     #[inline]
-    pub fn freeze(&mut self, lit: i32) {
-        ffi::freeze(&mut self.solver, lit);
+    pub fn freeze(&mut self, lit: impl Literal) {
+        ffi::freeze(&mut self.solver, lit.dimacs());
     }
 
     #[inline]
-    pub fn melt(&mut self, lit: i32) {
-        ffi::melt(&mut self.solver, lit);
+    pub fn melt(&mut self, lit: impl Literal) {
+        ffi::melt(&mut self.solver, lit.dimacs());
     }
 
     //------------------------------------------------------------------------
@@ -1087,21 +1334,21 @@ impl CaDiCal {
     ///
     #[must_use]
     #[inline]
-    pub fn fixed(&self, lit: i32) -> i32 {
-        ffi::fixed(&self.solver, lit)
+    pub fn fixed(&self, lit: impl Literal) -> i32 {
+        ffi::fixed(&self.solver, lit.dimacs())
     }
 
     //------------------------------------------------------------------------
     /// Force the default decision phase of a variable to a certain value.
     ///
     #[inline]
-    pub fn phase(&mut self, lit: i32) {
-        ffi::phase(&mut self.solver, lit);
+    pub fn phase(&mut self, lit: impl Literal) {
+        ffi::phase(&mut self.solver, lit.dimacs());
     }
 
     #[inline]
-    pub fn unphase(&mut self, lit: i32) {
-        ffi::unphase(&mut self.solver, lit);
+    pub fn unphase(&mut self, lit: impl Literal) {
+        ffi::unphase(&mut self.solver, lit.dimacs());
     }
 
     //------------------------------------------------------------------------
@@ -1115,14 +1362,81 @@ impl CaDiCal {
     ///
     /// Write DRAT proof.
     #[inline]
-    pub fn trace_proof1(&mut self, file: String, name: String) -> bool {
-        ffi::trace_proof1(&mut self.solver, file, name)
+    pub fn trace_proof1(&mut self, file: impl AsRef<Path>, name: String) -> bool {
+        self.try_trace_proof1(file, name).is_ok()
+    }
+
+    /// Same as '`trace_proof1`' but says why tracing could not be enabled.
+    ///
+    /// # Errors
+    ///
+    /// '`Error::Open`' if 'file' cannot be opened for writing.
+    ///
+    pub fn try_trace_proof1(&mut self, file: impl AsRef<Path>, name: String) -> Result<(), Error> {
+        let file = file.as_ref();
+        let compression = Compression::from_path(file);
+        let proof = if compression == Compression::None {
+            ffi::trace_proof1(&mut self.solver, path::bytes(file)?, name)
+                .map_err(|exception| Error::open(file, Error::os(&exception)))?
+        } else {
+            self.trace_proof_stream(file, compression, name)?
+        };
+        self.keep_proof_file(file, proof)
     }
 
     /// Open & write proof.
     #[inline]
-    pub fn trace_proof2(&mut self, path: String) -> bool {
-        ffi::trace_proof2(&mut self.solver, path)
+    pub fn trace_proof2(&mut self, path: impl AsRef<Path>) -> bool {
+        self.try_trace_proof2(path).is_ok()
+    }
+
+    /// Same as '`trace_proof2`' but returns an error if the proof file could
+    /// not be opened.
+    ///
+    /// # Errors
+    ///
+    /// '`Error::Open`' if 'path' cannot be opened for writing and
+    /// '`Error::Solver`' if '`trace_proof2`' fails otherwise.
+    ///
+    #[inline]
+    pub fn try_trace_proof2(&mut self, path: impl AsRef<Path>) -> Result<(), Error> {
+        let path = path.as_ref();
+        let compression = Compression::from_path(path);
+        if compression != Compression::None {
+            return self.trace_proof_compressed(path, compression);
+        }
+        let traced = ffi::trace_proof2(&mut self.solver, path::bytes(path)?)
+            .map_err(|exception| Error::open(path, Error::os(&exception)))?;
+        if traced {
+            Ok(())
+        } else {
+            Err(Error::Solver(format!(
+                "failed to trace proof to '{}'",
+                path.display()
+            )))
+        }
+    }
+
+    /// Same as '`try_trace_proof2`' but compresses the proof in Rust with
+    /// 'compression', whatever the extension of 'path'.  The file is complete
+    /// once the solver is dropped.
+    ///
+    ///   require (CONFIGURING)
+    ///   ensure (CONFIGURING)
+    ///
+    /// # Errors
+    ///
+    /// '`Error::Open`' if 'path' cannot be created and '`Error::Solver`' if
+    /// tracing fails otherwise.
+    ///
+    pub fn trace_proof_compressed(
+        &mut self,
+        path: impl AsRef<Path>,
+        compression: Compression,
+    ) -> Result<(), Error> {
+        let path = path.as_ref();
+        let proof = self.trace_proof_stream(path, compression, path.display().to_string())?;
+        self.keep_proof_file(path, proof)
     }
 
     /// Flushing the proof trace file eventually calls 'fflush' on the actual
@@ -1164,164 +1478,138 @@ impl CaDiCal {
         ffi::close_proof_trace(&mut self.solver, print);
     }
 
-    // /// Enables clausal proof tracing with or without antecedents using
-    // /// the Tracer interface defined in 'tracer.hpp'
-    // ///
-    // /// InternalTracer, StatTracer and FileTracer for internal use
-    // ///
-    // ///   require (CONFIGURING)
-    // ///   ensure (CONFIGURING)
-    // ///
-    #[allow(clippy::missing_panics_doc)]
-    #[allow(clippy::too_many_lines)]
-    pub fn connect_proof_tracer1<'a, 'b: 'a, T: ProofTracer>(
-        &'a mut self,
-        tracer: &'b mut T,
-        antecedents: bool,
-    ) {
-        fn add_original_clause<T: ProofTracer>(
-            state: *mut u8,
-            id: u64,
-            redundant: bool,
-            clause: &[i32],
-            restored: bool,
-        ) {
-            let ptr: *mut T = state.cast::<T>();
-            let t = unsafe { &mut *ptr };
-            t.add_original_clause(id, redundant, clause, restored);
-        }
-
-        fn add_derived_clause<T: ProofTracer>(
-            state: *mut u8,
-            id: u64,
-            redundant: bool,
-            clause: &[i32],
-            antecedents: &[u64],
-        ) {
-            let ptr: *mut T = state.cast::<T>();
-            let t = unsafe { &mut *ptr };
-            t.add_derived_clause(id, redundant, clause, antecedents);
-        }
-
-        fn delete_clause<T: ProofTracer>(state: *mut u8, id: u64, redundant: bool, clause: &[i32]) {
-            let ptr: *mut T = state.cast::<T>();
-            let t = unsafe { &mut *ptr };
-            t.delete_clause(id, redundant, clause);
-        }
-
-        fn weaken_minus<T: ProofTracer>(state: *mut u8, id: u64, clause: &[i32]) {
-            let ptr: *mut T = state.cast::<T>();
-            let t = unsafe { &mut *ptr };
-            t.weaken_minus(id, clause);
-        }
-
-        fn strengthen<T: ProofTracer>(state: *mut u8, id: u64) {
-            let ptr: *mut T = state.cast::<T>();
-            let t = unsafe { &mut *ptr };
-            t.strengthen(id);
-        }
-
-        fn finalize_clause<T: ProofTracer>(state: *mut u8, id: u64, clause: &[i32]) {
-            let ptr: *mut T = state.cast::<T>();
-            let t = unsafe { &mut *ptr };
-            t.finalize_clause(id, clause);
-        }
-
-        fn add_assumption<T: ProofTracer>(state: *mut u8, lit: i32) {
-            let ptr: *mut T = state.cast::<T>();
-            let t = unsafe { &mut *ptr };
-            t.add_assumption(lit);
-        }
-
-        fn add_constraint<T: ProofTracer>(state: *mut u8, clause: &[i32]) {
-            let ptr: *mut T = state.cast::<T>();
-            let t = unsafe { &mut *ptr };
-            t.add_constraint(clause);
-        }
-
-        fn reset_assumptions<T: ProofTracer>(state: *mut u8) {
-            let ptr: *mut T = state.cast::<T>();
-            let t = unsafe { &mut *ptr };
-            t.reset_assumptions();
-        }
-
-        fn add_assumption_clause<T: ProofTracer>(
-            state: *mut u8,
-            id: u64,
-            clause: &[i32],
-            antecedents: &[u64],
-        ) {
-            let ptr: *mut T = state.cast::<T>();
-            let t = unsafe { &mut *ptr };
-            t.add_assumption_clause(id, clause, antecedents);
-        }
-
-        fn conclude_sat<T: ProofTracer>(state: *mut u8, conclusion_type: i32, model: &[i32]) {
-            let ptr: *mut T = state.cast::<T>();
-            let t = unsafe { &mut *ptr };
-            t.conclude_sat(conclusion_type, model);
+    /// Same as '`close_proof_trace`' but also closes the files opened for
+    /// '`trace_proof1`' and '`trace_proof_compressed`', which are otherwise
+    /// closed silently when the solver is dropped, and reports if that fails.
+    /// For compressed proofs closing finishes the encoder.
+    ///
+    ///   require (VALID)
+    ///   ensure (VALID)
+    ///
+    /// # Errors
+    ///
+    /// '`Error::Io`' with the first error of closing a file.
+    ///
+    pub fn try_close_proof_trace(&mut self, print: bool) -> Result<(), Error> {
+        ffi::close_proof_trace(&mut self.solver, print);
+        let mut result = Ok(());
+        for mut proof in std::mem::take(&mut self.proof_files) {
+            if let Err(exception) = ffi::close_proof_file(&mut proof) {
+                result = result.and(Err(Error::Io(Error::os(&exception))));
+            }
         }
+        result
+    }
 
-        fn conclude_unsat<T: ProofTracer>(
-            state: *mut u8,
-            conclusion_type: i32,
-            clause_ids: &[u64],
-        ) {
-            let ptr: *mut T = state.cast::<T>();
-            let t = unsafe { &mut *ptr };
-            t.conclude_unsat(conclusion_type, clause_ids);
-        }
+    /// Enables clausal proof tracing with or without antecedents using
+    /// the Tracer interface defined in 'tracer.hpp'.  With 'finalize' set
+    /// the tracer is also given every clause still present when the proof
+    /// ends through '`ProofTracer::finalize_clause`', as FRAT needs.  The
+    /// tracer is connected only while `f` runs and is disconnected
+    /// afterwards.
+    ///
+    ///   require (CONFIGURING)
+    ///   ensure (CONFIGURING)
+    ///
+    pub fn with_proof_tracer<T: ProofTracer, R>(
+        &mut self,
+        tracer: &mut T,
+        antecedents: bool,
+        finalize: bool,
+        f: impl FnOnce(&mut Self) -> R,
+    ) -> R {
+        let binding = callbacks::bind(&self.control, tracer);
+        let connection = Connection::borrowed(&binding, callbacks::tracer_hook::<T>);
+        self.attach_tracer(connection, antecedents, finalize);
+        self.lend(binding, Self::release_tracer, f)
+    }
 
-        fn conclude_unknown<T: ProofTracer>(state: *mut u8, trail: &[i32]) {
-            let ptr: *mut T = state.cast::<T>();
-            let t = unsafe { &mut *ptr };
-            t.conclude_unknown(trail);
-        }
+    /// Owned version of '`with_proof_tracer`'.  The tracer stays connected,
+    /// next to any other tracers, until '`disconnect_proof_tracer1`' hands
+    /// it back.
+    ///
+    ///   require (CONFIGURING)
+    ///   ensure (CONFIGURING)
+    ///
+    pub fn connect_proof_tracer1(
+        &mut self,
+        tracer: Box<dyn ProofTracer>,
+        antecedents: bool,
+        finalize: bool,
+    ) -> TracerId {
+        let connection = Connection::owned(
+            &self.control,
+            tracer,
+            callbacks::tracer_hook::<Box<dyn ProofTracer>>,
+        );
+        self.attach_tracer(connection, antecedents, finalize)
+    }
 
-        let tracer_ptr = unsafe {
-            ffi::new_tracer(
-                std::ptr::from_mut(tracer).cast::<u8>(),
-                add_original_clause::<T>,
-                add_derived_clause::<T>,
-                delete_clause::<T>,
-                weaken_minus::<T>,
-                strengthen::<T>,
-                finalize_clause::<T>,
-                add_assumption::<T>,
-                add_constraint::<T>,
-                reset_assumptions::<T>,
-                add_assumption_clause::<T>,
-                conclude_sat::<T>,
-                conclude_unsat::<T>,
-                conclude_unknown::<T>,
-            )
-        };
+    // The overloads of 'connect_proof_tracer' for internal, stat and file
+    // tracers are named after their tracers, since only the stat tracers of
+    // `CaDiCaL` are used and file tracers write with the writers of 'proof'.
 
-        // Store the tracer to prevent it from being dropped
-        self.last_tracer = Some(tracer_ptr);
+    /// Connects a stat tracer of `CaDiCaL`, which the solver keeps until
+    /// '`disconnect_stat_tracer`' hands it back.
+    ///
+    ///   require (CONFIGURING)
+    ///   ensure (CONFIGURING)
+    ///
+    pub fn connect_stat_tracer(&mut self, mut tracer: StatTracer, antecedents: bool) -> TracerId {
+        ffi::connect_proof_tracer3(&mut self.solver, &mut tracer.tracer, antecedents, false);
+        let id = self.next_tracer_id();
+        self.stat_tracers.push((id, tracer));
+        id
+    }
 
-        // Connect the tracer to the solver
+    /// Connects a file tracer, with antecedents if its format needs them,
+    /// which the solver keeps until '`disconnect_file_tracer`' hands it
+    /// back.
+    ///
+    ///   require (CONFIGURING)
+    ///   ensure (CONFIGURING)
+    ///
+    pub fn connect_file_tracer(&mut self, tracer: FileTracer) -> TracerId {
+        let antecedents = tracer.format.antecedents();
+        let finalize = tracer.format == ProofFormat::Frat;
+        let mut connection = Connection::owned(
+            &self.control,
+            Box::new(tracer),
+            callbacks::tracer_hook::<FileTracer>,
+        );
         ffi::connect_proof_tracer1(
             &mut self.solver,
-            self.last_tracer.as_mut().unwrap(),
+            &mut connection.hook,
             antecedents,
+            finalize,
         );
+        let id = self.next_tracer_id();
+        self.file_tracers.push((id, connection));
+        id
     }
 
-    // pub fn connect_proof_tracer2(
-    //     &mut self,
-    //     tracer: &mut UniquePtr<InternalTracer>,
-    //     antecedents: bool,
-    // ) {
-    //     todo!()
-    // }
-    // pub fn connect_proof_tracer3(&mut self, tracer: &mut UniquePtr<StatTracer>, antecedents: bool) {
-    //     todo!()
-    // }
+    /// The ids of all connected proof tracers in the order of connection,
+    /// including those of '`with_proof_tracer`'.
+    #[must_use]
+    pub fn proof_tracers(&self) -> Vec<TracerId> {
+        let tracers = self.tracers.iter().map(|(id, _)| *id);
+        let stat_tracers = self.stat_tracers.iter().map(|(id, _)| *id);
+        let file_tracers = self.file_tracers.iter().map(|(id, _)| *id);
+        let mut ids: Vec<TracerId> = tracers.chain(stat_tracers).chain(file_tracers).collect();
+        ids.sort_unstable();
+        ids
+    }
 
-    // pub fn connect_proof_tracer4(&mut self, tracer: &mut UniquePtr<FileTracer>, antecedents: bool) {
-    //     todo!()
-    // }
+    /// The counts of the stat or file tracer 'id', 'None' if it is not
+    /// connected.
+    #[must_use]
+    pub fn proof_statistics(&self, id: TracerId) -> Option<ProofStatistics> {
+        if let Some((_, tracer)) = self.stat_tracers.iter().find(|(other, _)| *other == id) {
+            return Some(tracer.statistics());
+        }
+        let (_, tracer) = self.file_tracers.iter().find(|(other, _)| *other == id)?;
+        tracer.object().map(FileTracer::statistics)
+    }
 
     /// Triggers the conclusion of incremental proofs.
     /// if the solver is SATISFIED it will trigger extend ()
@@ -1340,27 +1628,50 @@ impl CaDiCal {
     #[inline]
     pub fn conclude(&mut self) {
         ffi::conclude(&mut self.solver);
+        self.control.resume();
+    }
+
+    /// Disconnects the tracer 'id' of '`connect_proof_tracer1`' and hands it
+    /// back, to be downcast like the terminator of '`disconnect_terminator`'.
+    /// Returns 'None' if it is not connected or was borrowed by
+    /// '`with_proof_tracer`', which is disconnected nevertheless.
+    ///
+    ///   require (VALID)
+    ///   ensure (VALID)
+    ///
+    pub fn disconnect_proof_tracer1(&mut self, id: TracerId) -> Option<Box<dyn ProofTracer>> {
+        let mut tracer = take_tracer(&mut self.tracers, id)?;
+        ffi::disconnect_proof_tracer1(&mut self.solver, &mut tracer.hook);
+        self.control.resume();
+        tracer.into_owned()
+    }
+
+    /// Disconnects the stat tracer 'id' of '`connect_stat_tracer`' and
+    /// hands it back.
+    ///
+    ///   require (VALID)
+    ///   ensure (VALID)
+    ///
+    pub fn disconnect_stat_tracer(&mut self, id: TracerId) -> Option<StatTracer> {
+        let mut tracer = take_tracer(&mut self.stat_tracers, id)?;
+        ffi::disconnect_proof_tracer2(&mut self.solver, &mut tracer.tracer);
+        self.control.resume();
+        Some(tracer)
+    }
+
+    /// Disconnects the file tracer 'id' of '`connect_file_tracer`' and
+    /// hands it back.  Its file is complete once it is dropped.
+    ///
+    ///   require (VALID)
+    ///   ensure (VALID)
+    ///
+    pub fn disconnect_file_tracer(&mut self, id: TracerId) -> Option<FileTracer> {
+        let mut tracer = take_tracer(&mut self.file_tracers, id)?;
+        ffi::disconnect_proof_tracer1(&mut self.solver, &mut tracer.hook);
+        self.control.resume();
+        tracer.into_owned().map(|tracer| *tracer)
     }
 
-    // /// Disconnect proof tracer. If this is not done before deleting
-    // /// the tracer will be deleted. Returns true if successful.
-    // ///
-    // ///   require (VALID)
-    // ///   ensure (VALID)
-    // ///
-    pub fn disconnect_proof_tracer1(&mut self) {
-        if let Some(tracer) = &mut self.last_tracer {
-            ffi::disconnect_proof_tracer1(&mut self.solver, tracer);
-        }
-        self.last_tracer = None;
-    }
-    // pub fn disconnect_proof_tracer2(&mut self, tracer: &mut UniquePtr<StatTracer>) -> bool {
-    //     todo!()
-    // }
-    // pub fn disconnect_proof_tracer3(&mut self, tracer: &mut UniquePtr<FileTracer>) -> bool {
-    //     todo!()
-    // }
-
     /// print usage information for long options
     #[inline]
     pub fn usage() {
@@ -1409,38 +1720,32 @@ impl CaDiCal {
     ///
     #[inline]
     pub fn traverse_clauses<I: ClauseIterator>(&self, i: &mut I) -> bool {
-        fn f<I: ClauseIterator>(state: *mut u8, clause: &[i32]) -> bool {
-            let ptr: *mut I = state.cast::<I>();
-            let i = unsafe { &mut *ptr };
-            i.clause(clause)
-        }
-        let mut iter =
-            unsafe { ffi::new_clause_iterator(std::ptr::from_mut(i).cast::<u8>(), f::<I>) };
-        ffi::traverse_clauses(&self.solver, &mut iter)
+        let control = Arc::new(Control::default());
+        let binding = callbacks::bind(&control, i);
+        let mut iter = callbacks::clause_iterator_hook::<I>(&binding);
+        let completed = ffi::traverse_clauses(&self.solver, &mut iter);
+        control.resume();
+        completed
     }
 
     #[inline]
     pub fn traverse_witnesses_backward<I: WitnessIterator>(&self, i: &mut I) -> bool {
-        fn f<I: WitnessIterator>(state: *mut u8, clause: &[i32], witness: &[i32], id: u64) -> bool {
-            let ptr: *mut I = state.cast::<I>();
-            let i = unsafe { &mut *ptr };
-            i.witness(clause, witness, id)
-        }
-        let mut iter =
-            unsafe { ffi::new_witness_iterator(std::ptr::from_mut(i).cast::<u8>(), f::<I>) };
-        ffi::traverse_witnesses_backward(&self.solver, &mut iter)
+        let control = Arc::new(Control::default());
+        let binding = callbacks::bind(&control, i);
+        let mut iter = callbacks::witness_iterator_hook::<I>(&binding);
+        let completed = ffi::traverse_witnesses_backward(&self.solver, &mut iter);
+        control.resume();
+        completed
     }
 
     #[inline]
     pub fn traverse_witnesses_forward<I: WitnessIterator>(&self, i: &mut I) -> bool {
-        fn f<I: WitnessIterator>(state: *mut u8, clause: &[i32], witness: &[i32], id: u64) -> bool {
-            let ptr: *mut I = state.cast::<I>();
-            let i = unsafe { &mut *ptr };
-            i.witness(clause, witness, id)
-        }
-        let mut iter =
-            unsafe { ffi::new_witness_iterator(std::ptr::from_mut(i).cast::<u8>(), f::<I>) };
-        ffi::traverse_witnesses_forward(&self.solver, &mut iter)
+        let control = Arc::new(Control::default());
+        let binding = callbacks::bind(&control, i);
+        let mut iter = callbacks::witness_iterator_hook::<I>(&binding);
+        let completed = ffi::traverse_witnesses_forward(&self.solver, &mut iter);
+        control.resume();
+        completed
     }
 
     //------------------------------------------------------------------------
@@ -1448,31 +1753,91 @@ impl CaDiCal {
     /// if appropriate helper functions 'gzip' etc. are available.  They are
     /// called through opening a pipe to an external command.
     ///
+    /// With the cargo features 'gzip', 'xz', 'bzip2' and 'zstd' files with
+    /// the matching extension are instead (de)compressed in Rust, see
+    /// '`Compression::from_path`'.  The '`_compressed`' functions take the
+    /// codec explicitly.
+    ///
     /// If the 'strict' argument is zero then the number of variables and
     /// clauses specified in the DIMACS headers are ignored, i.e., the header
     /// 'p cnf 0 0' is always legal.  If the 'strict' argument is larger '1'
     /// strict formatting of the header is required, i.e., single spaces
     /// everywhere and no trailing white space.
     ///
-    /// Returns zero if successful and otherwise an error message.
+    /// Errors of `CaDiCaL`'s parser are returned as '`Error::Solver`'.
     ///
     ///   require (VALID)
     ///   ensure (VALID)
     ///
+    /// # Errors
+    ///
+    /// '`Error::Open`' if 'file' cannot be opened and '`Error::Solver`' if
+    /// it cannot be parsed.
+    ///
     #[inline]
     pub fn read_dimacs1(
         &mut self,
-        file: String,
+        file: impl AsRef<Path>,
         name: String,
         vars: &mut i32,
         strict: i32,
-    ) -> String {
-        ffi::read_dimacs1(&mut self.solver, file, name, vars, strict)
+    ) -> Result<(), Error> {
+        let file = file.as_ref();
+        if let Some(buffer) = compress::decompressed(file)? {
+            return self.read_dimacs_buffer(&buffer, &name, vars, strict);
+        }
+        let message = ffi::read_dimacs1(&mut self.solver, path::bytes(file)?, name, vars, strict)
+            .map_err(|exception| Error::open(file, Error::os(&exception)))?;
+        self.reported(message)
     }
 
+    /// The file is opened once and handed to `CaDiCaL`, unless it spawns an
+    /// external decompressor for the extension of 'path', in which case a
+    /// failure to open it is only reported as '`Error::Solver`'.
+    ///
+    /// # Errors
+    ///
+    /// '`Error::Open`' if 'path' cannot be opened and '`Error::Solver`' if
+    /// it cannot be parsed.
+    ///
     #[inline]
-    pub fn read_dimacs2(&mut self, path: String, vars: &mut i32, strict: i32) -> String {
-        ffi::read_dimacs2(&mut self.solver, path, vars, strict)
+    pub fn read_dimacs2(
+        &mut self,
+        path: impl AsRef<Path>,
+        vars: &mut i32,
+        strict: i32,
+    ) -> Result<(), Error> {
+        let path = path.as_ref();
+        if let Some(buffer) = compress::decompressed(path)? {
+            let name = path.display().to_string();
+            return self.read_dimacs_buffer(&buffer, &name, vars, strict);
+        }
+        let message = ffi::read_dimacs2(&mut self.solver, path::bytes(path)?, vars, strict)
+            .map_err(|exception| Error::open(path, Error::os(&exception)))?;
+        self.reported(message)
+    }
+
+    /// Same as '`read_dimacs2`' but decompresses in Rust with 'compression',
+    /// whatever the extension of 'path'.
+    ///
+    ///   require (VALID)
+    ///   ensure (VALID)
+    ///
+    /// # Errors
+    ///
+    /// '`Error::Open`' if 'path' cannot be opened, '`Error::Io`' if it
+    /// cannot be decompressed and '`Error::Solver`' if it cannot be parsed.
+    ///
+    pub fn read_dimacs_compressed(
+        &mut self,
+        path: impl AsRef<Path>,
+        compression: Compression,
+        vars: &mut i32,
+        strict: i32,
+    ) -> Result<(), Error> {
+        let path = path.as_ref();
+        let buffer = compress::read(path, compression)?;
+        self.read_dimacs_buffer(&buffer, &path.display().to_string(), vars, strict)
     }
 
     /// The following routines work the same way but parse both DIMACS and
@@ -1480,37 +1845,168 @@ impl CaDiCal {
     /// parser finds and 'p inccnf' header or cubes then '*incremental' is set
     /// to true and the cubes are stored in the given vector (each cube
     /// terminated by a zero).
+    ///
+    /// # Errors
+    ///
+    /// '`Error::Open`' if 'file' cannot be opened and '`Error::Solver`' if
+    /// it cannot be parsed.
+    ///
     #[inline]
     pub fn read_dimacs3(
         &mut self,
-        file: String,
+        file: impl AsRef<Path>,
         name: String,
         vars: &mut i32,
         strict: i32,
         incremental: &mut bool,
         cubes: &mut Vec<i32>,
-    ) -> String {
-        ffi::read_dimacs3(
+    ) -> Result<(), Error> {
+        let file = file.as_ref();
+        if let Some(buffer) = compress::decompressed(file)? {
+            return self.read_inccnf_buffer(&buffer, &name, vars, strict, incremental, cubes);
+        }
+        let message = ffi::read_dimacs3(
             &mut self.solver,
-            file,
+            path::bytes(file)?,
             name,
             vars,
             strict,
             incremental,
             cubes,
         )
+        .map_err(|exception| Error::open(file, Error::os(&exception)))?;
+        self.reported(message)
     }
 
+    /// The file is opened once and handed to `CaDiCaL`, unless it spawns an
+    /// external decompressor for the extension of 'path', in which case a
+    /// failure to open it is only reported as '`Error::Solver`'.
+    ///
+    /// # Errors
+    ///
+    /// '`Error::Open`' if 'path' cannot be opened and '`Error::Solver`' if
+    /// it cannot be parsed.
+    ///
     #[inline]
     pub fn read_dimacs4(
         &mut self,
-        path: String,
+        path: impl AsRef<Path>,
+        vars: &mut i32,
+        strict: i32,
+        incremental: &mut bool,
+        cubes: &mut Vec<i32>,
+    ) -> Result<(), Error> {
+        let path = path.as_ref();
+        if let Some(buffer) = compress::decompressed(path)? {
+            let name = path.display().to_string();
+            return self.read_inccnf_buffer(&buffer, &name, vars, strict, incremental, cubes);
+        }
+        let message = ffi::read_dimacs4(
+            &mut self.solver,
+            path::bytes(path)?,
+            vars,
+            strict,
+            incremental,
+            cubes,
+        )
+        .map_err(|exception| Error::open(path, Error::os(&exception)))?;
+        self.reported(message)
+    }
+
+    /// Same as '`read_dimacs1`' but reads the formula from 'reader', for
+    /// instance a socket or the output of a generator.  The input is read
+    /// completely before it is parsed.
+    ///
+    ///   require (VALID)
+    ///   ensure (VALID)
+    ///
+    /// # Errors
+    ///
+    /// '`Error::Io`' if reading fails and '`Error::Solver`' if the input
+    /// cannot be parsed.
+    ///
+    pub fn read_dimacs_from(
+        &mut self,
+        mut reader: impl Read,
+        vars: &mut i32,
+        strict: i32,
+    ) -> Result<(), Error> {
+        let mut buffer = Vec::new();
+        reader.read_to_end(&mut buffer)?;
+        self.read_dimacs_buffer(&buffer, "<reader>", vars, strict)
+    }
+
+    /// Same as '`read_dimacs1`' but parses 'dimacs' itself.
+    ///
+    ///   require (VALID)
+    ///   ensure (VALID)
+    ///
+    /// # Errors
+    ///
+    /// '`Error::Solver`' if 'dimacs' cannot be parsed.
+    ///
+    #[inline]
+    pub fn read_dimacs_str(
+        &mut self,
+        dimacs: &str,
+        vars: &mut i32,
+        strict: i32,
+    ) -> Result<(), Error> {
+        self.read_dimacs_buffer(dimacs.as_bytes(), "<string>", vars, strict)
+    }
+
+    /// Same as '`read_dimacs3`', parsing DIMACS and INCCNF, but reads the
+    /// formula from 'reader'.  The input is read completely before it is
+    /// parsed.
+    ///
+    ///   require (VALID)
+    ///   ensure (VALID)
+    ///
+    /// # Errors
+    ///
+    /// '`Error::Io`' if reading fails and '`Error::Solver`' if the input
+    /// cannot be parsed.
+    ///
+    pub fn read_inccnf_from(
+        &mut self,
+        mut reader: impl Read,
+        vars: &mut i32,
+        strict: i32,
+        incremental: &mut bool,
+        cubes: &mut Vec<i32>,
+    ) -> Result<(), Error> {
+        let mut buffer = Vec::new();
+        reader.read_to_end(&mut buffer)?;
+        self.read_inccnf_buffer(&buffer, "<reader>", vars, strict, incremental, cubes)
+    }
+
+    /// Same as '`read_dimacs3`', parsing DIMACS and INCCNF, but parses
+    /// 'inccnf' itself.
+    ///
+    ///   require (VALID)
+    ///   ensure (VALID)
+    ///
+    /// # Errors
+    ///
+    /// '`Error::Solver`' if 'inccnf' cannot be parsed.
+    ///
+    #[inline]
+    pub fn read_inccnf_str(
+        &mut self,
+        inccnf: &str,
         vars: &mut i32,
         strict: i32,
         incremental: &mut bool,
         cubes: &mut Vec<i32>,
-    ) -> String {
-        ffi::read_dimacs4(&mut self.solver, path, vars, strict, incremental, cubes)
+    ) -> Result<(), Error> {
+        self.read_inccnf_buffer(
+            inccnf.as_bytes(),
+            "<string>",
+            vars,
+            strict,
+            incremental,
+            cubes,
+        )
     }
 
     //------------------------------------------------------------------------
@@ -1521,52 +2017,403 @@ impl CaDiCal {
     /// The '`min_max_var`' parameter gives a lower bound on the number '<vars>'
     /// of variables used in the DIMACS 'p cnf <vars> ...' header.
     ///
-    /// Returns zero if successful and otherwise an error message.
-    ///
     ///   require (VALID)
     ///   ensure (VALID)
     ///
+    /// # Errors
+    ///
+    /// '`Error::Open`' if 'path' cannot be opened for writing and
+    /// '`Error::Solver`' if writing fails.
+    ///
     #[inline]
-    pub fn write_dimacs(&mut self, path: String, min_max_var: i32) -> String {
-        ffi::write_dimacs(&mut self.solver, path, min_max_var)
+    pub fn write_dimacs(&mut self, path: impl AsRef<Path>, min_max_var: i32) -> Result<(), Error> {
+        let path = path.as_ref();
+        let compression = Compression::from_path(path);
+        if compression != Compression::None {
+            return self.write_dimacs_compressed(path, compression, min_max_var);
+        }
+        let message = ffi::write_dimacs(&mut self.solver, path::bytes(path)?, min_max_var)
+            .map_err(|exception| Error::open(path, Error::os(&exception)))?;
+        Error::reported(message)
+    }
+
+    /// Same as '`write_dimacs`' but compresses in Rust with 'compression',
+    /// whatever the extension of 'path'.
+    ///
+    ///   require (VALID)
+    ///   ensure (VALID)
+    ///
+    /// # Errors
+    ///
+    /// '`Error::Open`' if 'path' cannot be created and '`Error::Io`' if
+    /// writing fails.
+    ///
+    pub fn write_dimacs_compressed(
+        &mut self,
+        path: impl AsRef<Path>,
+        compression: Compression,
+        min_max_var: i32,
+    ) -> Result<(), Error> {
+        let mut encoder = compress::create(path.as_ref(), compression)?;
+        self.write_dimacs_to(&mut encoder, min_max_var)?;
+        encoder.finish()?;
+        Ok(())
+    }
+
+    /// Same as '`write_dimacs`' but writes to 'writer', which is buffered
+    /// here and flushed at the end.
+    ///
+    ///   require (VALID)
+    ///   ensure (VALID)
+    ///
+    /// # Errors
+    ///
+    /// '`Error::Io`' if writing fails.
+    ///
+    pub fn write_dimacs_to(&mut self, writer: impl Write, min_max_var: i32) -> Result<(), Error> {
+        let mut counter = stream::ClauseCounter::default();
+        self.traverse_clauses(&mut counter);
+        let mut clauses = stream::ClauseWriter::new(io::BufWriter::new(writer));
+        clauses.header(counter.vars.max(min_max_var), counter.clauses)?;
+        if !self.traverse_clauses(&mut clauses) {
+            let error = clauses.error.take();
+            return Err(error
+                .unwrap_or_else(|| io::Error::other("clause traversal stopped"))
+                .into());
+        }
+        clauses.flush()?;
+        Ok(())
     }
 
     /// The extension stack for reconstruction a solution can be written too.
     ///
+    /// # Errors
+    ///
+    /// '`Error::Open`' if 'path' cannot be opened for writing and
+    /// '`Error::Solver`' if writing fails.
+    ///
     #[inline]
-    pub fn write_extension(&mut self, path: String) -> String {
-        ffi::write_extension(&mut self.solver, path)
+    pub fn write_extension(&mut self, path: impl AsRef<Path>) -> Result<(), Error> {
+        let path = path.as_ref();
+        let compression = Compression::from_path(path);
+        if compression != Compression::None {
+            return self.write_extension_compressed(path, compression);
+        }
+        let message = ffi::write_extension(&mut self.solver, path::bytes(path)?)
+            .map_err(|exception| Error::open(path, Error::os(&exception)))?;
+        Error::reported(message)
+    }
+
+    /// Same as '`write_extension`' but compresses in Rust with 'compression',
+    /// whatever the extension of 'path'.  The entries are collected through
+    /// '`Extension::from_solver`' and written in the same format.
+    ///
+    /// # Errors
+    ///
+    /// '`Error::Open`' if 'path' cannot be created and '`Error::Io`' if
+    /// writing fails.
+    ///
+    pub fn write_extension_compressed(
+        &mut self,
+        path: impl AsRef<Path>,
+        compression: Compression,
+    ) -> Result<(), Error> {
+        let mut encoder = compress::create(path.as_ref(), compression)?;
+        extension::Extension::from_solver(self).write(&mut encoder)?;
+        encoder.finish()?;
+        Ok(())
     }
 
     /// Print build configuration to a file with prefix 'c '.  If the file
     /// is '<stdout>' or '<stderr>' then terminal color codes might be used.
     ///
+    /// # Errors
+    ///
+    /// '`Error::Open`' if 'file' cannot be opened.
+    ///
     #[inline]
-    pub fn build(file: String, prefix: String) {
-        ffi::build(file, prefix);
+    pub fn build(file: impl AsRef<Path>, prefix: String) -> Result<(), Error> {
+        let file = file.as_ref();
+        ffi::build(path::bytes(file)?, prefix)
+            .map_err(|exception| Error::open(file, Error::os(&exception)))
+    }
+}
+
+impl CaDiCal {
+    fn trace_proof_stream(
+        &mut self,
+        path: &Path,
+        compression: Compression,
+        name: String,
+    ) -> Result<UniquePtr<ffi::ProofFile>, Error> {
+        let stream = Box::into_raw(Box::new(compress::create(path, compression)?));
+        // SAFETY: 'stream' stays valid until the file closes it, or is freed
+        // here if the file could not be created
+        unsafe {
+            ffi::trace_proof_stream(
+                &mut self.solver,
+                stream.cast(),
+                compress::proof_stream_write,
+                compress::proof_stream_close,
+                name,
+            )
+        }
+        .map_err(|exception| {
+            drop(unsafe { Box::from_raw(stream) });
+            Error::open(path, Error::os(&exception))
+        })
+    }
+
+    /// Keeps a proof file of '`trace_proof1`' open until the solver is
+    /// deleted, where null means that tracing failed.
+    fn keep_proof_file(
+        &mut self,
+        path: &Path,
+        proof: UniquePtr<ffi::ProofFile>,
+    ) -> Result<(), Error> {
+        if proof.is_null() {
+            return Err(Error::Solver(format!(
+                "failed to trace proof to '{}'",
+                path.display()
+            )));
+        }
+        // the solver writes to the file until it is deleted
+        self.proof_files.push(proof);
+        Ok(())
+    }
+
+    fn read_dimacs_buffer(
+        &mut self,
+        buffer: &[u8],
+        name: &str,
+        vars: &mut i32,
+        strict: i32,
+    ) -> Result<(), Error> {
+        let message =
+            ffi::read_dimacs_buffer1(&mut self.solver, buffer, name.to_string(), vars, strict)
+                .map_err(|exception| Error::os(&exception))?;
+        self.reported(message)
+    }
+
+    fn read_inccnf_buffer(
+        &mut self,
+        buffer: &[u8],
+        name: &str,
+        vars: &mut i32,
+        strict: i32,
+        incremental: &mut bool,
+        cubes: &mut Vec<i32>,
+    ) -> Result<(), Error> {
+        let message = ffi::read_dimacs_buffer2(
+            &mut self.solver,
+            buffer,
+            name.to_string(),
+            vars,
+            strict,
+            incremental,
+            cubes,
+        )
+        .map_err(|exception| Error::os(&exception))?;
+        self.reported(message)
+    }
+
+    /// Raises a panic parked by the tracers called while reading clauses and
+    /// converts the message of the parser.
+    fn reported(&self, message: String) -> Result<(), Error> {
+        self.control.resume();
+        Error::reported(message)
+    }
+
+    /// Takes a panic parked by a call-back and converts the status returned
+    /// by the solver.
+    fn settle(&self, status: i32) -> Result<Status, Error> {
+        self.control.clear_stop();
+        match self.control.take_panic() {
+            Some(payload) => Err(Error::Panic(payload)),
+            None => Status::try_from(status),
+        }
+    }
+
+    /// Runs `f` and then ends the scoped connection made through `binding`.
+    fn lend<R>(
+        &mut self,
+        binding: Binding,
+        release: fn(&mut CaDiCal, &Binding),
+        f: impl FnOnce(&mut CaDiCal) -> R,
+    ) -> R {
+        let lend = Lend {
+            solver: self,
+            binding,
+            release,
+        };
+        f(&mut *lend.solver)
+    }
+
+    fn attach_terminator(&mut self, mut connection: Connection<ffi::Terminator, dyn Terminator>) {
+        ffi::connect_terminator(&mut self.solver, &mut connection.hook);
+        self.last_terminator = Some(connection);
+    }
+
+    fn release_terminator(&mut self, binding: &Binding) {
+        if Connection::is_bound_to(self.last_terminator.as_ref(), binding) {
+            self.disconnect_terminator();
+        }
+    }
+
+    fn attach_learner(&mut self, mut connection: Connection<ffi::Learner, dyn Learner>) {
+        ffi::connect_learner(&mut self.solver, &mut connection.hook);
+        self.last_learner = Some(connection);
+    }
+
+    fn release_learner(&mut self, binding: &Binding) {
+        if Connection::is_bound_to(self.last_learner.as_ref(), binding) {
+            self.disconnect_learner();
+        }
+    }
+
+    fn attach_fixed_listener(
+        &mut self,
+        mut connection: Connection<ffi::FixedAssignmentListener, dyn FixedAssignmentListener>,
+    ) {
+        ffi::connect_fixed_listener(&mut self.solver, &mut connection.hook);
+        self.last_fixed_listener = Some(connection);
+    }
+
+    fn release_fixed_listener(&mut self, binding: &Binding) {
+        if Connection::is_bound_to(self.last_fixed_listener.as_ref(), binding) {
+            self.disconnect_fixed_listener();
+        }
+    }
+
+    fn attach_external_propagator(
+        &mut self,
+        mut connection: Connection<ffi::ExternalPropagator, dyn ExternalPropagator>,
+    ) {
+        ffi::connect_external_propagator(&mut self.solver, &mut connection.hook);
+        self.last_external_propagator = Some(connection);
+    }
+
+    fn release_external_propagator(&mut self, binding: &Binding) {
+        if Connection::is_bound_to(self.last_external_propagator.as_ref(), binding) {
+            self.disconnect_external_propagator();
+        }
+    }
+
+    fn attach_tracer(
+        &mut self,
+        mut connection: Connection<ffi::Tracer, dyn ProofTracer>,
+        antecedents: bool,
+        finalize: bool,
+    ) -> TracerId {
+        ffi::connect_proof_tracer1(
+            &mut self.solver,
+            &mut connection.hook,
+            antecedents,
+            finalize,
+        );
+        let id = self.next_tracer_id();
+        self.tracers.push((id, connection));
+        id
+    }
+
+    fn release_tracer(&mut self, binding: &Binding) {
+        let bound = self
+            .tracers
+            .iter()
+            .find(|(_, connection)| Connection::is_bound_to(Some(connection), binding));
+        if let Some(&(id, _)) = bound {
+            self.disconnect_proof_tracer1(id);
+        }
+    }
+
+    fn next_tracer_id(&mut self) -> TracerId {
+        self.last_tracer_id += 1;
+        TracerId(self.last_tracer_id)
     }
 }
 
+/// The status of a '`try_`' function for its infallible form, which raises
+/// the panic of a call-back and takes unknown statuses as 'UNKNOWN'.
+fn settled(result: Result<Status, Error>) -> Status {
+    match result {
+        Ok(status) => status,
+        Err(Error::Panic(payload)) => std::panic::resume_unwind(payload),
+        Err(_) => Status::UNKNOWN,
+    }
+}
+
+/// Removes the tracer 'id' from 'tracers'.
+fn take_tracer<T>(tracers: &mut Vec<(TracerId, T)>, id: TracerId) -> Option<T> {
+    let index = tracers.iter().position(|(other, _)| *other == id)?;
+    Some(tracers.remove(index).1)
+}
+
 /// Connected terminators are checked for termination regularly.  If the
 /// 'terminate' function of the terminator returns true the solver is
 /// terminated synchronously as soon it calls this function.
-pub trait Terminator {
+pub trait Terminator: Send {
     fn terminated(&mut self) -> bool;
+
+    #[doc(hidden)]
+    fn callback_type_id(&self, _: callbacks::Internal) -> TypeId
+    where
+        Self: 'static,
+    {
+        TypeId::of::<Self>()
+    }
+}
+
+impl<T: Terminator + ?Sized> Terminator for Box<T> {
+    fn terminated(&mut self) -> bool {
+        (**self).terminated()
+    }
 }
 
 /// Connected learners which can be used to export learned clauses.
 /// The 'learning' can check the size of the learn clause and only if it
 /// returns true then the individual literals of the learned clause are given
-/// to the learn through 'learn' one by one terminated by a zero literal.
-pub trait Learner {
+/// to the learn through 'learn' one by one terminated by 'None'.
+pub trait Learner: Send {
     fn learning(&mut self, size: i32) -> bool;
-    fn learn(&mut self, lit: i32);
+    fn learn(&mut self, lit: Option<Lit>);
+
+    #[doc(hidden)]
+    fn callback_type_id(&self, _: callbacks::Internal) -> TypeId
+    where
+        Self: 'static,
+    {
+        TypeId::of::<Self>()
+    }
+}
+
+impl<L: Learner + ?Sized> Learner for Box<L> {
+    fn learning(&mut self, size: i32) -> bool {
+        (**self).learning(size)
+    }
+
+    fn learn(&mut self, lit: Option<Lit>) {
+        (**self).learn(lit);
+    }
 }
 
 /// Connected listener gets notified whenever the truth value of a variable is
 /// fixed (for example during inprocessing or due to some derived unit clauses).
-pub trait FixedAssignmentListener {
-    fn notify_fixed_assignment(&mut self, lit: i32);
+pub trait FixedAssignmentListener: Send {
+    fn notify_fixed_assignment(&mut self, lit: Lit);
+
+    #[doc(hidden)]
+    fn callback_type_id(&self, _: callbacks::Internal) -> TypeId
+    where
+        Self: 'static,
+    {
+        TypeId::of::<Self>()
+    }
+}
+
+impl<F: FixedAssignmentListener + ?Sized> FixedAssignmentListener for Box<F> {
+    fn notify_fixed_assignment(&mut self, lit: Lit) {
+        (**self).notify_fixed_assignment(lit);
+    }
 }
 
 use std::vec::Vec;
@@ -1574,7 +2421,7 @@ use std::vec::Vec;
 /// Allows to connect an external propagator to propagate values to variables
 /// with an external clause as a reason or to learn new clauses during the
 /// CDCL loop (without restart).
-pub trait ExternalPropagator {
+pub trait ExternalPropagator: Send {
     /// lazy propagator only checks complete assignments
     fn is_lazy(&mut self) -> bool {
         false
@@ -1589,7 +2436,7 @@ pub trait ExternalPropagator {
     /// The notification is not necessarily eager. It usually happens before
     /// the call of propagator callbacks and when a driving clause is leading
     /// to an assignment.
-    fn notify_assignment(&mut self, lits: &[i32]);
+    fn notify_assignment(&mut self, lits: &[Lit]);
 
     fn notify_new_decision_level(&mut self);
 
@@ -1598,31 +2445,31 @@ pub trait ExternalPropagator {
     /// Check by the external propagator the found complete solution (after
     /// solution reconstruction). If it returns false, the propagator must
     /// provide an external clause during the next callback.
-    fn cb_check_found_model(&mut self, model: &[i32]) -> bool;
+    fn cb_check_found_model(&mut self, model: &[Lit]) -> bool;
 
     /// Ask the external propagator for the next decision literal. If it
-    /// returns 0, the solver makes its own choice.
-    fn cb_decide(&mut self) -> i32 {
-        0
+    /// returns 'None', the solver makes its own choice.
+    fn cb_decide(&mut self) -> Option<Lit> {
+        None
     }
 
     /// Ask the external propagator if there is an external propagation to make
     /// under the current assignment. It returns either a literal to be
-    /// propagated or 0, indicating that there is no external propagation under
+    /// propagated or 'None', indicating that there is no external propagation under
     /// the current assignment.
-    fn cb_propagate(&mut self) -> i32 {
-        0
+    fn cb_propagate(&mut self) -> Option<Lit> {
+        None
     }
 
     /// Ask the external propagator for the reason clause of a previous
     /// external propagation step (done by `cb_propagate`). The clause must be
-    /// added literal-by-literal closed with a 0. Further, the clause must
+    /// added literal-by-literal closed with a 'None'. Further, the clause must
     /// contain the propagated literal.
     ///
     /// The clause will be learned as an Irredundant Non-Forgettable Clause (see
     /// below at '`cb_has_external_clause`' more details about it).
-    fn cb_add_reason_clause_lit(&mut self, _propagated_lit: i32) -> i32 {
-        0
+    fn cb_add_reason_clause_lit(&mut self, _propagated_lit: Lit) -> Option<Lit> {
+        None
     }
 
     /// The following two functions are used to add external clauses to the
@@ -1639,7 +2486,7 @@ pub trait ExternalPropagator {
     /// backtracking (independently from the value of the chrono option and
     /// independently from being falsified or satisfied or unassigned) to level
     /// 0. Empty clause (or root falsified clause, see above) makes the problem
-    /// unsat and stops the search immediately. A literal 0 must close the
+    /// unsat and stops the search immediately. A 'None' must close the
     /// clause.
     ///
     /// The external propagator indicates that there is a clause to add.
@@ -1666,7 +2513,61 @@ pub trait ExternalPropagator {
     fn cb_has_external_clause(&mut self, is_forgettable: &mut bool) -> bool;
 
     /// The actual function called to add the external clause.
-    fn cb_add_external_clause_lit(&mut self) -> i32;
+    fn cb_add_external_clause_lit(&mut self) -> Option<Lit>;
+
+    #[doc(hidden)]
+    fn callback_type_id(&self, _: callbacks::Internal) -> TypeId
+    where
+        Self: 'static,
+    {
+        TypeId::of::<Self>()
+    }
+}
+
+impl<T: ExternalPropagator + ?Sized> ExternalPropagator for Box<T> {
+    fn is_lazy(&mut self) -> bool {
+        (**self).is_lazy()
+    }
+
+    fn are_reasons_forgettable(&mut self) -> bool {
+        (**self).are_reasons_forgettable()
+    }
+
+    fn notify_assignment(&mut self, lits: &[Lit]) {
+        (**self).notify_assignment(lits);
+    }
+
+    fn notify_new_decision_level(&mut self) {
+        (**self).notify_new_decision_level();
+    }
+
+    fn notify_backtrack(&mut self, new_level: usize) {
+        (**self).notify_backtrack(new_level);
+    }
+
+    fn cb_check_found_model(&mut self, model: &[Lit]) -> bool {
+        (**self).cb_check_found_model(model)
+    }
+
+    fn cb_decide(&mut self) -> Option<Lit> {
+        (**self).cb_decide()
+    }
+
+    fn cb_propagate(&mut self) -> Option<Lit> {
+        (**self).cb_propagate()
+    }
+
+    fn cb_add_reason_clause_lit(&mut self, propagated_lit: Lit) -> Option<Lit> {
+        (**self).cb_add_reason_clause_lit(propagated_lit)
+    }
+
+    fn cb_has_external_clause(&mut self, is_forgettable: &mut bool) -> bool {
+        (**self).cb_has_external_clause(is_forgettable)
+    }
+
+    fn cb_add_external_clause_lit(&mut self) -> Option<Lit> {
+        (**self).cb_add_external_clause_lit()
+    }
 }
 
 /// Allows to traverse all remaining irredundant clauses.  Satisfied and
@@ -1676,7 +2577,7 @@ pub trait ExternalPropagator {
 ///
 /// If 'clause' returns false traversal aborts early.
 pub trait ClauseIterator {
-    fn clause(&mut self, clause: &[i32]) -> bool;
+    fn clause(&mut self, clause: &[Lit]) -> bool;
 }
 
 /// Allows to traverse all clauses on the extension stack together with their
@@ -1694,49 +2595,122 @@ pub trait ClauseIterator {
 ///
 /// If 'witness' returns false traversal aborts early.
 pub trait WitnessIterator {
-    fn witness(&mut self, clause: &[i32], witness: &[i32], id: u64) -> bool;
+    fn witness(&mut self, clause: &[Lit], witness: &[Lit], id: u64) -> bool;
 }
 
+/// Identifies a connected proof tracer, unique for the solver which
+/// returned it.  The ids increase in the order of connection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TracerId(u64);
+
 /// Trait for proof tracing that allows you to track proof events in real-time.
 /// This is useful for SMT solvers that need to interleave SAT solver clauses
 /// with theory clauses to generate eDRAT proofs.
-pub trait ProofTracer {
+pub trait ProofTracer: Send {
     /// Called when an original clause is added to the solver
-    fn add_original_clause(&mut self, id: u64, redundant: bool, clause: &[i32], restored: bool);
+    fn add_original_clause(&mut self, id: u64, redundant: bool, clause: &[Lit], restored: bool);
 
     /// Called when a derived clause is learned by the SAT solver
-    fn add_derived_clause(&mut self, id: u64, redundant: bool, clause: &[i32], antecedents: &[u64]);
+    fn add_derived_clause(&mut self, id: u64, redundant: bool, clause: &[Lit], antecedents: &[u64]);
 
     /// Called when a clause is deleted
-    fn delete_clause(&mut self, id: u64, redundant: bool, clause: &[i32]);
+    fn delete_clause(&mut self, id: u64, redundant: bool, clause: &[Lit]);
 
     /// Called when a clause is weakened (might be restored later)
-    fn weaken_minus(&mut self, id: u64, clause: &[i32]);
+    fn weaken_minus(&mut self, id: u64, clause: &[Lit]);
 
     /// Called when a clause is strengthened
     fn strengthen(&mut self, id: u64);
 
     /// Called when a clause is finalized
-    fn finalize_clause(&mut self, id: u64, clause: &[i32]);
+    fn finalize_clause(&mut self, id: u64, clause: &[Lit]);
 
     /// Called when an assumption is added
-    fn add_assumption(&mut self, lit: i32);
+    fn add_assumption(&mut self, lit: Lit);
 
     /// Called when a constraint is added
-    fn add_constraint(&mut self, clause: &[i32]);
+    fn add_constraint(&mut self, clause: &[Lit]);
 
     /// Called when assumptions are reset
     fn reset_assumptions(&mut self);
 
     /// Called when an assumption clause is added (negation of failing assumptions)
-    fn add_assumption_clause(&mut self, id: u64, clause: &[i32], antecedents: &[u64]);
+    fn add_assumption_clause(&mut self, id: u64, clause: &[Lit], antecedents: &[u64]);
 
     /// Called when the solver concludes SAT
-    fn conclude_sat(&mut self, conclusion_type: i32, model: &[i32]);
+    fn conclude_sat(&mut self, conclusion_type: i32, model: &[Lit]);
 
     /// Called when the solver concludes UNSAT
     fn conclude_unsat(&mut self, conclusion_type: i32, clause_ids: &[u64]);
 
     /// Called when the solver concludes UNKNOWN
-    fn conclude_unknown(&mut self, trail: &[i32]);
+    fn conclude_unknown(&mut self, trail: &[Lit]);
+
+    #[doc(hidden)]
+    fn callback_type_id(&self, _: callbacks::Internal) -> TypeId
+    where
+        Self: 'static,
+    {
+        TypeId::of::<Self>()
+    }
+}
+
+impl<T: ProofTracer + ?Sized> ProofTracer for Box<T> {
+    fn add_original_clause(&mut self, id: u64, redundant: bool, clause: &[Lit], restored: bool) {
+        (**self).add_original_clause(id, redundant, clause, restored);
+    }
+
+    fn add_derived_clause(
+        &mut self,
+        id: u64,
+        redundant: bool,
+        clause: &[Lit],
+        antecedents: &[u64],
+    ) {
+        (**self).add_derived_clause(id, redundant, clause, antecedents);
+    }
+
+    fn delete_clause(&mut self, id: u64, redundant: bool, clause: &[Lit]) {
+        (**self).delete_clause(id, redundant, clause);
+    }
+
+    fn weaken_minus(&mut self, id: u64, clause: &[Lit]) {
+        (**self).weaken_minus(id, clause);
+    }
+
+    fn strengthen(&mut self, id: u64) {
+        (**self).strengthen(id);
+    }
+
+    fn finalize_clause(&mut self, id: u64, clause: &[Lit]) {
+        (**self).finalize_clause(id, clause);
+    }
+
+    fn add_assumption(&mut self, lit: Lit) {
+        (**self).add_assumption(lit);
+    }
+
+    fn add_constraint(&mut self, clause: &[Lit]) {
+        (**self).add_constraint(clause);
+    }
+
+    fn reset_assumptions(&mut self) {
+        (**self).reset_assumptions();
+    }
+
+    fn add_assumption_clause(&mut self, id: u64, clause: &[Lit], antecedents: &[u64]) {
+        (**self).add_assumption_clause(id, clause, antecedents);
+    }
+
+    fn conclude_sat(&mut self, conclusion_type: i32, model: &[Lit]) {
+        (**self).conclude_sat(conclusion_type, model);
+    }
+
+    fn conclude_unsat(&mut self, conclusion_type: i32, clause_ids: &[u64]) {
+        (**self).conclude_unsat(conclusion_type, clause_ids);
+    }
+
+    fn conclude_unknown(&mut self, trail: &[Lit]) {
+        (**self).conclude_unknown(trail);
+    }
 }
@@ -0,0 +1,284 @@
+use crate::Error;
+use std::{fmt, num::NonZeroI32, ops::Not};
+
+/// A variable, numbered from '1' as in DIMACS.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct Var(NonZeroI32);
+
+impl Var {
+    /// The variable with the given DIMACS index, 'None' unless it is
+    /// positive.
+    #[must_use]
+    #[inline]
+    pub const fn new(index: i32) -> Option<Self> {
+        match NonZeroI32::new(index) {
+            Some(index) if index.get() > 0 => Some(Self(index)),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    #[inline]
+    pub const fn index(self) -> i32 {
+        self.0.get()
+    }
+
+    #[must_use]
+    #[inline]
+    pub const fn positive(self) -> Lit {
+        Lit(self.0)
+    }
+
+    #[must_use]
+    #[inline]
+    pub const fn negative(self) -> Lit {
+        // variables are positive, so the negation does not overflow
+        Lit(self.0.wrapping_neg())
+    }
+
+    /// The literal of this variable with the given polarity.
+    #[must_use]
+    #[inline]
+    pub const fn lit(self, positive: bool) -> Lit {
+        if positive {
+            self.positive()
+        } else {
+            self.negative()
+        }
+    }
+}
+
+impl fmt::Display for Var {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// A literal, that is a variable or its negation.  Unlike the DIMACS
+/// integers used by `CaDiCaL` it can be neither '0' nor '`INT_MIN`'.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct Lit(NonZeroI32);
+
+impl Lit {
+    /// The literal of a DIMACS integer, 'None' for '0' and '`INT_MIN`'.
+    #[must_use]
+    #[inline]
+    pub const fn from_dimacs(lit: i32) -> Option<Self> {
+        match NonZeroI32::new(lit) {
+            Some(lit) if lit.get() != i32::MIN => Some(Self(lit)),
+            _ => None,
+        }
+    }
+
+    /// Views DIMACS integers as literals, 'None' if one of them is not a
+    /// literal.
+    #[must_use]
+    pub fn slice_from_dimacs(lits: &[i32]) -> Option<&[Self]> {
+        if lits.iter().all(|&lit| Self::from_dimacs(lit).is_some()) {
+            // SAFETY: 'Lit' is a transparent 'i32' and all values are valid
+            Some(unsafe { std::slice::from_raw_parts(lits.as_ptr().cast::<Self>(), lits.len()) })
+        } else {
+            None
+        }
+    }
+
+    /// Views literals as DIMACS integers.
+    #[must_use]
+    pub fn slice_to_dimacs(lits: &[Self]) -> &[i32] {
+        // SAFETY: 'Lit' is a transparent 'i32'
+        unsafe { std::slice::from_raw_parts(lits.as_ptr().cast::<i32>(), lits.len()) }
+    }
+
+    #[must_use]
+    #[inline]
+    pub const fn to_dimacs(self) -> i32 {
+        self.0.get()
+    }
+
+    #[must_use]
+    #[inline]
+    pub const fn var(self) -> Var {
+        // 'INT_MIN' is excluded, so the absolute value does not overflow
+        Var(self.0.abs())
+    }
+
+    #[must_use]
+    #[inline]
+    pub const fn is_positive(self) -> bool {
+        self.0.get() > 0
+    }
+
+    #[must_use]
+    #[inline]
+    pub const fn is_negative(self) -> bool {
+        self.0.get() < 0
+    }
+}
+
+impl Not for Lit {
+    type Output = Lit;
+
+    #[inline]
+    fn not(self) -> Lit {
+        Lit(self.0.wrapping_neg())
+    }
+}
+
+impl From<Lit> for i32 {
+    #[inline]
+    fn from(lit: Lit) -> i32 {
+        lit.to_dimacs()
+    }
+}
+
+impl TryFrom<i32> for Lit {
+    type Error = Error;
+
+    fn try_from(lit: i32) -> Result<Self, Error> {
+        Self::from_dimacs(lit).ok_or(Error::InvalidLiteral {
+            function: "Lit::try_from",
+            lit,
+        })
+    }
+}
+
+impl fmt::Display for Lit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+pub(crate) mod sealed {
+    pub trait Sealed: Copy {
+        /// The DIMACS integer as given, without validation.
+        fn raw(self) -> i32;
+    }
+
+    impl Sealed for i32 {
+        fn raw(self) -> i32 {
+            self
+        }
+    }
+
+    impl Sealed for super::Lit {
+        fn raw(self) -> i32 {
+            self.to_dimacs()
+        }
+    }
+
+    impl Sealed for super::Var {
+        fn raw(self) -> i32 {
+            self.index()
+        }
+    }
+}
+
+/// Raw DIMACS integers are checked here, since `CaDiCaL` aborts the process
+/// on '0' and '`INT_MIN`'.
+fn valid(lit: i32) -> i32 {
+    match Lit::from_dimacs(lit) {
+        Some(lit) => lit.to_dimacs(),
+        None => panic!(
+            "{}",
+            Error::InvalidLiteral {
+                function: "Literal::dimacs",
+                lit
+            }
+        ),
+    }
+}
+
+/// The argument of '`CaDiCal::add`', where '0' terminates the clause.
+pub(crate) fn clause_item(lit: impl Literal) -> i32 {
+    match lit.raw() {
+        0 => 0,
+        lit => valid(lit),
+    }
+}
+
+/// Literal arguments of '`CaDiCal`': either a '`Lit`' or a raw DIMACS
+/// integer.  A '`Var`' is not a literal.  Raw integers are validated before
+/// they reach `CaDiCaL`, which would abort on invalid ones, and '`Checked`'
+/// returns '`Error::InvalidLiteral`' for them instead.  '`Typed`' takes
+/// '`Lit`'s only.
+pub trait Literal: sealed::Sealed {
+    /// The DIMACS integer of the literal.
+    ///
+    /// # Panics
+    ///
+    /// If a raw integer is '0' or '`INT_MIN`'.
+    ///
+    fn dimacs(self) -> i32;
+
+    /// The DIMACS integers of the literals.
+    ///
+    /// # Panics
+    ///
+    /// If one of the raw integers is '0' or '`INT_MIN`'.
+    ///
+    fn dimacs_slice(lits: &[Self]) -> &[i32];
+}
+
+impl Literal for i32 {
+    #[inline]
+    fn dimacs(self) -> i32 {
+        valid(self)
+    }
+
+    #[inline]
+    fn dimacs_slice(lits: &[Self]) -> &[i32] {
+        for &lit in lits {
+            valid(lit);
+        }
+        lits
+    }
+}
+
+impl Literal for Lit {
+    #[inline]
+    fn dimacs(self) -> i32 {
+        self.to_dimacs()
+    }
+
+    #[inline]
+    fn dimacs_slice(lits: &[Self]) -> &[i32] {
+        Lit::slice_to_dimacs(lits)
+    }
+}
+
+/// Variable arguments of '`CaDiCal`': either a '`Var`' or a raw DIMACS
+/// index, which has to be positive.
+pub trait Variable: sealed::Sealed {
+    /// The DIMACS index of the variable.
+    ///
+    /// # Panics
+    ///
+    /// If a raw index is not positive.
+    ///
+    fn dimacs(self) -> i32;
+}
+
+impl Variable for i32 {
+    #[inline]
+    fn dimacs(self) -> i32 {
+        match Var::new(self) {
+            Some(var) => var.index(),
+            None => panic!(
+                "{}",
+                Error::InvalidLiteral {
+                    function: "Variable::dimacs",
+                    lit: self
+                }
+            ),
+        }
+    }
+}
+
+impl Variable for Var {
+    #[inline]
+    fn dimacs(self) -> i32 {
+        self.index()
+    }
+}
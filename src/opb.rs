@@ -0,0 +1,547 @@
+//! Pseudo-Boolean problems in the OPB format of the PB competitions, that is
+//! linear constraints over products of literals and an optional objective
+//! which is minimized.
+//!
+//! ```text
+//! * comment
+//! min: +2 x1 -1 x2 x3 ;
+//! +1 x1 +2 ~x2 >= 1 ;
+//! +3 x1 x3 -1 x2 = 2 ;
+//! ```
+//!
+//! Constraints are translated into clauses through binary decision
+//! diagrams after Eén and Sörensson, products through fresh variables.
+//! Constraints whose diagrams would be too large, as for many large and
+//! distinct coefficients, are translated through adders instead.
+//!
+//! ```rust
+//! use cadical_sys::{opb::Opb, CaDiCal, Status};
+//!
+//! let opb: Opb = "min: +1 x1 +1 x2 ;\n+1 x1 +2 x2 >= 2 ;\n".parse().unwrap();
+//! let mut solver = CaDiCal::new();
+//! let objective = opb.add_to(&mut solver).unwrap();
+//! assert_eq!(solver.solve(), Status::SATISFIABLE);
+//! assert!(objective.value(&mut solver) >= 1);
+//! assert_eq!(opb.var("x2").unwrap().index(), 2);
+//! ```
+
+use crate::{
+    dimacs::{Cursor, ParseError},
+    CaDiCal, Error, Lit, Var,
+};
+use std::{
+    cmp::Reverse,
+    collections::{BTreeSet, HashMap},
+    io::BufRead,
+    str::FromStr,
+};
+
+/// A coefficient times the product of literals.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Term {
+    pub coefficient: i64,
+    pub lits: Vec<Lit>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Relation {
+    /// '>='
+    AtLeast,
+    /// '<=', which is not part of the competition format but common.
+    AtMost,
+    /// '='
+    Equal,
+}
+
+/// A linear constraint 'terms relation degree'.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Constraint {
+    pub terms: Vec<Term>,
+    pub relation: Relation,
+    pub degree: i64,
+}
+
+/// An owned problem, whose variables are numbered from '1' in the order of
+/// their first occurrence.  The sum of the absolute values of the
+/// coefficients and the degree of every constraint and of the objective fits
+/// into 'i64', which makes the translation free of overflows.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Opb {
+    pub objective: Option<Vec<Term>>,
+    pub constraints: Vec<Constraint>,
+    pub comments: Vec<String>,
+    names: Vec<String>,
+    vars: HashMap<String, Var>,
+}
+
+/// The objective in terms of solver literals, with products replaced by
+/// their fresh variables.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Objective {
+    pub terms: Vec<(i64, Lit)>,
+}
+
+impl Objective {
+    /// The value of the objective under the current model.
+    ///
+    ///   require (SATISFIED)
+    ///
+    pub fn value(&self, solver: &mut CaDiCal) -> i64 {
+        self.terms
+            .iter()
+            .filter(|(_, lit)| solver.value(*lit))
+            .map(|(coefficient, _)| coefficient)
+            .sum()
+    }
+}
+
+impl Opb {
+    /// Parses a whole file.  Comments start with '*', coefficients are
+    /// mandatory and variable names consist of letters, digits and '_',
+    /// starting with a letter or '_'.
+    ///
+    /// # Errors
+    ///
+    /// '`Error::Parse`' for the first syntax error and '`Error::Io`' if
+    /// reading fails.
+    ///
+    pub fn parse(reader: impl BufRead) -> Result<Self, Error> {
+        let mut reader = Reader {
+            cursor: Cursor::new(reader),
+            opb: Opb::default(),
+        };
+        while let Some(next) = reader.next_token()? {
+            let (line, column) = (reader.cursor.line(), reader.cursor.column());
+            if next == b'*' {
+                let comment = reader.cursor.rest();
+                reader.opb.comments.push(comment);
+            } else if next == b'm' {
+                reader.cursor.keyword("min:")?;
+                if reader.opb.objective.is_some() {
+                    return Err(Error::Parse(ParseError::new(
+                        line,
+                        column,
+                        "second objective",
+                    )));
+                }
+                let terms = reader.terms()?;
+                reader.end("expected term or ';'")?;
+                check_sum(&terms, 0, line, column)?;
+                reader.opb.objective = Some(terms);
+            } else {
+                let terms = reader.terms()?;
+                let relation = reader.relation()?;
+                reader.next_token()?;
+                let degree = reader.integer("degree")?;
+                reader.end("expected ';'")?;
+                check_sum(&terms, degree, line, column)?;
+                reader.opb.constraints.push(Constraint {
+                    terms,
+                    relation,
+                    degree,
+                });
+            }
+        }
+        Ok(reader.opb)
+    }
+
+    /// The number of variables.
+    #[must_use]
+    // bounded while parsing
+    #[allow(clippy::missing_panics_doc)]
+    pub fn vars(&self) -> i32 {
+        i32::try_from(self.names.len()).expect("checked while parsing")
+    }
+
+    /// The variable of 'name', which is also the solver variable after
+    /// '`Opb::add_to`'.
+    #[must_use]
+    pub fn var(&self, name: &str) -> Option<Var> {
+        self.vars.get(name).copied()
+    }
+
+    /// The name of 'var'.
+    #[must_use]
+    pub fn name(&self, var: Var) -> Option<&str> {
+        let index = usize::try_from(var.index()).ok()?;
+        self.names.get(index - 1).map(String::as_str)
+    }
+
+    /// Adds the constraints to 'solver' as clauses and returns the
+    /// objective if there is one.  The variables of the problem keep their
+    /// numbers, see '`Opb::var`', and the fresh variables of products and
+    /// decision diagrams follow the maximum variable of 'solver'.  The size
+    /// of a diagram is bounded by the number of terms times the degree and
+    /// by 2^16 nodes, beyond which a network of adders is used instead, whose
+    /// size is bounded by the number of terms times the bits of the degree.
+    ///
+    ///   require (VALID)
+    ///
+    /// # Panics
+    ///
+    /// If the fresh variables exceed '`i32::MAX`'.
+    ///
+    pub fn add_to(&self, solver: &mut CaDiCal) -> Option<Objective> {
+        solver.reserve(self.vars());
+        let next = solver.vars() + 1;
+        let mut translation = Translation {
+            solver,
+            next,
+            products: HashMap::new(),
+        };
+        for constraint in &self.constraints {
+            let terms = translation.terms(&constraint.terms);
+            if constraint.relation != Relation::AtMost {
+                translation.at_least(terms.clone(), constraint.degree);
+            }
+            if constraint.relation != Relation::AtLeast {
+                let negated = terms.iter().map(|&(a, lit)| (-a, lit)).collect();
+                translation.at_least(negated, -constraint.degree);
+            }
+        }
+        self.objective.as_ref().map(|objective| Objective {
+            terms: translation.terms(objective),
+        })
+    }
+}
+
+/// Parses with the defaults of '`Opb::parse`'.
+impl FromStr for Opb {
+    type Err = Error;
+
+    fn from_str(opb: &str) -> Result<Self, Error> {
+        Opb::parse(opb.as_bytes())
+    }
+}
+
+fn check_sum(terms: &[Term], degree: i64, line: usize, column: usize) -> Result<(), Error> {
+    terms
+        .iter()
+        .try_fold(degree.unsigned_abs(), |sum, term| {
+            sum.checked_add(term.coefficient.unsigned_abs())
+        })
+        .filter(|&sum| sum <= i64::MAX.unsigned_abs())
+        .map(|_| ())
+        .ok_or_else(|| Error::Parse(ParseError::new(line, column, "coefficients too large")))
+}
+
+struct Reader<R> {
+    cursor: Cursor<R>,
+    opb: Opb,
+}
+
+impl<R: BufRead> Reader<R> {
+    /// Skips white space and line ends, 'None' at the end of the file.
+    fn next_token(&mut self) -> Result<Option<u8>, Error> {
+        loop {
+            self.cursor.skip_space();
+            if let Some(next) = self.cursor.peek() {
+                return Ok(Some(next));
+            }
+            if !self.cursor.next_line()? {
+                return Ok(None);
+            }
+        }
+    }
+
+    fn terms(&mut self) -> Result<Vec<Term>, Error> {
+        let mut terms = Vec::new();
+        while let Some(b'+' | b'-' | b'0'..=b'9') = self.next_token()? {
+            let coefficient = self.integer("coefficient")?;
+            let mut lits = Vec::new();
+            while let Some(b'~' | b'a'..=b'z' | b'A'..=b'Z' | b'_') = self.next_token()? {
+                lits.push(self.literal()?);
+            }
+            if lits.is_empty() {
+                return Err(self.cursor.error("expected variable"));
+            }
+            terms.push(Term { coefficient, lits });
+        }
+        Ok(terms)
+    }
+
+    fn integer(&mut self, what: &str) -> Result<i64, Error> {
+        let column = self.cursor.column();
+        let negative = self.cursor.peek() == Some(b'-');
+        if negative {
+            self.cursor.keyword("-")?;
+        } else if self.cursor.peek() == Some(b'+') {
+            self.cursor.keyword("+")?;
+        }
+        let digits = self.cursor.take_while(|c| c.is_ascii_digit());
+        if digits.is_empty() {
+            return Err(self.cursor.error(format!("expected {what}")));
+        }
+        let value = digits
+            .parse::<i64>()
+            .map_err(|_| ParseError::new(self.cursor.line(), column, format!("{what} too large")))
+            .map_err(Error::Parse)?;
+        Ok(if negative { -value } else { value })
+    }
+
+    fn literal(&mut self) -> Result<Lit, Error> {
+        let positive = self.cursor.peek() != Some(b'~');
+        if !positive {
+            self.cursor.keyword("~")?;
+        }
+        let column = self.cursor.column();
+        let name = self
+            .cursor
+            .take_while(|c| c.is_ascii_alphanumeric() || c == b'_');
+        if !name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_') {
+            return Err(self.cursor.error("expected variable"));
+        }
+        let var = if let Some(&var) = self.opb.vars.get(&name) {
+            var
+        } else {
+            let var = i32::try_from(self.opb.names.len() + 1)
+                .ok()
+                .and_then(Var::new)
+                .ok_or_else(|| {
+                    Error::Parse(ParseError::new(
+                        self.cursor.line(),
+                        column,
+                        "too many variables",
+                    ))
+                })?;
+            self.opb.names.push(name.clone());
+            self.opb.vars.insert(name, var);
+            var
+        };
+        Ok(var.lit(positive))
+    }
+
+    fn relation(&mut self) -> Result<Relation, Error> {
+        match self.next_token()? {
+            Some(b'>') => self.cursor.keyword(">=").map(|()| Relation::AtLeast),
+            Some(b'<') => self.cursor.keyword("<=").map(|()| Relation::AtMost),
+            Some(b'=') => self.cursor.keyword("=").map(|()| Relation::Equal),
+            _ => Err(self.cursor.error("expected term or relation")),
+        }
+    }
+
+    /// The terminating ';' of a constraint or objective.
+    fn end(&mut self, expected: &str) -> Result<(), Error> {
+        if self.next_token()? == Some(b';') {
+            self.cursor.keyword(";")
+        } else {
+            Err(self.cursor.error(expected))
+        }
+    }
+}
+
+/// Decision diagrams with more nodes are replaced by adders.
+const MAX_NODES: usize = 1 << 16;
+
+/// A node of a decision diagram.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Node {
+    True,
+    False,
+    Lit(Lit),
+}
+
+struct Translation<'a> {
+    solver: &'a mut CaDiCal,
+    next: i32,
+    products: HashMap<Vec<Lit>, Lit>,
+}
+
+impl Translation<'_> {
+    fn fresh(&mut self) -> Lit {
+        let var = Var::new(self.next).expect("too many variables");
+        self.next += 1;
+        var.positive()
+    }
+
+    fn terms(&mut self, terms: &[Term]) -> Vec<(i64, Lit)> {
+        terms
+            .iter()
+            .map(|term| (term.coefficient, self.product(&term.lits)))
+            .collect()
+    }
+
+    /// A literal equivalent to the conjunction of 'lits'.
+    fn product(&mut self, lits: &[Lit]) -> Lit {
+        let mut key = lits.to_vec();
+        key.sort_unstable_by_key(|lit| lit.to_dimacs());
+        key.dedup();
+        if let [lit] = key[..] {
+            return lit;
+        }
+        if let Some(&product) = self.products.get(&key) {
+            return product;
+        }
+        let product = self.fresh();
+        let mut long = vec![product];
+        for &lit in &key {
+            self.solver.clause6(&[!product, lit]);
+            long.push(!lit);
+        }
+        self.solver.clause6(&long);
+        self.products.insert(key, product);
+        product
+    }
+
+    /// Adds 'sum terms >= degree' after turning negative coefficients into
+    /// positive ones on negated literals.
+    fn at_least(&mut self, terms: Vec<(i64, Lit)>, mut degree: i64) {
+        let mut normal = Vec::with_capacity(terms.len());
+        for (coefficient, lit) in terms {
+            if coefficient > 0 {
+                normal.push((coefficient, lit));
+            } else if coefficient < 0 {
+                normal.push((-coefficient, !lit));
+                degree -= coefficient;
+            }
+        }
+        if degree <= 0 {
+            return;
+        }
+        for term in &mut normal {
+            term.0 = term.0.min(degree);
+        }
+        normal.sort_by_key(|&(coefficient, _)| Reverse(coefficient));
+        let mut suffix = vec![0; normal.len() + 1];
+        for i in (0..normal.len()).rev() {
+            suffix[i] = suffix[i + 1] + normal[i].0;
+        }
+        let terminal = |i: usize, k: i64| {
+            if k <= 0 {
+                Some(Node::True)
+            } else if suffix[i] < k {
+                Some(Node::False)
+            } else {
+                None
+            }
+        };
+
+        // the remaining degrees reachable on each level top-down
+        let mut levels = vec![BTreeSet::new()];
+        if terminal(0, degree).is_none() {
+            levels[0].insert(degree);
+        }
+        let mut size = levels[0].len();
+        for (i, &(coefficient, _)) in normal.iter().enumerate() {
+            let next: BTreeSet<i64> = levels[i]
+                .iter()
+                .flat_map(|&k| [k - coefficient, k])
+                .filter(|&k| terminal(i + 1, k).is_none())
+                .collect();
+            size += next.len();
+            if size > MAX_NODES {
+                return self.adder(&normal, degree);
+            }
+            levels.push(next);
+        }
+
+        // and their nodes bottom-up.  The node of degree 'k' implies the one of
+        // 'k - coefficient', so 'lo' implies 'hi' and the node implies 'hi'
+        // whatever 'lit' is, which is why '[!node, hi]' lacks '!lit'
+        let mut nodes: Vec<HashMap<i64, Node>> = vec![HashMap::new(); normal.len() + 1];
+        for (i, &(coefficient, lit)) in normal.iter().enumerate().rev() {
+            let lookup = |k: i64| terminal(i + 1, k).unwrap_or_else(|| nodes[i + 1][&k]);
+            let mut level = HashMap::with_capacity(levels[i].len());
+            for &k in &levels[i] {
+                let hi = lookup(k - coefficient);
+                let lo = lookup(k);
+                if hi == lo {
+                    level.insert(k, hi);
+                    continue;
+                }
+                let node = self.fresh();
+                match hi {
+                    Node::True => {}
+                    Node::False => self.solver.clause6(&[!node]),
+                    Node::Lit(hi) => self.solver.clause6(&[!node, hi]),
+                }
+                match lo {
+                    Node::True => {}
+                    Node::False => self.solver.clause6(&[!node, lit]),
+                    Node::Lit(lo) => self.solver.clause6(&[!node, lit, lo]),
+                }
+                level.insert(k, Node::Lit(node));
+            }
+            nodes[i] = level;
+        }
+        match terminal(0, degree).unwrap_or_else(|| nodes[0][&degree]) {
+            Node::True => {}
+            Node::False => self.solver.clause6::<Lit>(&[]),
+            Node::Lit(root) => self.solver.clause6(&[root]),
+        }
+    }
+
+    /// Adds 'sum terms >= degree' for positive coefficients by summing the
+    /// bits of the coefficients with full and half adders and comparing the
+    /// resulting bits with those of 'degree'.
+    fn adder(&mut self, terms: &[(i64, Lit)], degree: i64) {
+        let mut buckets: Vec<Vec<Lit>> = Vec::new();
+        for &(coefficient, lit) in terms {
+            for bit in 0..64 - coefficient.leading_zeros() as usize {
+                if coefficient >> bit & 1 == 1 {
+                    if buckets.len() <= bit {
+                        buckets.resize(bit + 1, Vec::new());
+                    }
+                    buckets[bit].push(lit);
+                }
+            }
+        }
+        // 'None' for bits which are always false
+        let mut sum: Vec<Option<Lit>> = Vec::with_capacity(buckets.len());
+        let mut bit = 0;
+        while bit < buckets.len() {
+            while buckets[bit].len() > 1 {
+                let bucket = &mut buckets[bit];
+                let inputs = bucket.split_off(bucket.len() - bucket.len().min(3));
+                let carry = self.gate(&inputs, |count| count >= 2);
+                let parity = self.gate(&inputs, |count| count % 2 == 1);
+                buckets[bit].push(parity);
+                if buckets.len() <= bit + 1 {
+                    buckets.push(Vec::new());
+                }
+                buckets[bit + 1].push(carry);
+            }
+            sum.push(buckets[bit].pop());
+            bit += 1;
+        }
+
+        // 'sum < degree' iff at some bit of 'degree' the sum has a zero and
+        // agrees with 'degree' on all higher bits
+        let set = |bit: usize| degree >> bit & 1 == 1;
+        for (i, &lit) in sum.iter().enumerate().filter(|&(i, _)| set(i)) {
+            let mut clause: Vec<Lit> = lit.into_iter().collect();
+            let mut satisfied = false;
+            for (j, &higher) in sum.iter().enumerate().skip(i + 1) {
+                match (higher, set(j)) {
+                    (Some(higher), true) => clause.push(!higher),
+                    (Some(higher), false) => clause.push(higher),
+                    (None, true) => satisfied = true,
+                    (None, false) => {}
+                }
+            }
+            if !satisfied {
+                self.solver.clause6(&clause);
+            }
+        }
+    }
+
+    /// A literal equivalent to 'output' of the number of true 'inputs'.
+    fn gate(&mut self, inputs: &[Lit], output: impl Fn(u32) -> bool) -> Lit {
+        let gate = self.fresh();
+        for assignment in 0..1u32 << inputs.len() {
+            // excludes the assignment with the wrong output
+            let mut clause: Vec<Lit> = inputs
+                .iter()
+                .enumerate()
+                .map(|(i, &lit)| if assignment >> i & 1 == 1 { !lit } else { lit })
+                .collect();
+            clause.push(if output(assignment.count_ones()) {
+                gate
+            } else {
+                !gate
+            });
+            self.solver.clause6(&clause);
+        }
+        gate
+    }
+}
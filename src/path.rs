@@ -0,0 +1,24 @@
+//! Paths handed to `CaDiCaL`, which opens files through C strings.
+
+use crate::Error;
+use std::{io, path::Path};
+
+/// The bytes of 'path' as passed to 'fopen'.  Non-UTF-8 paths are passed on
+/// unchanged where the platform has byte paths.
+pub(crate) fn bytes(path: &Path) -> Result<&[u8], Error> {
+    #[cfg(unix)]
+    let bytes = std::os::unix::ffi::OsStrExt::as_bytes(path.as_os_str());
+    #[cfg(not(unix))]
+    let bytes = path
+        .to_str()
+        .ok_or_else(|| invalid(path, "path is not valid UTF-8"))?
+        .as_bytes();
+    if bytes.contains(&0) {
+        return Err(invalid(path, "path contains a nul byte"));
+    }
+    Ok(bytes)
+}
+
+fn invalid(path: &Path, message: &str) -> Error {
+    Error::open(path, io::Error::new(io::ErrorKind::InvalidInput, message))
+}
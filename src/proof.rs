@@ -0,0 +1,721 @@
+//! Proof tracers writing DRAT, LRAT and FRAT proofs to any
+//! '`std::io::Write`', in ASCII or in the binary encoding where literals and
+//! clause ids are variable length numbers behind a one byte tag.  Unlike
+//! '`CaDiCal::trace_proof1`' these do not need a path, so proofs can be
+//! streamed into memory, pipes or compressors.
+//!
+//! Writing stops at the first error, which is returned by 'finish' together
+//! with the writer.
+//!
+//! '`FileTracer`' writes one of these proofs to a file and counts its steps
+//! and '`StatTracer`' gives access to the stat tracers of `CaDiCaL` itself.
+//!
+//! '`LratChecker`' verifies the proof of the solver while it runs, without
+//! writing it anywhere, and '`drat::check`' verifies DRAT proofs afterwards.
+//!
+//! ```rust
+//! use cadical_sys::{proof::LratWriter, CaDiCal, Status};
+//!
+//! let mut solver = CaDiCal::new();
+//! let mut lrat = LratWriter::new(Vec::new(), false);
+//! let status = solver.with_proof_tracer(&mut lrat, true, false, |solver| {
+//!     solver.clause2(1, 2);
+//!     solver.clause2(-1, 2);
+//!     solver.clause2(1, -2);
+//!     solver.clause2(-1, -2);
+//!     solver.solve()
+//! });
+//! assert_eq!(status, Status::UNSATISFIABLE);
+//! let proof = String::from_utf8(lrat.finish().unwrap()).unwrap();
+//! assert!(proof.lines().any(|line| line.split(' ').nth(1) == Some("0")));
+//! ```
+
+use crate::{
+    bridge::ffi,
+    compress::{self, ProofStream},
+    Compression, Error, Lit, ProofTracer,
+};
+use cxx::UniquePtr;
+use std::{
+    fmt,
+    io::{self, Write},
+    path::Path,
+};
+
+pub mod drat;
+mod lrat;
+
+pub use lrat::LratChecker;
+
+/// A proof step which could not be verified, identified by the id of the
+/// clause it derives or deletes.  Lemmas of DRAT proofs have no ids, so
+/// '`drat::check`' numbers them after the clauses of the formula, that is
+/// the lemma of step 'k' has the id 'n + k' for 'n' original clauses.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProofError {
+    id: u64,
+    message: String,
+}
+
+impl ProofError {
+    pub(crate) fn new(id: u64, message: impl Into<String>) -> Self {
+        Self {
+            id,
+            message: message.into(),
+        }
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for ProofError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "clause {}: {}", self.id, self.message)
+    }
+}
+
+impl std::error::Error for ProofError {}
+
+/// Encodes one proof line at a time and writes it as a whole.
+struct Encoder<W> {
+    writer: W,
+    binary: bool,
+    line: Vec<u8>,
+    error: Option<io::Error>,
+}
+
+impl<W: Write> Encoder<W> {
+    fn new(writer: W, binary: bool) -> Self {
+        Self {
+            writer,
+            binary,
+            line: Vec::new(),
+            error: None,
+        }
+    }
+
+    fn separate(&mut self) {
+        if !self.line.is_empty() {
+            self.line.push(b' ');
+        }
+    }
+
+    /// A tag which is written in both encodings.
+    fn tag(&mut self, tag: u8) {
+        if self.binary {
+            self.line.push(tag);
+        } else {
+            self.separate();
+            self.line.push(tag);
+        }
+    }
+
+    /// A tag which only the binary encoding has, like 'a' for additions.
+    fn binary_tag(&mut self, tag: u8) {
+        if self.binary {
+            self.line.push(tag);
+        }
+    }
+
+    fn number(&mut self, ascii: &impl ToString, mut binary: u64) {
+        if self.binary {
+            loop {
+                let byte = (binary & 0x7f) as u8;
+                binary >>= 7;
+                if binary == 0 {
+                    self.line.push(byte);
+                    break;
+                }
+                self.line.push(byte | 0x80);
+            }
+        } else {
+            self.separate();
+            self.line.extend_from_slice(ascii.to_string().as_bytes());
+        }
+    }
+
+    fn lits(&mut self, lits: &[Lit]) {
+        for lit in lits {
+            let var = u64::from(lit.var().index().unsigned_abs());
+            self.number(lit, 2 * var + u64::from(!lit.is_positive()));
+        }
+    }
+
+    fn id(&mut self, id: u64) {
+        self.number(&id, 2 * id);
+    }
+
+    fn ids(&mut self, ids: &[u64]) {
+        for &id in ids {
+            self.id(id);
+        }
+    }
+
+    /// Hints of RAT steps, where negative ids are written with the sign
+    /// bit set in the binary encoding.
+    fn hints(&mut self, hints: &[i64]) {
+        for &hint in hints {
+            self.number(&hint, 2 * hint.unsigned_abs() + u64::from(hint < 0));
+        }
+    }
+
+    /// Terminates a list of literals or ids and, with 'end', the line.
+    fn zero(&mut self, end: bool) {
+        self.number(&0, 0);
+        if end {
+            if !self.binary {
+                self.line.push(b'\n');
+            }
+            if self.error.is_none() {
+                self.error = self.writer.write_all(&self.line).err();
+            }
+            self.line.clear();
+        }
+    }
+
+    fn finish(mut self) -> Result<W, Error> {
+        if let Some(error) = self.error.take() {
+            return Err(error.into());
+        }
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+/// Writes derived and deleted clauses in DRAT, ignoring antecedents.
+pub struct DratWriter<W> {
+    encoder: Encoder<W>,
+}
+
+impl<W: Write> DratWriter<W> {
+    /// Writes to 'writer' in the binary encoding if 'binary' is set.
+    pub fn new(writer: W, binary: bool) -> Self {
+        Self {
+            encoder: Encoder::new(writer, binary),
+        }
+    }
+
+    /// Flushes and returns the writer.
+    ///
+    /// # Errors
+    ///
+    /// '`Error::Io`' with the first error of writing or flushing.
+    ///
+    pub fn finish(self) -> Result<W, Error> {
+        self.encoder.finish()
+    }
+
+    fn add(&mut self, clause: &[Lit]) {
+        self.encoder.binary_tag(b'a');
+        self.encoder.lits(clause);
+        self.encoder.zero(true);
+    }
+
+    fn delete(&mut self, clause: &[Lit]) {
+        self.encoder.tag(b'd');
+        self.encoder.lits(clause);
+        self.encoder.zero(true);
+    }
+}
+
+impl<W: Write + Send> ProofTracer for DratWriter<W> {
+    fn add_original_clause(
+        &mut self,
+        _id: u64,
+        _redundant: bool,
+        _clause: &[Lit],
+        _restored: bool,
+    ) {
+    }
+
+    fn add_derived_clause(
+        &mut self,
+        _id: u64,
+        _redundant: bool,
+        clause: &[Lit],
+        _antecedents: &[u64],
+    ) {
+        self.add(clause);
+    }
+
+    fn delete_clause(&mut self, _id: u64, _redundant: bool, clause: &[Lit]) {
+        self.delete(clause);
+    }
+
+    fn weaken_minus(&mut self, _id: u64, _clause: &[Lit]) {}
+
+    fn strengthen(&mut self, _id: u64) {}
+
+    fn finalize_clause(&mut self, _id: u64, _clause: &[Lit]) {}
+
+    fn add_assumption(&mut self, _lit: Lit) {}
+
+    fn add_constraint(&mut self, _clause: &[Lit]) {}
+
+    fn reset_assumptions(&mut self) {}
+
+    fn add_assumption_clause(&mut self, _id: u64, clause: &[Lit], _antecedents: &[u64]) {
+        self.add(clause);
+    }
+
+    fn conclude_sat(&mut self, _conclusion_type: i32, _model: &[Lit]) {}
+
+    fn conclude_unsat(&mut self, _conclusion_type: i32, _clause_ids: &[u64]) {}
+
+    fn conclude_unknown(&mut self, _trail: &[Lit]) {}
+}
+
+/// Writes derived clauses with their antecedents in LRAT, so the tracer has
+/// to be connected with 'antecedents' set.  Deletions are collected and
+/// written in one line before the next derived clause, as `CaDiCaL` does.
+/// Original clauses are numbered by the ids of the solver, which match the
+/// order of a DIMACS file if its clauses are added in order.
+pub struct LratWriter<W> {
+    encoder: Encoder<W>,
+    latest: u64,
+    deleted: Vec<u64>,
+}
+
+impl<W: Write> LratWriter<W> {
+    /// Writes to 'writer' in the binary encoding if 'binary' is set.
+    pub fn new(writer: W, binary: bool) -> Self {
+        Self {
+            encoder: Encoder::new(writer, binary),
+            latest: 0,
+            deleted: Vec::new(),
+        }
+    }
+
+    /// Writes pending deletions, flushes and returns the writer.
+    ///
+    /// # Errors
+    ///
+    /// '`Error::Io`' with the first error of writing or flushing.
+    ///
+    pub fn finish(mut self) -> Result<W, Error> {
+        self.delete();
+        self.encoder.finish()
+    }
+
+    fn delete(&mut self) {
+        if self.deleted.is_empty() {
+            return;
+        }
+        if !self.encoder.binary {
+            self.encoder.id(self.latest);
+        }
+        self.encoder.tag(b'd');
+        self.encoder.ids(&self.deleted);
+        self.encoder.zero(true);
+        self.deleted.clear();
+    }
+
+    /// Writes pending deletions and the clause of an addition.
+    fn start(&mut self, id: u64, clause: &[Lit]) {
+        self.delete();
+        self.latest = self.latest.max(id);
+        self.encoder.binary_tag(b'a');
+        self.encoder.id(id);
+        self.encoder.lits(clause);
+        self.encoder.zero(false);
+    }
+
+    fn add(&mut self, id: u64, clause: &[Lit], antecedents: &[u64]) {
+        self.start(id, clause);
+        self.encoder.ids(antecedents);
+        self.encoder.zero(true);
+    }
+
+    /// An addition with the hints of a RAT step, see '`Encoder::hints`'.
+    fn add_hints(&mut self, id: u64, clause: &[Lit], hints: &[i64]) {
+        self.start(id, clause);
+        self.encoder.hints(hints);
+        self.encoder.zero(true);
+    }
+}
+
+impl<W: Write + Send> ProofTracer for LratWriter<W> {
+    fn add_original_clause(&mut self, id: u64, _redundant: bool, _clause: &[Lit], _restored: bool) {
+        self.latest = self.latest.max(id);
+    }
+
+    fn add_derived_clause(
+        &mut self,
+        id: u64,
+        _redundant: bool,
+        clause: &[Lit],
+        antecedents: &[u64],
+    ) {
+        self.add(id, clause, antecedents);
+    }
+
+    fn delete_clause(&mut self, id: u64, _redundant: bool, _clause: &[Lit]) {
+        self.deleted.push(id);
+    }
+
+    fn weaken_minus(&mut self, _id: u64, _clause: &[Lit]) {}
+
+    fn strengthen(&mut self, _id: u64) {}
+
+    fn finalize_clause(&mut self, _id: u64, _clause: &[Lit]) {}
+
+    fn add_assumption(&mut self, _lit: Lit) {}
+
+    fn add_constraint(&mut self, _clause: &[Lit]) {}
+
+    fn reset_assumptions(&mut self) {}
+
+    fn add_assumption_clause(&mut self, id: u64, clause: &[Lit], antecedents: &[u64]) {
+        self.add(id, clause, antecedents);
+    }
+
+    fn conclude_sat(&mut self, _conclusion_type: i32, _model: &[Lit]) {}
+
+    fn conclude_unsat(&mut self, _conclusion_type: i32, _clause_ids: &[u64]) {}
+
+    fn conclude_unknown(&mut self, _trail: &[Lit]) {}
+}
+
+/// Writes original, derived, deleted and finalized clauses in FRAT, with
+/// the antecedents of derived clauses as 'l' hints if there are any.  The
+/// solver only finalizes clauses for tracers connected with 'finalize' set,
+/// see '`CaDiCal::with_proof_tracer`'.
+pub struct FratWriter<W> {
+    encoder: Encoder<W>,
+}
+
+impl<W: Write> FratWriter<W> {
+    /// Writes to 'writer' in the binary encoding if 'binary' is set.
+    pub fn new(writer: W, binary: bool) -> Self {
+        Self {
+            encoder: Encoder::new(writer, binary),
+        }
+    }
+
+    /// Flushes and returns the writer.
+    ///
+    /// # Errors
+    ///
+    /// '`Error::Io`' with the first error of writing or flushing.
+    ///
+    pub fn finish(self) -> Result<W, Error> {
+        self.encoder.finish()
+    }
+
+    fn step(&mut self, tag: u8, id: u64, clause: &[Lit]) {
+        self.encoder.tag(tag);
+        self.encoder.id(id);
+        self.encoder.lits(clause);
+        self.encoder.zero(true);
+    }
+
+    fn add(&mut self, id: u64, clause: &[Lit], antecedents: &[u64]) {
+        if antecedents.is_empty() {
+            self.step(b'a', id, clause);
+            return;
+        }
+        self.encoder.tag(b'a');
+        self.encoder.id(id);
+        self.encoder.lits(clause);
+        self.encoder.zero(false);
+        self.encoder.tag(b'l');
+        self.encoder.ids(antecedents);
+        self.encoder.zero(true);
+    }
+}
+
+impl<W: Write + Send> ProofTracer for FratWriter<W> {
+    fn add_original_clause(&mut self, id: u64, _redundant: bool, clause: &[Lit], _restored: bool) {
+        self.step(b'o', id, clause);
+    }
+
+    fn add_derived_clause(
+        &mut self,
+        id: u64,
+        _redundant: bool,
+        clause: &[Lit],
+        antecedents: &[u64],
+    ) {
+        self.add(id, clause, antecedents);
+    }
+
+    fn delete_clause(&mut self, id: u64, _redundant: bool, clause: &[Lit]) {
+        self.step(b'd', id, clause);
+    }
+
+    fn weaken_minus(&mut self, _id: u64, _clause: &[Lit]) {}
+
+    fn strengthen(&mut self, _id: u64) {}
+
+    fn finalize_clause(&mut self, id: u64, clause: &[Lit]) {
+        self.step(b'f', id, clause);
+    }
+
+    fn add_assumption(&mut self, _lit: Lit) {}
+
+    fn add_constraint(&mut self, _clause: &[Lit]) {}
+
+    fn reset_assumptions(&mut self) {}
+
+    fn add_assumption_clause(&mut self, id: u64, clause: &[Lit], antecedents: &[u64]) {
+        self.add(id, clause, antecedents);
+    }
+
+    fn conclude_sat(&mut self, _conclusion_type: i32, _model: &[Lit]) {}
+
+    fn conclude_unsat(&mut self, _conclusion_type: i32, _clause_ids: &[u64]) {}
+
+    fn conclude_unknown(&mut self, _trail: &[Lit]) {}
+}
+
+/// The proof formats of `CaDiCaL`, selected by the options 'lrat', 'frat',
+/// 'veripb', 'idrup' and 'lidrup' for '`CaDiCal::trace_proof2`'.  A
+/// '`FileTracer`' writes the first three.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ProofFormat {
+    #[default]
+    Drat,
+    Lrat,
+    Frat,
+    Veripb,
+    Idrup,
+    Lidrup,
+}
+
+impl ProofFormat {
+    /// Whether the tracer has to be connected with antecedents.
+    pub(crate) fn antecedents(self) -> bool {
+        !matches!(self, ProofFormat::Drat | ProofFormat::Idrup)
+    }
+}
+
+/// The writer of a '`FileTracer`' for its format.
+enum FileWriter {
+    Drat(DratWriter<ProofStream>),
+    Lrat(LratWriter<ProofStream>),
+    Frat(FratWriter<ProofStream>),
+}
+
+/// Writes a proof file in DRAT, LRAT or FRAT with the writers of this
+/// module and counts its steps, connected with
+/// '`CaDiCal::connect_file_tracer`'.  The file is compressed after its
+/// extension as by '`Compression::from_path`' and complete once the tracer
+/// is finished or dropped.
+pub struct FileTracer {
+    // 'None' once finished, boxed as encoders keep their state inline
+    writer: Option<Box<FileWriter>>,
+    statistics: ProofStatistics,
+    pub(crate) format: ProofFormat,
+}
+
+impl FileTracer {
+    /// Creates 'path' for a proof in 'format', binary if 'binary' is set.
+    ///
+    /// # Errors
+    ///
+    /// '`Error::UnsupportedFormat`' for formats other than DRAT, LRAT and
+    /// FRAT, which only '`CaDiCal::trace_proof2`' writes, and '`Error::Open`'
+    /// if 'path' cannot be opened for writing, and '`Error::Io`' if its
+    /// compression cannot be set up.
+    ///
+    pub fn new(path: impl AsRef<Path>, format: ProofFormat, binary: bool) -> Result<Self, Error> {
+        if !matches!(
+            format,
+            ProofFormat::Drat | ProofFormat::Lrat | ProofFormat::Frat
+        ) {
+            return Err(Error::UnsupportedFormat(format));
+        }
+        let path = path.as_ref();
+        let file = compress::create(path, Compression::from_path(path))?;
+        let writer = match format {
+            ProofFormat::Drat => FileWriter::Drat(DratWriter::new(file, binary)),
+            ProofFormat::Lrat => FileWriter::Lrat(LratWriter::new(file, binary)),
+            _ => FileWriter::Frat(FratWriter::new(file, binary)),
+        };
+        Ok(FileTracer {
+            writer: Some(Box::new(writer)),
+            statistics: ProofStatistics::default(),
+            format,
+        })
+    }
+
+    #[must_use]
+    pub fn format(&self) -> ProofFormat {
+        self.format
+    }
+
+    /// The counts of the proof steps given to the tracer so far.
+    #[must_use]
+    pub fn statistics(&self) -> ProofStatistics {
+        self.statistics
+    }
+
+    /// Completes the file, which dropping the tracer does as well but
+    /// without reporting errors.
+    ///
+    /// # Errors
+    ///
+    /// '`Error::Io`' with the first error of writing or compressing the file.
+    ///
+    pub fn finish(mut self) -> Result<(), Error> {
+        self.complete()
+    }
+
+    fn complete(&mut self) -> Result<(), Error> {
+        let Some(writer) = self.writer.take() else {
+            return Ok(());
+        };
+        let stream = match *writer {
+            FileWriter::Drat(writer) => writer.finish()?,
+            FileWriter::Lrat(writer) => writer.finish()?,
+            FileWriter::Frat(writer) => writer.finish()?,
+        };
+        stream.finish()?;
+        Ok(())
+    }
+
+    fn writer(&mut self) -> &mut dyn ProofTracer {
+        match self
+            .writer
+            .as_deref_mut()
+            .expect("tracers are finished when disconnected")
+        {
+            FileWriter::Drat(writer) => writer,
+            FileWriter::Lrat(writer) => writer,
+            FileWriter::Frat(writer) => writer,
+        }
+    }
+}
+
+impl Drop for FileTracer {
+    fn drop(&mut self) {
+        // errors are only reported by 'finish'
+        let _ = self.complete();
+    }
+}
+
+impl ProofTracer for FileTracer {
+    fn add_original_clause(&mut self, id: u64, redundant: bool, clause: &[Lit], restored: bool) {
+        self.statistics.original += 1;
+        self.writer()
+            .add_original_clause(id, redundant, clause, restored);
+    }
+
+    fn add_derived_clause(
+        &mut self,
+        id: u64,
+        redundant: bool,
+        clause: &[Lit],
+        antecedents: &[u64],
+    ) {
+        self.statistics.derived += 1;
+        self.writer()
+            .add_derived_clause(id, redundant, clause, antecedents);
+    }
+
+    fn delete_clause(&mut self, id: u64, redundant: bool, clause: &[Lit]) {
+        self.statistics.deleted += 1;
+        self.writer().delete_clause(id, redundant, clause);
+    }
+
+    fn weaken_minus(&mut self, id: u64, clause: &[Lit]) {
+        self.statistics.weakened += 1;
+        self.writer().weaken_minus(id, clause);
+    }
+
+    fn strengthen(&mut self, id: u64) {
+        self.statistics.strengthened += 1;
+        self.writer().strengthen(id);
+    }
+
+    fn finalize_clause(&mut self, id: u64, clause: &[Lit]) {
+        self.statistics.finalized += 1;
+        self.writer().finalize_clause(id, clause);
+    }
+
+    fn add_assumption(&mut self, lit: Lit) {
+        self.writer().add_assumption(lit);
+    }
+
+    fn add_constraint(&mut self, clause: &[Lit]) {
+        self.writer().add_constraint(clause);
+    }
+
+    fn reset_assumptions(&mut self) {
+        self.writer().reset_assumptions();
+    }
+
+    fn add_assumption_clause(&mut self, id: u64, clause: &[Lit], antecedents: &[u64]) {
+        self.statistics.derived += 1;
+        self.writer().add_assumption_clause(id, clause, antecedents);
+    }
+
+    fn conclude_sat(&mut self, conclusion_type: i32, model: &[Lit]) {
+        self.writer().conclude_sat(conclusion_type, model);
+    }
+
+    fn conclude_unsat(&mut self, conclusion_type: i32, clause_ids: &[u64]) {
+        self.writer().conclude_unsat(conclusion_type, clause_ids);
+    }
+
+    fn conclude_unknown(&mut self, trail: &[Lit]) {
+        self.writer().conclude_unknown(trail);
+    }
+}
+
+/// Counts of the proof steps seen by a '`StatTracer`'.  Failed assumption
+/// clauses are counted as derived.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct ProofStatistics {
+    pub original: u64,
+    pub derived: u64,
+    pub deleted: u64,
+    pub weakened: u64,
+    pub strengthened: u64,
+    pub finalized: u64,
+}
+
+/// A stat tracer of `CaDiCaL` counting proof steps, connected with
+/// '`CaDiCal::connect_stat_tracer`'.
+pub struct StatTracer {
+    pub(crate) tracer: UniquePtr<ffi::StatTracer>,
+}
+
+impl StatTracer {
+    #[must_use]
+    pub fn new() -> Self {
+        StatTracer {
+            tracer: ffi::new_stat_tracer(),
+        }
+    }
+
+    #[must_use]
+    pub fn statistics(&self) -> ProofStatistics {
+        let [original, derived, deleted, weakened, strengthened, finalized] =
+            ffi::stat_tracer_statistics(&self.tracer);
+        ProofStatistics {
+            original,
+            derived,
+            deleted,
+            weakened,
+            strengthened,
+            finalized,
+        }
+    }
+}
+
+impl Default for StatTracer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
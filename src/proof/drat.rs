@@ -0,0 +1,828 @@
+//! DRAT proofs, as written by '`CaDiCal::trace_proof2`' or '`DratWriter`',
+//! and a checker for them which can also trim the proof, extract an
+//! unsatisfiable core and convert the proof to LRAT.
+//!
+//! Each step of a proof either adds a lemma, which has to be implied by the
+//! formula and the lemmas before, or deletes a clause.  In ASCII a step is a
+//! line of literals terminated by '0', deletions start with 'd' and lines
+//! starting with 'c' are comments.  In the binary encoding additions start
+//! with 'a' and deletions with 'd', followed by the literals as variable
+//! length numbers '2 * var + sign' up to a '0' byte.
+//!
+//! '`check`' works backward as 'drat-trim' does: lemmas are added until unit
+//! propagation finds a conflict, then going back only the lemmas needed for
+//! that conflict, the core, are verified by reverse unit propagation (RUP)
+//! or as resolution asymmetric tautologies (RAT) on their first literal.
+//! Deleting a clause which is the reason of a literal fixed by unit
+//! propagation is ignored, since solvers delete satisfied clauses without
+//! deleting these units first, and so is deleting a clause which is not
+//! there.
+//!
+//! ```rust
+//! use cadical_sys::{
+//!     dimacs::Cnf,
+//!     proof::drat::{self, Proof},
+//! };
+//!
+//! let formula: Cnf = "p cnf 2 4\n1 2 0\n1 -2 0\n-1 2 0\n-1 -2 0\n".parse().unwrap();
+//! let proof: Proof = "1 0\n0\n".parse().unwrap();
+//! let refutation = drat::check(&formula, &proof).unwrap();
+//! assert_eq!(refutation.core().clauses.len(), 4);
+//! let mut lrat = Vec::new();
+//! refutation.write_lrat(&mut lrat, false).unwrap();
+//! assert_eq!(lrat, b"5 1 0 1 2 0\n6 0 5 3 4 0\n");
+//! ```
+
+use super::{DratWriter, LratWriter, ProofError};
+use crate::{compress, dimacs::Cnf, dimacs::Cursor, dimacs::ParseError, Error, Lit, Var};
+use std::{
+    collections::HashMap,
+    fmt,
+    io::{BufRead, Write},
+    mem,
+    path::Path,
+    str::FromStr,
+};
+
+/// A step of a DRAT proof.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Step {
+    Add(Vec<Lit>),
+    Delete(Vec<Lit>),
+}
+
+/// The steps of a DRAT proof in order.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Proof {
+    pub steps: Vec<Step>,
+}
+
+impl Proof {
+    /// Parses a proof in either encoding.  Binary proofs are recognized by
+    /// the '0' bytes terminating their steps, which never occur in ASCII.
+    /// Since they have no lines, their errors are reported in line 1 with
+    /// the byte offset plus one as column.
+    ///
+    /// # Errors
+    ///
+    /// '`Error::Parse`' for the first syntax error and '`Error::Io`' if
+    /// reading fails.
+    ///
+    pub fn parse(mut reader: impl BufRead) -> Result<Self, Error> {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+        if data.contains(&0) {
+            Self::parse_binary(&data)
+        } else {
+            Self::parse_ascii(data.as_slice())
+        }
+    }
+
+    fn parse_ascii(reader: impl BufRead) -> Result<Self, Error> {
+        let mut cursor = Cursor::new(reader);
+        let mut proof = Proof::default();
+        while cursor.next_line()? {
+            cursor.skip_space();
+            let delete = match cursor.peek() {
+                None | Some(b'c') => continue,
+                Some(b'd') => {
+                    cursor.keyword("d")?;
+                    true
+                }
+                Some(b'-' | b'0'..=b'9') => false,
+                Some(_) => return Err(cursor.error("expected literal, 'd' or comment")),
+            };
+            let lits = Self::lits(&mut cursor)?;
+            cursor.skip_space();
+            if !cursor.at_end() {
+                return Err(cursor.error("unexpected character after '0'"));
+            }
+            proof.steps.push(if delete {
+                Step::Delete(lits)
+            } else {
+                Step::Add(lits)
+            });
+        }
+        Ok(proof)
+    }
+
+    /// Literals up to the terminating '0' within the current line.
+    fn lits<R: BufRead>(cursor: &mut Cursor<R>) -> Result<Vec<Lit>, Error> {
+        let mut lits = Vec::new();
+        loop {
+            cursor.skip_space();
+            if cursor.at_end() {
+                return Err(cursor.error("missing terminating '0'"));
+            }
+            match Lit::from_dimacs(cursor.integer()?.0) {
+                Some(lit) => lits.push(lit),
+                None => return Ok(lits),
+            }
+        }
+    }
+
+    fn parse_binary(data: &[u8]) -> Result<Self, Error> {
+        let error = |pos: usize, message: &str| Error::Parse(ParseError::new(1, pos + 1, message));
+        let mut proof = Proof::default();
+        let mut pos = 0;
+        while let Some(&tag) = data.get(pos) {
+            let delete = match tag {
+                b'a' => false,
+                b'd' => true,
+                _ => return Err(error(pos, "expected 'a' or 'd'")),
+            };
+            pos += 1;
+            let mut lits = Vec::new();
+            loop {
+                let start = pos;
+                let mut value: u64 = 0;
+                let mut shift = 0;
+                loop {
+                    let Some(&byte) = data.get(pos) else {
+                        return Err(error(pos, "missing terminating '0'"));
+                    };
+                    pos += 1;
+                    value |= u64::from(byte & 0x7f) << shift;
+                    if byte & 0x80 == 0 {
+                        break;
+                    }
+                    shift += 7;
+                    if shift > 28 {
+                        return Err(error(start, "literal too large"));
+                    }
+                }
+                if value == 0 {
+                    break;
+                }
+                let var = i32::try_from(value >> 1).ok().and_then(Var::new);
+                let var = var.ok_or_else(|| error(start, "invalid literal"))?;
+                lits.push(var.lit(value & 1 == 0));
+            }
+            proof.steps.push(if delete {
+                Step::Delete(lits)
+            } else {
+                Step::Add(lits)
+            });
+        }
+        Ok(proof)
+    }
+
+    /// Reads the file at 'path', decompressed as in
+    /// '`CaDiCal::read_dimacs2`'.
+    ///
+    /// # Errors
+    ///
+    /// '`Error::Open`' if the file cannot be opened and otherwise the
+    /// errors of '`Proof::parse`'.
+    ///
+    pub fn read(path: impl AsRef<Path>) -> Result<Self, Error> {
+        Self::parse(compress::reader(path.as_ref())?)
+    }
+
+    /// Writes the steps as '`DratWriter`' does.
+    ///
+    /// # Errors
+    ///
+    /// '`Error::Io`' if writing fails.
+    ///
+    pub fn write(&self, writer: impl Write, binary: bool) -> Result<(), Error> {
+        let mut drat = DratWriter::new(writer, binary);
+        for step in &self.steps {
+            match step {
+                Step::Add(clause) => drat.add(clause),
+                Step::Delete(clause) => drat.delete(clause),
+            }
+        }
+        drat.finish()?;
+        Ok(())
+    }
+}
+
+/// Parses either encoding, see '`Proof::parse`'.
+impl FromStr for Proof {
+    type Err = Error;
+
+    fn from_str(proof: &str) -> Result<Self, Error> {
+        Proof::parse(proof.as_bytes())
+    }
+}
+
+/// The ASCII encoding of the proof.
+impl fmt::Display for Proof {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for step in &self.steps {
+            let clause = match step {
+                Step::Add(clause) => clause,
+                Step::Delete(clause) => {
+                    write!(f, "d ")?;
+                    clause
+                }
+            };
+            for lit in clause {
+                write!(f, "{lit} ")?;
+            }
+            writeln!(f, "0")?;
+        }
+        Ok(())
+    }
+}
+
+/// Checks that 'proof' refutes 'formula', see the module documentation.
+///
+/// # Errors
+///
+/// '`Error::Proof`' with the id of the first lemma, going backward, which
+/// is neither RUP nor RAT, or with id 0 if unit propagation finds no
+/// conflict after the last step.
+///
+pub fn check(formula: &Cnf, proof: &Proof) -> Result<Refutation, Error> {
+    let mut checker = Checker::new(formula, proof);
+    let (records, conflict, step) = checker.forward(formula, proof)?;
+    let hints = checker.analyze(Seed::Conflict(conflict));
+    let mut lines = Vec::new();
+    for record in records.into_iter().rev() {
+        match record {
+            Record::Add { clause, trail } => {
+                checker.deactivate(clause);
+                checker.backtrack(trail);
+                if checker.clauses[clause].core {
+                    let hints = checker.justify(clause).map_err(Error::Proof)?;
+                    lines.push(Line::Add { clause, hints });
+                }
+            }
+            Record::Delete(clause) => {
+                checker.watch(clause);
+                lines.push(Line::Delete(clause));
+            }
+        }
+    }
+    lines.reverse();
+    // RAT lemmas depend on every deletion of an original clause
+    let originals = formula.clauses.len() as u64;
+    lines.retain(|line| match line {
+        Line::Add { .. } => true,
+        Line::Delete(clause) => {
+            let clause = &checker.clauses[*clause];
+            clause.core || clause.id <= originals
+        }
+    });
+
+    let mut refutation = Refutation {
+        vars: formula.vars,
+        core: Vec::new(),
+        steps: Vec::new(),
+        latest: formula.clauses.len() as u64,
+    };
+    for (clause, core) in formula.clauses.iter().zip(&checker.clauses) {
+        if core.core {
+            refutation.core.push(clause.clone());
+        }
+    }
+    for line in lines {
+        refutation.steps.push(match line {
+            Line::Add { clause, hints } => Lemma {
+                id: checker.clauses[clause].id,
+                clause: checker.ordered(clause),
+                hints,
+                delete: false,
+            },
+            Line::Delete(clause) => Lemma {
+                id: checker.clauses[clause].id,
+                clause: checker.clauses[clause].lits.clone(),
+                hints: Vec::new(),
+                delete: true,
+            },
+        });
+    }
+    let hints = checker.rup_ids(&hints);
+    refutation.steps.push(Lemma {
+        id: refutation.latest + step as u64 + 1,
+        clause: Vec::new(),
+        hints,
+        delete: false,
+    });
+    Ok(refutation)
+}
+
+/// A lemma of the trimmed proof with its hints in LRAT, or a deletion.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Lemma {
+    id: u64,
+    clause: Vec<Lit>,
+    hints: Vec<i64>,
+    delete: bool,
+}
+
+/// A verified proof, reduced to the core of the formula and the lemmas
+/// needed to refute it.  The last lemma is always the empty clause.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Refutation {
+    vars: i32,
+    core: Vec<Vec<Lit>>,
+    steps: Vec<Lemma>,
+    latest: u64,
+}
+
+impl Refutation {
+    /// The clauses of the formula used by the proof, in their order.  This
+    /// is an unsatisfiable core with the variables of the formula.
+    #[must_use]
+    pub fn core(&self) -> Cnf {
+        Cnf {
+            vars: self.vars,
+            clauses: self.core.clone(),
+            comments: Vec::new(),
+        }
+    }
+
+    /// The lemmas needed for the refutation, ending with the empty clause,
+    /// with the deletions of these lemmas and of all original clauses, so
+    /// that RAT lemmas are checked against the same clauses as in the
+    /// original proof.  It refutes the formula as well as its core, where
+    /// deleting clauses outside of the core has no effect.
+    #[must_use]
+    pub fn trimmed(&self) -> Proof {
+        let steps = self.steps.iter().map(|lemma| {
+            if lemma.delete {
+                Step::Delete(lemma.clause.clone())
+            } else {
+                Step::Add(lemma.clause.clone())
+            }
+        });
+        Proof {
+            steps: steps.collect(),
+        }
+    }
+
+    /// Writes the trimmed proof in LRAT, in the binary encoding if 'binary'
+    /// is set.  Original clauses are numbered from 1 in the order of the
+    /// formula and lemmas by the numbering of '`ProofError`'.  The hints
+    /// of RAT lemmas list every clause containing the negated pivot as a
+    /// negative id followed by the hints of the resolvent.
+    ///
+    /// # Errors
+    ///
+    /// '`Error::Io`' if writing fails.
+    ///
+    pub fn write_lrat(&self, writer: impl Write, binary: bool) -> Result<(), Error> {
+        let mut lrat = LratWriter::new(writer, binary);
+        lrat.latest = self.latest;
+        for lemma in &self.steps {
+            if lemma.delete {
+                lrat.deleted.push(lemma.id);
+            } else {
+                lrat.add_hints(lemma.id, &lemma.clause, &lemma.hints);
+            }
+        }
+        lrat.finish()?;
+        Ok(())
+    }
+}
+
+struct Clause {
+    /// The literals without duplicates, the first two are watched.
+    lits: Vec<Lit>,
+    /// The first literal in the proof, the pivot of RAT.
+    pivot: Option<Lit>,
+    id: u64,
+    active: bool,
+    core: bool,
+}
+
+/// The steps of the forward pass which are undone going backward.
+enum Record {
+    /// A lemma with the length of the trail before it.
+    Add {
+        clause: usize,
+        trail: usize,
+    },
+    Delete(usize),
+}
+
+/// The core steps found going backward, in reverse.
+enum Line {
+    Add { clause: usize, hints: Vec<i64> },
+    Delete(usize),
+}
+
+/// The start of conflict analysis.
+#[derive(Clone, Copy)]
+enum Seed {
+    /// A falsified clause.
+    Conflict(usize),
+    /// A literal of the checked clause which is already true.
+    Satisfied(Lit),
+}
+
+fn index(lit: Lit) -> usize {
+    lit.var().index().unsigned_abs() as usize
+}
+
+fn code(lit: Lit) -> usize {
+    2 * index(lit) + usize::from(lit.is_negative())
+}
+
+fn value(values: &[Option<bool>], lit: Lit) -> Option<bool> {
+    values[index(lit)].map(|value| value == lit.is_positive())
+}
+
+/// Unit propagation with watched literals over the active clauses, on top
+/// of a trail whose prefix is the assignment implied by the clauses at the
+/// current step.
+struct Checker {
+    clauses: Vec<Clause>,
+    watches: Vec<Vec<usize>>,
+    values: Vec<Option<bool>>,
+    reasons: Vec<Option<usize>>,
+    positions: Vec<usize>,
+    seen: Vec<bool>,
+    assumed: Vec<bool>,
+    marks: Vec<bool>,
+    trail: Vec<Lit>,
+    propagated: usize,
+}
+
+impl Checker {
+    fn new(formula: &Cnf, proof: &Proof) -> Self {
+        let steps = proof.steps.iter().map(|step| match step {
+            Step::Add(clause) | Step::Delete(clause) => clause,
+        });
+        let lits = formula.clauses.iter().chain(steps).flatten();
+        let vars = lits
+            .map(|&lit| index(lit))
+            .chain([formula.vars.unsigned_abs() as usize])
+            .max()
+            .unwrap_or(0)
+            + 1;
+        Self {
+            clauses: Vec::with_capacity(formula.clauses.len() + proof.steps.len()),
+            watches: vec![Vec::new(); 2 * vars],
+            values: vec![None; vars],
+            reasons: vec![None; vars],
+            positions: vec![0; vars],
+            seen: vec![false; vars],
+            assumed: vec![false; vars],
+            marks: vec![false; 2 * vars],
+            trail: Vec::new(),
+            propagated: 0,
+        }
+    }
+
+    /// Adds the formula and then the steps of the proof until propagation
+    /// finds a conflict, returning the steps to undo, the conflict and the
+    /// number of the step which led to it.
+    fn forward(
+        &mut self,
+        formula: &Cnf,
+        proof: &Proof,
+    ) -> Result<(Vec<Record>, usize, usize), Error> {
+        let mut active: HashMap<Vec<Lit>, Vec<usize>> = HashMap::new();
+        let mut records = Vec::new();
+        for (id, clause) in (1..).zip(&formula.clauses) {
+            let Some(clause) = self.push(clause, id, &mut active) else {
+                continue;
+            };
+            if let Some(conflict) = self.add(clause) {
+                return Ok((records, conflict, 0));
+            }
+        }
+        let latest = formula.clauses.len() as u64;
+        for (number, step) in (1..).zip(&proof.steps) {
+            let id = latest + number as u64;
+            match step {
+                Step::Add(lits) => {
+                    let Some(clause) = self.push(lits, id, &mut active) else {
+                        continue;
+                    };
+                    let trail = self.trail.len();
+                    records.push(Record::Add { clause, trail });
+                    if let Some(conflict) = self.add(clause) {
+                        return Ok((records, conflict, number));
+                    }
+                }
+                Step::Delete(lits) => {
+                    let key = key(lits);
+                    let Some(clauses) = active.get_mut(&key) else {
+                        continue;
+                    };
+                    let clause = *clauses.last().expect("empty entries are removed");
+                    if self.is_reason(clause) {
+                        continue;
+                    }
+                    clauses.pop();
+                    if clauses.is_empty() {
+                        active.remove(&key);
+                    }
+                    self.deactivate(clause);
+                    records.push(Record::Delete(clause));
+                }
+            }
+        }
+        Err(Error::Proof(ProofError::new(
+            0,
+            "unit propagation finds no conflict after the last step",
+        )))
+    }
+
+    /// Stores a clause without duplicate literals, 'None' for tautologies
+    /// which are never needed and stay inactive.
+    fn push(
+        &mut self,
+        lits: &[Lit],
+        id: u64,
+        active: &mut HashMap<Vec<Lit>, Vec<usize>>,
+    ) -> Option<usize> {
+        let mut unique = Vec::with_capacity(lits.len());
+        let mut tautology = false;
+        for &lit in lits {
+            if !self.marks[code(lit)] {
+                tautology |= self.marks[code(!lit)];
+                self.marks[code(lit)] = true;
+                unique.push(lit);
+            }
+        }
+        for &lit in &unique {
+            self.marks[code(lit)] = false;
+        }
+        let clause = self.clauses.len();
+        if !tautology {
+            active.entry(key(&unique)).or_default().push(clause);
+        }
+        self.clauses.push(Clause {
+            pivot: unique.first().copied(),
+            lits: unique,
+            id,
+            active: false,
+            core: false,
+        });
+        (!tautology).then_some(clause)
+    }
+
+    /// Activates 'clause' and propagates, returning a conflict.
+    fn add(&mut self, clause: usize) -> Option<usize> {
+        let lits = &self.clauses[clause].lits;
+        match lits.len() {
+            0 => return Some(clause),
+            1 => {
+                let lit = lits[0];
+                self.clauses[clause].active = true;
+                match value(&self.values, lit) {
+                    Some(true) => {}
+                    Some(false) => return Some(clause),
+                    None => self.assign(lit, Some(clause)),
+                }
+            }
+            _ => {
+                self.watch(clause);
+                let lits = &self.clauses[clause].lits;
+                let (first, second) = (lits[0], lits[1]);
+                match (value(&self.values, first), value(&self.values, second)) {
+                    (Some(false), _) => return Some(clause),
+                    (None, Some(false)) => self.assign(first, Some(clause)),
+                    _ => {}
+                }
+            }
+        }
+        self.propagate()
+    }
+
+    /// Activates 'clause', watching true literals first, then unassigned
+    /// ones and then those falsified last.
+    fn watch(&mut self, clause: usize) {
+        self.clauses[clause].active = true;
+        let lits = &mut self.clauses[clause].lits;
+        if lits.len() < 2 {
+            return;
+        }
+        let rank = |lit: Lit| match value(&self.values, lit) {
+            Some(true) => (0, 0),
+            None => (1, 0),
+            Some(false) => (2, usize::MAX - self.positions[index(lit)]),
+        };
+        for watched in 0..2 {
+            let best = (watched..lits.len())
+                .min_by_key(|&i| rank(lits[i]))
+                .expect("at least two literals");
+            lits.swap(watched, best);
+        }
+        self.watches[code(lits[0])].push(clause);
+        self.watches[code(lits[1])].push(clause);
+    }
+
+    fn deactivate(&mut self, clause: usize) {
+        self.clauses[clause].active = false;
+        let lits = &self.clauses[clause].lits;
+        if lits.len() < 2 {
+            return;
+        }
+        for lit in [lits[0], lits[1]] {
+            let watches = &mut self.watches[code(lit)];
+            if let Some(i) = watches.iter().position(|&other| other == clause) {
+                watches.swap_remove(i);
+            }
+        }
+    }
+
+    fn is_reason(&self, clause: usize) -> bool {
+        self.clauses[clause].lits.iter().any(|&lit| {
+            value(&self.values, lit) == Some(true) && self.reasons[index(lit)] == Some(clause)
+        })
+    }
+
+    fn assign(&mut self, lit: Lit, reason: Option<usize>) {
+        let index = index(lit);
+        self.values[index] = Some(lit.is_positive());
+        self.reasons[index] = reason;
+        self.positions[index] = self.trail.len();
+        self.trail.push(lit);
+    }
+
+    fn backtrack(&mut self, trail: usize) {
+        for lit in self.trail.drain(trail..) {
+            self.values[index(lit)] = None;
+            self.reasons[index(lit)] = None;
+        }
+        self.propagated = trail;
+    }
+
+    fn propagate(&mut self) -> Option<usize> {
+        while let Some(&lit) = self.trail.get(self.propagated) {
+            self.propagated += 1;
+            let falsified = !lit;
+            let mut watches = mem::take(&mut self.watches[code(falsified)]);
+            let mut conflict = None;
+            let mut i = 0;
+            while i < watches.len() {
+                let clause = watches[i];
+                let lits = &mut self.clauses[clause].lits;
+                if lits[0] == falsified {
+                    lits.swap(0, 1);
+                }
+                let other = lits[0];
+                if value(&self.values, other) == Some(true) {
+                    i += 1;
+                    continue;
+                }
+                let replacement =
+                    (2..lits.len()).find(|&k| value(&self.values, lits[k]) != Some(false));
+                if let Some(k) = replacement {
+                    lits.swap(1, k);
+                    self.watches[code(lits[1])].push(clause);
+                    watches.swap_remove(i);
+                    continue;
+                }
+                if value(&self.values, other) == Some(false) {
+                    conflict = Some(clause);
+                    break;
+                }
+                self.assign(other, Some(clause));
+                i += 1;
+            }
+            self.watches[code(falsified)] = watches;
+            if conflict.is_some() {
+                return conflict;
+            }
+        }
+        None
+    }
+
+    /// The clauses used to derive the conflict, in the order of the trail,
+    /// which are marked as core.  Literals of the checked clause are
+    /// assumed and need no reason.
+    fn analyze(&mut self, seed: Seed) -> Vec<usize> {
+        let (start, skipped) = match seed {
+            Seed::Conflict(clause) => (Some(clause), None),
+            Seed::Satisfied(lit) => (self.reasons[index(lit)], Some(index(lit))),
+        };
+        let mut pending = 0;
+        if let Some(clause) = start {
+            for &lit in &self.clauses[clause].lits {
+                let index = index(lit);
+                if Some(index) != skipped && !self.seen[index] {
+                    self.seen[index] = true;
+                    pending += 1;
+                }
+            }
+        }
+        let mut hints = Vec::new();
+        for position in (0..self.trail.len()).rev() {
+            if pending == 0 {
+                break;
+            }
+            let var = index(self.trail[position]);
+            if !self.seen[var] {
+                continue;
+            }
+            self.seen[var] = false;
+            pending -= 1;
+            if self.assumed[var] {
+                continue;
+            }
+            let Some(reason) = self.reasons[var] else {
+                continue;
+            };
+            hints.push(reason);
+            for &lit in &self.clauses[reason].lits {
+                let index = index(lit);
+                if index != var && !self.seen[index] {
+                    self.seen[index] = true;
+                    pending += 1;
+                }
+            }
+        }
+        hints.reverse();
+        hints.extend(start);
+        for &clause in &hints {
+            self.clauses[clause].core = true;
+        }
+        hints
+    }
+
+    /// The hints showing that 'lits' is implied by unit propagation.
+    fn implied(&mut self, lits: &[Lit]) -> Option<Vec<usize>> {
+        let trail = self.trail.len();
+        for &lit in lits {
+            self.assumed[index(lit)] = true;
+        }
+        let mut seed = None;
+        for &lit in lits {
+            match value(&self.values, lit) {
+                Some(true) => {
+                    seed = Some(Seed::Satisfied(lit));
+                    break;
+                }
+                Some(false) => {}
+                None => self.assign(!lit, None),
+            }
+        }
+        let seed = seed.or_else(|| self.propagate().map(Seed::Conflict));
+        let hints = seed.map(|seed| self.analyze(seed));
+        for &lit in lits {
+            self.assumed[index(lit)] = false;
+        }
+        self.backtrack(trail);
+        hints
+    }
+
+    /// Verifies a core lemma by RUP or by RAT on its pivot, returning its
+    /// hints with the candidates of RAT as negative ids.
+    fn justify(&mut self, clause: usize) -> Result<Vec<i64>, ProofError> {
+        let lits = self.clauses[clause].lits.clone();
+        if let Some(hints) = self.implied(&lits) {
+            return Ok(self.rup_ids(&hints));
+        }
+        let id = self.clauses[clause].id;
+        let Some(pivot) = self.clauses[clause].pivot else {
+            return Err(ProofError::new(
+                id,
+                "the empty clause is not implied by unit propagation",
+            ));
+        };
+        let mut hints = Vec::new();
+        for candidate in 0..clause {
+            let other = &self.clauses[candidate];
+            if !other.active || !other.lits.contains(&!pivot) {
+                continue;
+            }
+            let mut resolvent = lits.clone();
+            resolvent.extend(other.lits.iter().filter(|&&lit| lit != !pivot));
+            let Some(chain) = self.implied(&resolvent) else {
+                return Err(ProofError::new(
+                    id,
+                    format!("lemma is neither RUP nor RAT on {pivot}"),
+                ));
+            };
+            self.clauses[candidate].core = true;
+            hints.push(-self.hint(candidate));
+            hints.extend(self.rup_ids(&chain));
+        }
+        Ok(hints)
+    }
+
+    fn hint(&self, clause: usize) -> i64 {
+        i64::try_from(self.clauses[clause].id).expect("fewer than 2^63 clauses")
+    }
+
+    fn rup_ids(&self, hints: &[usize]) -> Vec<i64> {
+        hints.iter().map(|&clause| self.hint(clause)).collect()
+    }
+
+    /// The literals of a lemma with its pivot first.
+    fn ordered(&self, clause: usize) -> Vec<Lit> {
+        let clause = &self.clauses[clause];
+        let mut lits = clause.lits.clone();
+        if let Some(pivot) = clause.pivot {
+            let position = lits.iter().position(|&lit| lit == pivot);
+            lits.swap(0, position.expect("the pivot is a literal"));
+        }
+        lits
+    }
+}
+
+/// The literals of a clause as set, to find the clauses deleted by a proof.
+fn key(lits: &[Lit]) -> Vec<Lit> {
+    let mut key = lits.to_vec();
+    key.sort_unstable();
+    key.dedup();
+    key
+}
@@ -0,0 +1,296 @@
+use super::ProofError;
+use crate::{Error, Lit, ProofTracer};
+use std::collections::{HashMap, HashSet};
+
+/// Assignment used while checking a single step, undone afterwards.
+#[derive(Debug, Default)]
+struct Assignment {
+    values: Vec<Option<bool>>,
+    trail: Vec<Lit>,
+}
+
+impl Assignment {
+    fn value(&self, lit: Lit) -> Option<bool> {
+        let index = index(lit);
+        let value = self.values.get(index).copied().flatten()?;
+        Some(value == lit.is_positive())
+    }
+
+    fn assign(&mut self, lit: Lit) {
+        let index = index(lit);
+        if self.values.len() <= index {
+            self.values.resize(index + 1, None);
+        }
+        self.values[index] = Some(lit.is_positive());
+        self.trail.push(lit);
+    }
+
+    fn backtrack(&mut self) {
+        for lit in self.trail.drain(..) {
+            self.values[index(lit)] = None;
+        }
+    }
+}
+
+fn index(lit: Lit) -> usize {
+    lit.var().index().unsigned_abs() as usize
+}
+
+/// Proof tracer checking the proof of the solver while it is produced.
+///
+/// Every derived clause must follow by reverse unit propagation over its
+/// antecedents, in the order given: after assigning the negation of the
+/// clause, each antecedent has to be unit or falsified, and the last one
+/// falsified.  Antecedents are only given by the solver if the checker is
+/// connected with 'antecedents' set.  Deleted clauses are forgotten and may
+/// not be used afterwards.  The clauses of '`ProofTracer::add_assumption_clause`'
+/// are checked the same way and must consist of negated assumptions or
+/// constraint literals.
+///
+/// '`ProofTracer::conclude_unsat`' is verified against the empty clause or the
+/// failed assumption clause it names, so '`CaDiCal::conclude`' has to be called
+/// to complete the check.  The first failing step is kept and all later steps
+/// are ignored.
+///
+/// ```rust
+/// use cadical_sys::{proof::LratChecker, CaDiCal, Status};
+///
+/// let mut solver = CaDiCal::new();
+/// let mut checker = LratChecker::new();
+/// let status = solver.with_proof_tracer(&mut checker, true, false, |solver| {
+///     solver.clause2(1, 2);
+///     solver.clause2(-1, 2);
+///     solver.clause2(1, -2);
+///     solver.clause2(-1, -2);
+///     let status = solver.solve();
+///     solver.conclude();
+///     status
+/// });
+/// assert_eq!(status, Status::UNSATISFIABLE);
+/// assert!(checker.check().is_ok());
+/// ```
+#[derive(Debug, Default)]
+pub struct LratChecker {
+    clauses: HashMap<u64, Vec<Lit>>,
+    assumption_clauses: HashSet<u64>,
+    assumptions: Vec<Lit>,
+    constraint: Vec<Lit>,
+    assignment: Assignment,
+    error: Option<ProofError>,
+    verified: bool,
+}
+
+impl LratChecker {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The first step which could not be verified.
+    #[must_use]
+    #[inline]
+    pub fn error(&self) -> Option<&ProofError> {
+        self.error.as_ref()
+    }
+
+    /// Whether the last conclusion of the solver was unsatisfiable and its
+    /// proof was verified.
+    #[must_use]
+    #[inline]
+    pub fn verified(&self) -> bool {
+        self.verified && self.error.is_none()
+    }
+
+    /// Returns the result of the check.
+    ///
+    /// # Errors
+    ///
+    /// Returns '`Error::Proof`' with the first failing step, or with clause
+    /// id 0 if no unsatisfiable conclusion was verified.
+    pub fn check(&self) -> Result<(), Error> {
+        match &self.error {
+            Some(error) => Err(Error::Proof(error.clone())),
+            None if self.verified => Ok(()),
+            None => Err(Error::Proof(ProofError::new(
+                0,
+                "no unsatisfiable conclusion was verified",
+            ))),
+        }
+    }
+
+    fn fail(&mut self, error: ProofError) {
+        if self.error.is_none() {
+            self.error = Some(error);
+        }
+    }
+
+    fn derive(&mut self, id: u64, clause: &[Lit], antecedents: &[u64]) -> Result<(), ProofError> {
+        if self.clauses.contains_key(&id) {
+            return Err(ProofError::new(id, "id is already used"));
+        }
+        if antecedents.is_empty() {
+            return Err(ProofError::new(
+                id,
+                "missing antecedents, connect the checker with 'antecedents' set",
+            ));
+        }
+        let result = self.propagate(id, clause, antecedents);
+        self.assignment.backtrack();
+        result?;
+        self.clauses.insert(id, clause.to_vec());
+        Ok(())
+    }
+
+    fn propagate(
+        &mut self,
+        id: u64,
+        clause: &[Lit],
+        antecedents: &[u64],
+    ) -> Result<(), ProofError> {
+        for &lit in clause {
+            match self.assignment.value(lit) {
+                // tautologies are implied by anything
+                Some(true) => return Ok(()),
+                Some(false) => {}
+                None => self.assignment.assign(!lit),
+            }
+        }
+        for &antecedent in antecedents {
+            let Some(hint) = self.clauses.get(&antecedent) else {
+                return Err(ProofError::new(
+                    id,
+                    format!("antecedent {antecedent} is unknown"),
+                ));
+            };
+            let mut unit = None;
+            for &lit in hint {
+                match self.assignment.value(lit) {
+                    Some(true) => {
+                        return Err(ProofError::new(
+                            id,
+                            format!("antecedent {antecedent} is satisfied"),
+                        ))
+                    }
+                    Some(false) => {}
+                    None => {
+                        if unit.replace(lit).is_some_and(|other| other != lit) {
+                            return Err(ProofError::new(
+                                id,
+                                format!("antecedent {antecedent} is not unit"),
+                            ));
+                        }
+                    }
+                }
+            }
+            match unit {
+                Some(lit) => self.assignment.assign(lit),
+                None => return Ok(()),
+            }
+        }
+        Err(ProofError::new(id, "antecedents do not lead to a conflict"))
+    }
+
+    fn assumption_clause(
+        &mut self,
+        id: u64,
+        clause: &[Lit],
+        antecedents: &[u64],
+    ) -> Result<(), ProofError> {
+        for &lit in clause {
+            let assumed = self.assumptions.contains(&!lit) || self.constraint.contains(&lit);
+            if !assumed {
+                return Err(ProofError::new(
+                    id,
+                    format!("literal {lit} is neither a negated assumption nor in the constraint"),
+                ));
+            }
+        }
+        self.derive(id, clause, antecedents)?;
+        self.assumption_clauses.insert(id);
+        Ok(())
+    }
+
+    fn conclude(&self, ids: &[u64]) -> Result<(), ProofError> {
+        if ids.is_empty() {
+            return Err(ProofError::new(0, "no clause concludes unsatisfiability"));
+        }
+        for &id in ids {
+            match self.clauses.get(&id) {
+                None => return Err(ProofError::new(id, "concluding clause is unknown")),
+                Some(clause) if !clause.is_empty() && !self.assumption_clauses.contains(&id) => {
+                    return Err(ProofError::new(
+                        id,
+                        "concluding clause is neither empty nor an assumption clause",
+                    ))
+                }
+                Some(_) => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+impl ProofTracer for LratChecker {
+    fn add_original_clause(&mut self, id: u64, _: bool, clause: &[Lit], _: bool) {
+        self.clauses.insert(id, clause.to_vec());
+    }
+
+    fn add_derived_clause(&mut self, id: u64, _: bool, clause: &[Lit], antecedents: &[u64]) {
+        if self.error.is_none() {
+            if let Err(error) = self.derive(id, clause, antecedents) {
+                self.fail(error);
+            }
+        }
+    }
+
+    fn delete_clause(&mut self, id: u64, _: bool, _: &[Lit]) {
+        if self.clauses.remove(&id).is_none() {
+            self.fail(ProofError::new(id, "deleted clause is unknown"));
+        }
+        self.assumption_clauses.remove(&id);
+    }
+
+    fn weaken_minus(&mut self, _: u64, _: &[Lit]) {}
+
+    fn strengthen(&mut self, _: u64) {}
+
+    fn finalize_clause(&mut self, _: u64, _: &[Lit]) {}
+
+    fn add_assumption(&mut self, lit: Lit) {
+        self.assumptions.push(lit);
+    }
+
+    fn add_constraint(&mut self, clause: &[Lit]) {
+        self.constraint = clause.to_vec();
+    }
+
+    fn reset_assumptions(&mut self) {
+        self.assumptions.clear();
+    }
+
+    fn add_assumption_clause(&mut self, id: u64, clause: &[Lit], antecedents: &[u64]) {
+        if self.error.is_none() {
+            if let Err(error) = self.assumption_clause(id, clause, antecedents) {
+                self.fail(error);
+            }
+        }
+    }
+
+    fn conclude_sat(&mut self, _: i32, _: &[Lit]) {
+        self.verified = false;
+    }
+
+    fn conclude_unsat(&mut self, _: i32, ids: &[u64]) {
+        self.verified = false;
+        if self.error.is_none() {
+            match self.conclude(ids) {
+                Ok(()) => self.verified = true,
+                Err(error) => self.fail(error),
+            }
+        }
+    }
+
+    fn conclude_unknown(&mut self, _: &[Lit]) {
+        self.verified = false;
+    }
+}
@@ -0,0 +1,269 @@
+//! Results in the output format of the SAT competition, that is comment
+//! lines starting with 'c', a status line and for satisfiable formulas the
+//! model in 'v' lines terminated by '0'.
+//!
+//! ```text
+//! c comment
+//! s SATISFIABLE
+//! v 1 -2 3 0
+//! ```
+//!
+//! ```rust
+//! use cadical_sys::{solution::Solution, CaDiCal, Status};
+//!
+//! let mut solver = CaDiCal::new();
+//! solver.clause2(1, 2);
+//! solver.clause1(-1);
+//! let status = solver.solve();
+//! let solution = Solution::from_solver(&mut solver, status);
+//! assert_eq!(solution.to_string(), "s SATISFIABLE\nv -1 2 0\n");
+//! assert_eq!(solution.to_string().parse::<Solution>().unwrap(), solution);
+//! ```
+
+use crate::{
+    compress,
+    dimacs::{Cnf, Cursor, ParseError},
+    CaDiCal, Error, Lit, Status, Var,
+};
+use std::{
+    fmt,
+    io::{BufRead, Write},
+    path::Path,
+    str::FromStr,
+};
+
+/// The maximum length of 'v' lines written by '`Solution::write`', which
+/// is also what `CaDiCaL` uses.
+pub const LINE_WIDTH: usize = 78;
+
+/// A status with the model if it is satisfiable.  The comments of a parsed
+/// file are kept in order, but not their positions.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Solution {
+    pub status: Status,
+    pub model: Vec<Lit>,
+    pub comments: Vec<String>,
+}
+
+impl Solution {
+    /// The result of 'solve' with the values of all variables if 'status'
+    /// is '`Status::SATISFIABLE`'.
+    ///
+    ///   require (SATISFIED) if status is SATISFIABLE
+    ///
+    pub fn from_solver(solver: &mut CaDiCal, status: Status) -> Self {
+        let mut model = Vec::new();
+        if status == Status::SATISFIABLE {
+            for var in (1..=solver.vars()).filter_map(Var::new) {
+                model.push(var.lit(solver.value(var.positive())));
+            }
+        }
+        Solution {
+            status,
+            model,
+            comments: Vec::new(),
+        }
+    }
+
+    /// Parses a solution file.  A status line is required and 'v' lines
+    /// are only allowed after 's SATISFIABLE', where each variable has at
+    /// most one value and nothing follows the terminating '0'.
+    ///
+    /// # Errors
+    ///
+    /// '`Error::Parse`' for the first syntax error and '`Error::Io`' if
+    /// reading fails.
+    ///
+    pub fn parse(reader: impl BufRead) -> Result<Self, Error> {
+        let mut cursor = Cursor::new(reader);
+        let mut status = None;
+        let mut solution = Solution {
+            status: Status::UNKNOWN,
+            model: Vec::new(),
+            comments: Vec::new(),
+        };
+        let mut assigned = Vec::new();
+        let mut values = false;
+        let mut terminated = false;
+        while cursor.next_line()? {
+            cursor.skip_space();
+            match cursor.peek() {
+                None => {}
+                Some(b'c') => solution.comments.push(cursor.rest()),
+                Some(b's') if status.is_none() => {
+                    cursor.keyword("s")?;
+                    cursor.separator(false)?;
+                    let column = cursor.column();
+                    let parsed = match cursor.take_while(|c| c != b' ' && c != b'\t').as_str() {
+                        "SATISFIABLE" => Status::SATISFIABLE,
+                        "UNSATISFIABLE" => Status::UNSATISFIABLE,
+                        "UNKNOWN" => Status::UNKNOWN,
+                        _ => {
+                            return Err(Error::Parse(ParseError::new(
+                                cursor.line(),
+                                column,
+                                "expected 'SATISFIABLE', 'UNSATISFIABLE' or 'UNKNOWN'",
+                            )))
+                        }
+                    };
+                    cursor.skip_space();
+                    if !cursor.at_end() {
+                        return Err(cursor.error("unexpected character after status"));
+                    }
+                    status = Some(parsed);
+                }
+                Some(b's') => return Err(cursor.error("second status line")),
+                Some(b'v') if status == Some(Status::SATISFIABLE) => {
+                    cursor.keyword("v")?;
+                    values = true;
+                    if !cursor.at_end() && !cursor.skip_space() {
+                        return Err(cursor.error("unexpected character after 'v'"));
+                    }
+                    while !cursor.at_end() {
+                        if terminated {
+                            return Err(cursor.error("value after terminating '0'"));
+                        }
+                        let (lit, column) = cursor.integer()?;
+                        cursor.skip_space();
+                        let Some(lit) = Lit::from_dimacs(lit) else {
+                            terminated = true;
+                            continue;
+                        };
+                        let index = index(lit);
+                        if assigned.len() <= index {
+                            assigned.resize(index + 1, false);
+                        }
+                        if std::mem::replace(&mut assigned[index], true) {
+                            return Err(Error::Parse(ParseError::new(
+                                cursor.line(),
+                                column,
+                                format!("variable {} assigned twice", lit.var()),
+                            )));
+                        }
+                        solution.model.push(lit);
+                    }
+                }
+                Some(b'v') => return Err(cursor.error("values without 's SATISFIABLE'")),
+                Some(_) => return Err(cursor.error("expected 'c', 's' or 'v'")),
+            }
+        }
+        if values && !terminated {
+            return Err(cursor.error("missing terminating '0'"));
+        }
+        solution.status = status.ok_or_else(|| cursor.error("missing status line"))?;
+        Ok(solution)
+    }
+
+    /// The value of 'var' in the model, 'None' if it is unassigned.
+    #[must_use]
+    pub fn value(&self, var: Var) -> Option<bool> {
+        self.model
+            .iter()
+            .find(|lit| lit.var() == var)
+            .map(|lit| lit.is_positive())
+    }
+
+    /// The index of the first clause of 'cnf' without a literal which is
+    /// true in the model, 'None' if the model satisfies all clauses.
+    #[must_use]
+    pub fn falsified(&self, cnf: &Cnf) -> Option<usize> {
+        let mut values = Vec::new();
+        for lit in &self.model {
+            let index = index(*lit);
+            if values.len() <= index {
+                values.resize(index + 1, None);
+            }
+            values[index] = Some(lit.is_positive());
+        }
+        let satisfied =
+            |lit: &Lit| values.get(index(*lit)).copied().flatten() == Some(lit.is_positive());
+        cnf.clauses
+            .iter()
+            .position(|clause| !clause.iter().any(satisfied))
+    }
+
+    /// Whether the solution is satisfiable and its model satisfies the
+    /// DIMACS file at 'path', which is parsed with 'strict' set to '1' and
+    /// decompressed as in '`CaDiCal::read_dimacs2`'.
+    ///
+    /// # Errors
+    ///
+    /// '`Error::Open`' if the file cannot be opened and otherwise the
+    /// errors of '`Cnf::parse`'.
+    ///
+    pub fn verify(&self, path: impl AsRef<Path>) -> Result<bool, Error> {
+        let cnf = Cnf::parse(compress::reader(path.as_ref())?, 1)?;
+        Ok(self.status == Status::SATISFIABLE && self.falsified(&cnf).is_none())
+    }
+
+    /// Writes the comments, the status line and the model in 'v' lines of
+    /// at most '`LINE_WIDTH`' characters.
+    ///
+    /// # Errors
+    ///
+    /// '`Error::Io`' if writing fails.
+    ///
+    pub fn write(&self, writer: impl Write) -> Result<(), Error> {
+        self.write_wrapped(writer, LINE_WIDTH)
+    }
+
+    /// Writes as '`Solution::write`' with 'v' lines of at most 'width'
+    /// characters, unless a single value is longer.
+    ///
+    /// # Errors
+    ///
+    /// '`Error::Io`' if writing fails.
+    ///
+    pub fn write_wrapped(&self, mut writer: impl Write, width: usize) -> Result<(), Error> {
+        for comment in &self.comments {
+            if comment.is_empty() {
+                writeln!(writer, "c")?;
+            } else {
+                writeln!(writer, "c {comment}")?;
+            }
+        }
+        let status = match self.status {
+            Status::SATISFIABLE => "SATISFIABLE",
+            Status::UNSATISFIABLE => "UNSATISFIABLE",
+            Status::UNKNOWN => "UNKNOWN",
+        };
+        writeln!(writer, "s {status}")?;
+        if self.status == Status::SATISFIABLE {
+            let mut line = String::from("v");
+            let values = self.model.iter().map(ToString::to_string);
+            for value in values.chain(std::iter::once("0".to_string())) {
+                if line.len() > 1 && line.len() + 1 + value.len() > width {
+                    writeln!(writer, "{line}")?;
+                    line.truncate(1);
+                }
+                line.push(' ');
+                line.push_str(&value);
+            }
+            writeln!(writer, "{line}")?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+fn index(lit: Lit) -> usize {
+    lit.var().index().unsigned_abs() as usize
+}
+
+/// Parses with the defaults of '`Solution::parse`'.
+impl FromStr for Solution {
+    type Err = Error;
+
+    fn from_str(solution: &str) -> Result<Self, Error> {
+        Solution::parse(solution.as_bytes())
+    }
+}
+
+/// The text written by '`Solution::write`'.
+impl fmt::Display for Solution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut buffer = Vec::new();
+        self.write(&mut buffer).map_err(|_| fmt::Error)?;
+        f.write_str(&String::from_utf8_lossy(&buffer))
+    }
+}
@@ -0,0 +1,206 @@
+use crate::Error;
+use std::{fmt, ops};
+
+/// States are represented by a bit-set in order to combine them.  The solver
+/// itself is always in exactly one of the single states, while the combined
+/// states 'READY', 'VALID' and 'INVALID' are used to check contracts.
+#[derive(PartialEq, Eq, Hash, Clone, Copy)]
+pub struct State(i32);
+
+impl State {
+    /// during initialization (invalid)
+    pub const INITIALIZING: State = State(1);
+    /// configure options (with 'set')
+    pub const CONFIGURING: State = State(2);
+    /// ready to call 'solve'
+    pub const STEADY: State = State(4);
+    /// adding clause literals (zero missing)
+    pub const ADDING: State = State(8);
+    /// while solving (within 'solve')
+    pub const SOLVING: State = State(16);
+    /// satisfiable allows 'val'
+    pub const SATISFIED: State = State(32);
+    /// unsatisfiable allows 'failed'
+    pub const UNSATISFIED: State = State(64);
+    /// during and after deletion (invalid)
+    pub const DELETING: State = State(128);
+
+    /// These combined states are used to check contracts.
+    /// CONFIGURING | STEADY | SATISFIED | UNSATISFIED,
+    pub const READY: State = State(102);
+    /// READY | ADDING
+    pub const VALID: State = State(110);
+    /// INITIALIZING | DELETING
+    pub const INVALID: State = State(129);
+
+    /// Named states in the order used by 'Display', combined states first.
+    const NAMES: [(State, &'static str); 11] = [
+        (State::VALID, "VALID"),
+        (State::READY, "READY"),
+        (State::INVALID, "INVALID"),
+        (State::INITIALIZING, "INITIALIZING"),
+        (State::CONFIGURING, "CONFIGURING"),
+        (State::STEADY, "STEADY"),
+        (State::ADDING, "ADDING"),
+        (State::SOLVING, "SOLVING"),
+        (State::SATISFIED, "SATISFIED"),
+        (State::UNSATISFIED, "UNSATISFIED"),
+        (State::DELETING, "DELETING"),
+    ];
+
+    /// All bits used by `CaDiCaL`.
+    const ALL: i32 = 255;
+
+    /// The state without any bits set.
+    #[must_use]
+    #[inline]
+    pub const fn empty() -> Self {
+        State(0)
+    }
+
+    /// The state of the given bits, 'None' if a bit is not used by `CaDiCaL`.
+    #[must_use]
+    #[inline]
+    pub const fn from_bits(bits: i32) -> Option<Self> {
+        if bits & !Self::ALL == 0 {
+            Some(State(bits))
+        } else {
+            None
+        }
+    }
+
+    /// The state of the given bits, keeping those not used by `CaDiCaL`.
+    #[must_use]
+    #[inline]
+    pub const fn from_bits_retain(bits: i32) -> Self {
+        State(bits)
+    }
+
+    #[must_use]
+    #[inline]
+    pub const fn bits(self) -> i32 {
+        self.0
+    }
+
+    #[must_use]
+    #[inline]
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Whether all bits of 'other' are set.
+    #[must_use]
+    #[inline]
+    pub const fn contains(self, other: State) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Whether some bit of 'other' is set.
+    #[must_use]
+    #[inline]
+    pub const fn intersects(self, other: State) -> bool {
+        self.0 & other.0 != 0
+    }
+
+    /// Whether the solver may be configured, solved, or queried, that is
+    /// 'READY.intersects (self)'.
+    #[must_use]
+    #[inline]
+    pub const fn is_ready(self) -> bool {
+        self.intersects(State::READY)
+    }
+
+    /// Whether the solver may be given literals, that is
+    /// 'VALID.intersects (self)'.
+    #[must_use]
+    #[inline]
+    pub const fn is_valid(self) -> bool {
+        self.intersects(State::VALID)
+    }
+}
+
+impl TryFrom<i32> for State {
+    type Error = Error;
+
+    fn try_from(val: i32) -> Result<Self, Error> {
+        State::from_bits(val).ok_or(Error::InvalidState(val))
+    }
+}
+
+impl From<State> for i32 {
+    #[inline]
+    fn from(state: State) -> i32 {
+        state.bits()
+    }
+}
+
+impl ops::BitOr for State {
+    type Output = State;
+
+    #[inline]
+    fn bitor(self, other: State) -> State {
+        State(self.0 | other.0)
+    }
+}
+
+impl ops::BitOrAssign for State {
+    #[inline]
+    fn bitor_assign(&mut self, other: State) {
+        self.0 |= other.0;
+    }
+}
+
+impl ops::BitAnd for State {
+    type Output = State;
+
+    #[inline]
+    fn bitand(self, other: State) -> State {
+        State(self.0 & other.0)
+    }
+}
+
+impl ops::BitAndAssign for State {
+    #[inline]
+    fn bitand_assign(&mut self, other: State) {
+        self.0 &= other.0;
+    }
+}
+
+/// Names the set bits separated by ' | ', preferring combined states and
+/// printing unknown bits in hexadecimal, for instance 'VALID',
+/// 'STEADY | ADDING' or 'STEADY | 0x100'.
+impl fmt::Display for State {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return f.write_str("0");
+        }
+        let mut remaining = self.0;
+        let mut first = true;
+        let mut separate = |f: &mut fmt::Formatter<'_>| {
+            if first {
+                first = false;
+                Ok(())
+            } else {
+                f.write_str(" | ")
+            }
+        };
+        for (state, name) in State::NAMES {
+            if self.contains(state) && remaining & state.0 != 0 {
+                separate(f)?;
+                f.write_str(name)?;
+                remaining &= !state.0;
+            }
+        }
+        if remaining != 0 {
+            separate(f)?;
+            write!(f, "{remaining:#x}")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for State {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "State({self})")
+    }
+}
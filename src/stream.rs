@@ -0,0 +1,64 @@
+//! Writing DIMACS to a '`std::io::Write`' through '`CaDiCal::traverse_clauses`',
+//! which is how `CaDiCaL` writes DIMACS files itself.
+
+use crate::{ClauseIterator, Lit};
+use std::io::{self, Write};
+
+/// Counts the clauses and the largest variable for the DIMACS header.
+#[derive(Default)]
+pub(crate) struct ClauseCounter {
+    pub(crate) vars: i32,
+    pub(crate) clauses: u64,
+}
+
+impl ClauseIterator for ClauseCounter {
+    fn clause(&mut self, clause: &[Lit]) -> bool {
+        for lit in clause {
+            self.vars = self.vars.max(lit.var().index());
+        }
+        self.clauses += 1;
+        true
+    }
+}
+
+/// Writes each clause as a DIMACS line, stopping at the first write error.
+pub(crate) struct ClauseWriter<W> {
+    writer: W,
+    pub(crate) error: Option<io::Error>,
+}
+
+impl<W: Write> ClauseWriter<W> {
+    pub(crate) fn new(writer: W) -> Self {
+        Self {
+            writer,
+            error: None,
+        }
+    }
+
+    pub(crate) fn header(&mut self, vars: i32, clauses: u64) -> io::Result<()> {
+        writeln!(self.writer, "p cnf {vars} {clauses}")
+    }
+
+    pub(crate) fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+
+    fn write(&mut self, clause: &[Lit]) -> io::Result<()> {
+        for lit in clause {
+            write!(self.writer, "{lit} ")?;
+        }
+        self.writer.write_all(b"0\n")
+    }
+}
+
+impl<W: Write> ClauseIterator for ClauseWriter<W> {
+    fn clause(&mut self, clause: &[Lit]) -> bool {
+        match self.write(clause) {
+            Ok(()) => true,
+            Err(error) => {
+                self.error = Some(error);
+                false
+            }
+        }
+    }
+}
@@ -0,0 +1,28 @@
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
+/// Stops a '`CaDiCal`' from any thread, also while it is inside 'solve'.
+///
+/// Obtained through '`CaDiCal::terminate_handle`'.  A request stops the
+/// running or, if none is running, the next call of 'solve', 'simplify',
+/// 'lookahead' or '`generate_cubes`', which then returns as if a connected
+/// terminator returned 'true'.  The request is withdrawn when that call
+/// returns.
+#[derive(Debug, Clone)]
+pub struct TerminateHandle {
+    stop: Arc<AtomicBool>,
+}
+
+impl TerminateHandle {
+    pub(crate) fn new(stop: Arc<AtomicBool>) -> Self {
+        Self { stop }
+    }
+
+    /// Asks the solver to stop as soon as it checks for termination.
+    #[inline]
+    pub fn terminate(&self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
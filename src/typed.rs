@@ -0,0 +1,85 @@
+use crate::{CaDiCal, Lit};
+
+/// View of a '`CaDiCal`' whose functions take '`Lit`' arguments only.  Raw
+/// DIMACS integers, which may be '0' or '`INT_MIN`', and '`Var`'s, which are
+/// no literals, are compile errors here instead of panics.  The contracts
+/// are those of the functions of '`CaDiCal`' with the same names.
+///
+/// Obtained through '`CaDiCal::typed`'.
+pub struct Typed<'s> {
+    solver: &'s mut CaDiCal,
+}
+
+impl<'s> Typed<'s> {
+    pub(crate) fn new(solver: &'s mut CaDiCal) -> Self {
+        Self { solver }
+    }
+
+    /// Adds the clause of 'lits' as '`CaDiCal::clause6`'.
+    #[inline]
+    pub fn clause(&mut self, lits: &[Lit]) {
+        self.solver.clause6(lits);
+    }
+
+    #[inline]
+    pub fn assume(&mut self, lit: Lit) {
+        self.solver.assume(lit);
+    }
+
+    /// Replaces the constraint clause with 'lits', see
+    /// '`CaDiCal::constrain`'.
+    #[inline]
+    pub fn constrain(&mut self, lits: &[Lit]) {
+        for &lit in lits {
+            self.solver.constrain(lit);
+        }
+        self.solver.constrain(0);
+    }
+
+    /// The literal of the variable of 'lit' which is true in the model.
+    #[must_use]
+    #[inline]
+    pub fn val(&mut self, lit: Lit) -> Lit {
+        let val = self.solver.val(lit);
+        // 'val' returns 'lit' or its negation
+        lit.var().lit(val > 0)
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn value(&mut self, lit: Lit) -> bool {
+        self.solver.value(lit)
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn failed(&mut self, lit: Lit) -> bool {
+        self.solver.failed(lit)
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn frozen(&self, lit: Lit) -> bool {
+        self.solver.frozen(lit)
+    }
+
+    #[inline]
+    pub fn freeze(&mut self, lit: Lit) {
+        self.solver.freeze(lit);
+    }
+
+    #[inline]
+    pub fn melt(&mut self, lit: Lit) {
+        self.solver.melt(lit);
+    }
+
+    #[inline]
+    pub fn phase(&mut self, lit: Lit) {
+        self.solver.phase(lit);
+    }
+
+    #[inline]
+    pub fn unphase(&mut self, lit: Lit) {
+        self.solver.unphase(lit);
+    }
+}
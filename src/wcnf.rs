@@ -0,0 +1,301 @@
+//! Weighted CNF for `MaxSAT`, in the classic format with a 'p wcnf' header and
+//! in the header-less format of the `MaxSAT` Evaluations since 2022, where
+//! hard clauses start with 'h' and soft clauses with their weight.
+//!
+//! ```rust
+//! use cadical_sys::{wcnf::Wcnf, CaDiCal, Status};
+//!
+//! let wcnf: Wcnf = "c example\nh 1 2 0\n3 -1 0\n5 -2 0\n".parse().unwrap();
+//! assert_eq!(wcnf.hard.len(), 1);
+//! assert_eq!(wcnf.soft[1].weight, 5);
+//!
+//! let mut solver = CaDiCal::new();
+//! wcnf.add_hard(&mut solver);
+//! assert_eq!(solver.solve(), Status::SATISFIABLE);
+//! ```
+
+use crate::{
+    dimacs::{Cursor, ParseError},
+    CaDiCal, Error, Lit,
+};
+use std::{
+    fmt,
+    io::{self, BufRead, Write},
+    str::FromStr,
+};
+
+/// A soft clause, whose weight is paid if it is falsified.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SoftClause {
+    pub weight: u64,
+    pub lits: Vec<Lit>,
+}
+
+/// How a WCNF file is written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Format {
+    /// 'p wcnf <vars> <clauses> <top>' where hard clauses have weight 'top'.
+    Classic,
+    /// The format of the `MaxSAT` Evaluations since 2022 with 'h' for hard
+    /// clauses and without header.
+    #[default]
+    Evaluation,
+}
+
+/// An owned weighted formula.  The comments of the file are kept in order,
+/// but not their positions relative to the clauses.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Wcnf {
+    pub vars: i32,
+    pub hard: Vec<Vec<Lit>>,
+    pub soft: Vec<SoftClause>,
+    pub comments: Vec<String>,
+}
+
+/// The counts of a 'p wcnf' header, where 'top' is absent in files without
+/// hard clauses.
+#[derive(Clone, Copy)]
+struct Header {
+    vars: i32,
+    clauses: u64,
+    top: Option<u64>,
+}
+
+impl Wcnf {
+    /// Parses a whole file in either format, told apart by the 'p wcnf'
+    /// header.  The 'strict' levels are those of '`dimacs::Parser`', where
+    /// the counts checked with '1' only exist in the classic format.
+    /// Weights have to be positive and in the classic format soft clauses
+    /// weigh less than 'top'.
+    ///
+    /// # Errors
+    ///
+    /// '`Error::Parse`' for the first syntax error and '`Error::Io`' if
+    /// reading fails.
+    ///
+    pub fn parse(reader: impl BufRead, strict: i32) -> Result<Self, Error> {
+        let mut cursor = Cursor::new(reader);
+        let mut wcnf = Wcnf::default();
+        let mut format = None;
+        let mut header: Option<Header> = None;
+        // the weight of the current clause, 'None' for hard clauses
+        let mut weight: Option<Option<u64>> = None;
+        let mut clause = Vec::new();
+        let mut parsed = 0;
+        loop {
+            if cursor.at_end() && !cursor.next_line()? {
+                break;
+            }
+            cursor.skip_space();
+            match cursor.peek() {
+                None => {}
+                Some(b'c') => wcnf.comments.push(cursor.rest()),
+                Some(b'p') if format.is_none() => {
+                    header = Some(Self::parse_header(&mut cursor, strict)?);
+                    format = Some(Format::Classic);
+                }
+                Some(b'h') if weight.is_none() && format != Some(Format::Classic) => {
+                    format = Some(Format::Evaluation);
+                    cursor.keyword("h")?;
+                    if !cursor.at_end() && !cursor.skip_space() {
+                        return Err(cursor.error("unexpected character after 'h'"));
+                    }
+                    weight = Some(None);
+                }
+                Some(b'0'..=b'9') if weight.is_none() => {
+                    let format = *format.get_or_insert(Format::Evaluation);
+                    let top = header.filter(|_| format == Format::Classic);
+                    weight = Some(Self::parse_weight(&mut cursor, top, parsed, strict)?);
+                }
+                Some(b'-' | b'0'..=b'9') if weight.is_some() => {
+                    let (lit, column) = cursor.integer()?;
+                    if let Some(lit) = Lit::from_dimacs(lit) {
+                        let var = lit.var().index();
+                        if let Some(header) = header {
+                            if strict >= 1 && var > header.vars {
+                                return Err(Error::Parse(ParseError::new(
+                                    cursor.line(),
+                                    column,
+                                    format!(
+                                        "literal {lit} exceeds maximum variable {}",
+                                        header.vars
+                                    ),
+                                )));
+                            }
+                        }
+                        wcnf.vars = wcnf.vars.max(var);
+                        clause.push(lit);
+                    } else {
+                        let lits = std::mem::take(&mut clause);
+                        match weight.take().flatten() {
+                            Some(weight) => wcnf.soft.push(SoftClause { weight, lits }),
+                            None => wcnf.hard.push(lits),
+                        }
+                        parsed += 1;
+                    }
+                }
+                Some(_) => {
+                    let expected = match (format, weight) {
+                        (_, Some(_)) => "expected literal or comment",
+                        (Some(Format::Classic), None) => "expected weight or comment",
+                        _ => "expected 'h', weight or comment",
+                    };
+                    return Err(cursor.error(expected));
+                }
+            }
+        }
+        if weight.is_some() {
+            return Err(cursor.error("last clause without terminating '0'"));
+        }
+        if let Some(header) = header {
+            if strict >= 1 && parsed < header.clauses {
+                let missing = header.clauses - parsed;
+                return Err(cursor.error(format!("{missing} clauses missing")));
+            }
+            wcnf.vars = wcnf.vars.max(header.vars);
+        }
+        Ok(wcnf)
+    }
+
+    /// The weight starting a clause, 'None' if it is at least 'top' of the
+    /// classic 'header'.
+    fn parse_weight<R: BufRead>(
+        cursor: &mut Cursor<R>,
+        header: Option<Header>,
+        parsed: u64,
+        strict: i32,
+    ) -> Result<Option<u64>, Error> {
+        let (line, column) = (cursor.line(), cursor.column());
+        let weight = cursor.unsigned("weight", u64::MAX)?;
+        if weight == 0 {
+            return Err(Error::Parse(ParseError::new(
+                line,
+                column,
+                "weight must be positive",
+            )));
+        }
+        let Some(header) = header else {
+            return Ok(Some(weight));
+        };
+        if strict >= 1 && parsed == header.clauses {
+            return Err(Error::Parse(ParseError::new(
+                line,
+                column,
+                format!("too many clauses, header specifies {}", header.clauses),
+            )));
+        }
+        Ok(match header.top {
+            Some(top) if weight >= top => None,
+            _ => Some(weight),
+        })
+    }
+
+    fn parse_header<R: BufRead>(cursor: &mut Cursor<R>, strict: i32) -> Result<Header, Error> {
+        let pedantic = strict >= 2;
+        cursor.keyword("p")?;
+        cursor.separator(pedantic)?;
+        cursor.keyword("wcnf")?;
+        cursor.separator(pedantic)?;
+        let max_vars = i32::MAX.unsigned_abs().into();
+        let vars = cursor.unsigned("number of variables", max_vars)?;
+        cursor.separator(pedantic)?;
+        let clauses = cursor.unsigned("number of clauses", u64::MAX)?;
+        let mut top = None;
+        cursor.skip_space();
+        if !cursor.at_end() {
+            top = Some(cursor.unsigned("top weight", u64::MAX)?);
+        }
+        cursor.end_of_header(pedantic)?;
+        Ok(Header {
+            vars: i32::try_from(vars).expect("bounded by 'i32::MAX'"),
+            clauses,
+            top,
+        })
+    }
+
+    /// Adds the hard clauses to 'solver' and reserves all variables.
+    ///
+    ///   require (VALID)
+    ///
+    pub fn add_hard(&self, solver: &mut CaDiCal) {
+        for clause in &self.hard {
+            solver.clause6(clause);
+        }
+        solver.reserve(self.vars);
+    }
+
+    /// The sum of all soft weights, 'None' if it overflows.
+    #[must_use]
+    pub fn total_weight(&self) -> Option<u64> {
+        self.soft
+            .iter()
+            .try_fold(0u64, |sum, soft| sum.checked_add(soft.weight))
+    }
+
+    /// Writes the formula in 'format'.  In the classic format 'top' is one
+    /// more than the sum of all soft weights.
+    ///
+    /// # Errors
+    ///
+    /// '`Error::Io`' if writing fails or if the sum of the weights does not
+    /// fit into 'u64' for the classic format.
+    ///
+    pub fn write(&self, mut writer: impl Write, format: Format) -> Result<(), Error> {
+        for comment in &self.comments {
+            if comment.is_empty() {
+                writeln!(writer, "c")?;
+            } else {
+                writeln!(writer, "c {comment}")?;
+            }
+        }
+        let hard = match format {
+            Format::Classic => {
+                let top = self
+                    .total_weight()
+                    .and_then(|sum| sum.checked_add(1))
+                    .ok_or_else(|| {
+                        io::Error::new(io::ErrorKind::InvalidData, "sum of weights overflows")
+                    })?;
+                let clauses = self.hard.len() + self.soft.len();
+                writeln!(writer, "p wcnf {} {clauses} {top}", self.vars)?;
+                top.to_string()
+            }
+            Format::Evaluation => "h".to_string(),
+        };
+        for clause in &self.hard {
+            write_clause(&mut writer, &hard, clause)?;
+        }
+        for soft in &self.soft {
+            write_clause(&mut writer, &soft.weight.to_string(), &soft.lits)?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+fn write_clause(writer: &mut impl Write, weight: &str, lits: &[Lit]) -> io::Result<()> {
+    write!(writer, "{weight} ")?;
+    for lit in lits {
+        write!(writer, "{lit} ")?;
+    }
+    writeln!(writer, "0")
+}
+
+/// Parses with 'strict' set to '1', the default of `CaDiCaL`.
+impl FromStr for Wcnf {
+    type Err = Error;
+
+    fn from_str(wcnf: &str) -> Result<Self, Error> {
+        Wcnf::parse(wcnf.as_bytes(), 1)
+    }
+}
+
+/// The text of the formula in the format of the `MaxSAT` Evaluations.
+impl fmt::Display for Wcnf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut buffer = Vec::new();
+        self.write(&mut buffer, Format::Evaluation)
+            .map_err(|_| fmt::Error)?;
+        f.write_str(&String::from_utf8_lossy(&buffer))
+    }
+}
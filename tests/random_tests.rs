@@ -1,4 +1,4 @@
-use cadical_sys::{CaDiCal, ClauseIterator, Learner, Status, Terminator, WitnessIterator};
+use cadical_sys::{CaDiCal, ClauseIterator, Learner, Lit, Status, Terminator, WitnessIterator};
 use rand::Rng;
 
 fn get_random_cnf<R: Rng>(rng: &mut R) -> Vec<Vec<i32>> {
@@ -25,8 +25,8 @@ struct CI {
 }
 
 impl ClauseIterator for CI {
-    fn clause(&mut self, clause: &[i32]) -> bool {
-        self.v.push(clause.to_vec());
+    fn clause(&mut self, clause: &[Lit]) -> bool {
+        self.v.push(Lit::slice_to_dimacs(clause).to_vec());
         true
     }
 }
@@ -36,8 +36,12 @@ struct WI {
 }
 
 impl WitnessIterator for WI {
-    fn witness(&mut self, clause: &[i32], witness: &[i32], id: u64) -> bool {
-        self.v.push((clause.to_vec(), witness.to_vec(), id));
+    fn witness(&mut self, clause: &[Lit], witness: &[Lit], id: u64) -> bool {
+        self.v.push((
+            Lit::slice_to_dimacs(clause).to_vec(),
+            Lit::slice_to_dimacs(witness).to_vec(),
+            id,
+        ));
         true
     }
 }
@@ -149,7 +153,7 @@ fn dimacs_solving() {
 
     // Read a DIMACS CNF file
     let result = solver.read_dimacs1(
-        "./tests/problem.cnf".to_string(),
+        "./tests/problem.cnf",
         "my_problem".to_string(),
         &mut var_count,
         0,
@@ -162,7 +166,7 @@ fn dimacs_solving() {
 
     // Write out results or extension
     if status == Status::SATISFIABLE {
-        solver.write_extension("/tmp/solution.ext".to_string());
+        solver.write_extension("/tmp/solution.ext").unwrap();
     }
 }
 
@@ -205,8 +209,8 @@ fn terminator_memory_test() {
     }
 
     impl Learner for L {
-        fn learn(&mut self, lit: i32) {
-            self.v.push(lit);
+        fn learn(&mut self, lit: Option<Lit>) {
+            self.v.push(lit.map_or(0, Lit::to_dimacs));
         }
 
         fn learning(&mut self, size: i32) -> bool {
@@ -231,18 +235,17 @@ fn terminator_memory_test() {
         }
 
         let mut t = Term { v: vec![7; 1000] };
-        let t_ref = &mut t;
-        solver.connect_terminator(t_ref);
-
         let mut l = L { v: Vec::new() };
-        let l_ref = &mut l;
-        solver.connect_learner(l_ref);
 
-        solver.simplify(3);
+        solver.with_terminator(&mut t, |solver| {
+            solver.with_learner(&mut l, |solver| {
+                solver.simplify(3);
 
-        if i % 2 == 0 {
-            solver.disconnect_terminator();
-        }
+                if i % 2 == 0 {
+                    solver.disconnect_terminator();
+                }
+            });
+        });
     }
 }
 
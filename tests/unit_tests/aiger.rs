@@ -0,0 +1,187 @@
+use super::common::parse_error;
+use cadical_sys::{
+    aiger::{Aiger, And, Latch, Symbol, SymbolKind},
+    CaDiCal, Status,
+};
+
+/// A two bit counter whose bad state is reached when both bits are set.
+const COUNTER: &str = "aag 6 0 2 0 4 1\n2 3\n4 11\n12\n6 5 2\n8 4 3\n10 9 7\n12 4 2\n";
+
+#[test]
+fn test_parse_ascii() {
+    let text = "aag 7 2 1 2 4 1 1\n2\n4\n6 13 1\n6\n7\n14\n10\n8 2 4\n10 3 5\n12 9 11\n14 2 6\ni0 enable\nl0 state\nb0 error\nc\nfirst comment\nsecond comment\n";
+    let aiger: Aiger = text.parse().unwrap();
+    assert_eq!(aiger.max_var, 7);
+    assert_eq!(aiger.inputs, [2, 4]);
+    assert_eq!(
+        aiger.latches,
+        [Latch {
+            lit: 6,
+            next: 13,
+            reset: 1
+        }]
+    );
+    assert_eq!(aiger.outputs, [6, 7]);
+    assert_eq!(aiger.bad, [14]);
+    assert_eq!(aiger.constraints, [10]);
+    assert_eq!(
+        aiger.ands[3],
+        And {
+            lhs: 14,
+            rhs0: 2,
+            rhs1: 6
+        }
+    );
+    assert_eq!(
+        aiger.symbols[2],
+        Symbol {
+            kind: SymbolKind::Bad,
+            index: 0,
+            name: "error".to_string()
+        }
+    );
+    assert_eq!(aiger.comments, ["first comment", "second comment"]);
+}
+
+#[test]
+fn test_parse_binary() {
+    let ascii: Aiger = "aag 3 2 0 1 1\n2\n4\n6\n6 4 2\ni1 b\n".parse().unwrap();
+    let binary = Aiger::parse(&b"aig 3 2 0 1 1\n6\n\x02\x02i1 b\n"[..]).unwrap();
+    assert_eq!(binary, ascii);
+
+    let ascii: Aiger = COUNTER.parse().unwrap();
+    let binary =
+        Aiger::parse(&b"aig 6 0 2 0 4 1\n3\n11\n12\n\x01\x03\x04\x01\x01\x02\x08\x02"[..]).unwrap();
+    assert_eq!(binary, ascii);
+
+    // deltas in more than one byte
+    let binary = Aiger::parse(&b"aig 130 129 0 1 1\n260\n\x82\x02\x00"[..]).unwrap();
+    assert_eq!(
+        binary.ands,
+        [And {
+            lhs: 260,
+            rhs0: 2,
+            rhs1: 2
+        }]
+    );
+}
+
+#[test]
+fn test_aiger_errors() {
+    assert_eq!(
+        parse_error(Aiger::parse(&b"aig3 0 0 0 0\n"[..])),
+        (1, 1, "expected 'aag' or 'aig' header".to_string())
+    );
+    assert_eq!(
+        parse_error(Aiger::parse(&b"aag 1 1 0 0 0\n3\n"[..])),
+        (2, 1, "expected positive variable, got 3".to_string())
+    );
+    assert_eq!(
+        parse_error(Aiger::parse(&b"aag 1 2 0 0 0\n2\n2\n"[..])),
+        (3, 1, "variable 1 defined twice".to_string())
+    );
+    assert_eq!(
+        parse_error(Aiger::parse(&b"aag 2 1 0 1 0\n2\n4\n"[..])),
+        (3, 1, "undefined literal 4".to_string())
+    );
+    assert_eq!(
+        parse_error(Aiger::parse(&b"aag 1 1 0 1 0\n2\n6\n"[..])),
+        (3, 1, "literal 6 exceeds maximum variable 1".to_string())
+    );
+    assert_eq!(
+        parse_error(Aiger::parse(&b"aag 2 1 1 0 0\n2\n4 2 3\n"[..])),
+        (3, 5, "invalid reset value".to_string())
+    );
+    assert_eq!(
+        parse_error(Aiger::parse(&b"aig 2 1 0 0 0\n"[..])),
+        (
+            1,
+            5,
+            "maximum variable does not match I + L + A".to_string()
+        )
+    );
+    assert_eq!(
+        parse_error(Aiger::parse(&b"aig 2 1 0 0 1\n\x00\x00"[..])),
+        (2, 2, "invalid delta".to_string())
+    );
+    assert_eq!(
+        parse_error(Aiger::parse(&b"aig 2 1 0 0 1\n\x02"[..])),
+        (2, 2, "unexpected end of file".to_string())
+    );
+    assert_eq!(
+        parse_error(Aiger::parse(&b"aag 1 1 0 0 0\n2\ni1 x\n"[..])),
+        (3, 1, "symbol index out of range".to_string())
+    );
+    assert_eq!(
+        parse_error(Aiger::parse(&b"aag 1 1 0 0 0\n2\nx\n"[..])),
+        (3, 1, "expected symbol or comment".to_string())
+    );
+}
+
+#[test]
+fn test_and_gate_translation() {
+    let aiger: Aiger = "aag 3 2 0 2 1\n2\n4\n6\n7\n6 3 5\n".parse().unwrap();
+    let mut solver = CaDiCal::new();
+    solver.clause1(1);
+    let mapping = aiger.add_to(&mut solver);
+    let [a, b, nor] = [2, 4, 6].map(|lit| mapping.lit(lit).unwrap());
+    assert!(a.var().index() > 1);
+    assert_eq!(mapping.lit(7), Some(!nor));
+    assert_eq!(mapping.lit(8), None);
+
+    for (x, y) in [(false, false), (false, true), (true, false), (true, true)] {
+        solver.assume(if x { a } else { !a });
+        solver.assume(if y { b } else { !b });
+        assert_eq!(solver.solve(), Status::SATISFIABLE);
+        assert_eq!(solver.val(nor) > 0, !x && !y);
+    }
+
+    // constants
+    let constants: Aiger = "aag 0 0 0 2 0\n0\n1\n".parse().unwrap();
+    let mapping = constants.add_to(&mut solver);
+    solver.assume(mapping.lit(1).unwrap());
+    assert_eq!(solver.solve(), Status::SATISFIABLE);
+    solver.assume(mapping.lit(0).unwrap());
+    assert_eq!(solver.solve(), Status::UNSATISFIABLE);
+}
+
+#[test]
+fn test_unroll_counter() {
+    let aiger: Aiger = COUNTER.parse().unwrap();
+    let mut solver = CaDiCal::new();
+    let frames = aiger.unroll(&mut solver, 5);
+    assert_eq!(frames.len(), 5);
+    for (k, frame) in frames.iter().enumerate() {
+        solver.assume(frame.lit(aiger.bad[0]).unwrap());
+        let expected = if k == 3 {
+            Status::SATISFIABLE
+        } else {
+            Status::UNSATISFIABLE
+        };
+        assert_eq!(solver.solve(), expected, "frame {k}");
+    }
+}
+
+#[test]
+fn test_memory_is_bounded_by_content() {
+    // the header alone would ask for billions of variables in every frame
+    let aiger: Aiger = "aag 2147483647 1 1 1 0\n4294967294\n2 3\n2\n"
+        .parse()
+        .unwrap();
+    let mut solver = CaDiCal::new();
+    let frames = aiger.unroll(&mut solver, 1000);
+    assert_eq!(frames.len(), 1000);
+    assert!(frames[999].lit(4_294_967_294).is_some());
+    assert_eq!(frames[0].lit(6), None);
+    assert_eq!(solver.solve(), Status::SATISFIABLE);
+}
+
+#[test]
+fn test_constraints_restrict_inputs() {
+    // the output equals the only input which is constrained to be false
+    let aiger: Aiger = "aag 1 1 0 1 0 0 1\n2\n2\n3\n".parse().unwrap();
+    let mut solver = CaDiCal::new();
+    let mapping = aiger.add_to(&mut solver);
+    solver.assume(mapping.lit(aiger.outputs[0]).unwrap());
+    assert_eq!(solver.solve(), Status::UNSATISFIABLE);
+}
@@ -0,0 +1,193 @@
+use cadical_sys::{
+    proof::{FileTracer, ProofFormat, StatTracer},
+    CaDiCal, Configured, Error, Lit, ProofTracer, SolverBuilder, State, Status, Terminator,
+};
+use std::sync::{Arc, Mutex};
+
+struct OriginalTracer {
+    original: Arc<Mutex<Vec<Vec<i32>>>>,
+}
+
+impl ProofTracer for OriginalTracer {
+    fn add_original_clause(&mut self, _: u64, _: bool, clause: &[Lit], _: bool) {
+        self.original
+            .lock()
+            .unwrap()
+            .push(Lit::slice_to_dimacs(clause).to_vec());
+    }
+    fn add_derived_clause(&mut self, _: u64, _: bool, _: &[Lit], _: &[u64]) {}
+    fn delete_clause(&mut self, _: u64, _: bool, _: &[Lit]) {}
+    fn weaken_minus(&mut self, _: u64, _: &[Lit]) {}
+    fn strengthen(&mut self, _: u64) {}
+    fn finalize_clause(&mut self, _: u64, _: &[Lit]) {}
+    fn add_assumption(&mut self, _: Lit) {}
+    fn add_constraint(&mut self, _: &[Lit]) {}
+    fn reset_assumptions(&mut self) {}
+    fn add_assumption_clause(&mut self, _: u64, _: &[Lit], _: &[u64]) {}
+    fn conclude_sat(&mut self, _: i32, _: &[Lit]) {}
+    fn conclude_unsat(&mut self, _: i32, _: &[u64]) {}
+    fn conclude_unknown(&mut self, _: &[Lit]) {}
+}
+
+#[test]
+fn test_builder_produces_steady_solver() {
+    let mut solver = CaDiCal::builder()
+        .configure("sat".to_string())
+        .set("verbose".to_string(), 0)
+        .long_option("--no-elim".to_string())
+        .prefix("test: ".to_string())
+        .build()
+        .unwrap();
+    assert_eq!(solver.state(), State::STEADY);
+    assert_eq!(solver.get("elim".to_string()), 0);
+    assert_eq!(solver.vars(), 0);
+
+    solver.clause2(1, 2);
+    solver.clause1(-1);
+    assert_eq!(solver.solve(), Status::SATISFIABLE);
+    assert_eq!(solver.val(2), 2);
+}
+
+#[test]
+fn test_builder_applies_settings_in_order() {
+    let mut solver = SolverBuilder::new()
+        .set("elim".to_string(), 0)
+        .configure("plain".to_string())
+        .set("probe".to_string(), 1)
+        .build()
+        .unwrap();
+    // 'plain' disables elimination and probing, the later 'set' wins
+    assert_eq!(solver.get("elim".to_string()), 0);
+    assert_eq!(solver.get("probe".to_string()), 1);
+}
+
+#[test]
+fn test_builder_reports_invalid_settings() {
+    let result = SolverBuilder::new()
+        .set("no-such-option".to_string(), 1)
+        .build();
+    assert!(matches!(result, Err(Error::InvalidOption(name)) if name == "no-such-option"));
+
+    let result = SolverBuilder::new()
+        .configure("no-such-configuration".to_string())
+        .build();
+    assert!(matches!(result, Err(Error::InvalidConfiguration(_))));
+
+    let result = SolverBuilder::new()
+        .long_option("--no-such-option".to_string())
+        .build();
+    assert!(matches!(result, Err(Error::InvalidOption(_))));
+
+    let result = SolverBuilder::new()
+        .trace_proof("/does/not/exist/proof.drat")
+        .build();
+    assert!(matches!(result, Err(Error::Open { .. })));
+}
+
+#[test]
+fn test_builder_connects_proof_tracer_before_clauses() {
+    let original = Arc::new(Mutex::new(Vec::new()));
+    let tracer = OriginalTracer {
+        original: Arc::clone(&original),
+    };
+    let mut solver = SolverBuilder::new()
+        .proof_tracer(Box::new(tracer), true, false)
+        .build()
+        .unwrap();
+    solver.clause2(1, 2);
+    solver.clause2(-1, 2);
+    solver.clause1(-2);
+    assert_eq!(solver.solve(), Status::UNSATISFIABLE);
+
+    assert_eq!(original.lock().unwrap().len(), 3);
+    let [id] = solver.proof_tracers()[..] else {
+        panic!("expected one tracer");
+    };
+    assert!(solver.disconnect_proof_tracer1(id).is_some());
+}
+
+#[test]
+fn test_builder_writes_complete_proof() {
+    let path = std::env::temp_dir().join("cadical_sys_builder_proof.drat");
+    let mut solver = SolverBuilder::new().trace_proof(&path).build().unwrap();
+    solver.clause1(1);
+    solver.clause1(-1);
+    assert_eq!(solver.solve(), Status::UNSATISFIABLE);
+    solver.close_proof_trace(false);
+
+    let proof = std::fs::read(&path).unwrap();
+    assert!(!proof.is_empty(), "proof should contain the empty clause");
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_builder_connects_all_tracers_in_order() {
+    let path = std::env::temp_dir().join("cadical_sys_builder_tracers.lrat");
+    let original = Arc::new(Mutex::new(Vec::new()));
+    let tracer = OriginalTracer {
+        original: Arc::clone(&original),
+    };
+    let mut solver = SolverBuilder::new()
+        .stat_tracer(StatTracer::new(), false)
+        .file_tracer(FileTracer::new(&path, ProofFormat::Lrat, false).unwrap())
+        .proof_tracer(Box::new(tracer), false, false)
+        .build()
+        .unwrap();
+    solver.clause2(1, 2);
+    solver.clause2(-1, 2);
+    solver.clause1(-2);
+    assert_eq!(solver.solve(), Status::UNSATISFIABLE);
+
+    let [stat, file, rust] = solver.proof_tracers()[..] else {
+        panic!("expected three tracers");
+    };
+    assert_eq!(solver.proof_statistics(stat).unwrap().original, 3);
+    assert_eq!(solver.proof_statistics(file).unwrap().original, 3);
+    let tracer = solver.disconnect_proof_tracer1(rust).unwrap();
+    let tracer = tracer.downcast::<OriginalTracer>().ok().unwrap();
+    assert_eq!(tracer.original.lock().unwrap().len(), 3);
+    drop(solver.disconnect_file_tracer(file).unwrap());
+    assert!(!std::fs::read(&path).unwrap().is_empty());
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_builder_traces_named_proof() {
+    let path = std::env::temp_dir().join("cadical_sys_builder_named.drat");
+    let mut solver = SolverBuilder::new()
+        .trace_proof1(&path, "named proof".to_string())
+        .build()
+        .unwrap();
+    solver.clause1(1);
+    solver.clause1(-1);
+    assert_eq!(solver.solve(), Status::UNSATISFIABLE);
+    solver.close_proof_trace(false);
+    assert!(!std::fs::read(&path).unwrap().is_empty());
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_configured_solver_lends_itself() {
+    struct Never;
+
+    impl Terminator for Never {
+        fn terminated(&mut self) -> bool {
+            false
+        }
+    }
+
+    let mut solver = SolverBuilder::new().build().unwrap();
+    let mut terminator = Never;
+    // the scoped solver is 'Configured' as well, so it cannot be configured
+    let status = solver.with_terminator(&mut terminator, |solver: &mut Configured| {
+        solver.clause2(1, 2);
+        solver.solve()
+    });
+    assert_eq!(status, Status::SATISFIABLE);
+
+    // helpers taking a 'CaDiCal' are reached by unwrapping it
+    let mut solver: CaDiCal = solver.into_inner();
+    assert_eq!(solver.state(), State::SATISFIED);
+    solver.clause1(-1);
+    assert_eq!(solver.solve(), Status::SATISFIABLE);
+}
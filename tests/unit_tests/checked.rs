@@ -0,0 +1,127 @@
+use cadical_sys::{CaDiCal, Error, State, Status};
+
+#[test]
+fn test_checked_happy_path() {
+    let mut solver = CaDiCal::new();
+    let mut checked = solver.checked();
+    checked.set("verbose".to_string(), 0).unwrap();
+    checked.clause(&[1, 2]).unwrap();
+    checked.clause(&[-1, 2]).unwrap();
+    checked.assume(-2).unwrap();
+    assert_eq!(checked.solve().unwrap(), Status::UNSATISFIABLE);
+    assert!(checked.failed(-2).unwrap());
+    assert_eq!(checked.solve().unwrap(), Status::SATISFIABLE);
+    assert_eq!(checked.val(2).unwrap(), 2);
+}
+
+#[test]
+fn test_val_before_solve_is_contract_violation() {
+    let mut solver = CaDiCal::new();
+    solver.clause2(1, 2);
+    let error = solver.checked().val(1).unwrap_err();
+    assert!(matches!(
+        error,
+        Error::ContractViolation {
+            function: "val",
+            required: State::SATISFIED,
+            actual: State::STEADY,
+        }
+    ));
+    assert_eq!(
+        error.to_string(),
+        "'val' requires state SATISFIED but the solver is STEADY"
+    );
+}
+
+#[test]
+fn test_configuration_after_clauses_is_contract_violation() {
+    let mut solver = CaDiCal::new();
+    solver.clause2(1, 2);
+    let mut checked = solver.checked();
+    assert!(matches!(
+        checked.set("verbose".to_string(), 1),
+        Err(Error::ContractViolation {
+            required: State::CONFIGURING,
+            ..
+        })
+    ));
+    assert!(matches!(
+        checked.trace_proof2("/tmp/unused.drat"),
+        Err(Error::ContractViolation {
+            function: "trace_proof",
+            ..
+        })
+    ));
+}
+
+#[test]
+fn test_failed_after_sat_is_contract_violation() {
+    let mut solver = CaDiCal::new();
+    let mut checked = solver.checked();
+    checked.clause(&[1]).unwrap();
+    assert_eq!(checked.solve().unwrap(), Status::SATISFIABLE);
+    assert!(matches!(
+        checked.failed(1),
+        Err(Error::ContractViolation {
+            required: State::UNSATISFIED,
+            actual: State::SATISFIED,
+            ..
+        })
+    ));
+    assert!(matches!(
+        checked.constraint_failed(),
+        Err(Error::ContractViolation { .. })
+    ));
+}
+
+#[test]
+fn test_solve_with_unterminated_clause_is_contract_violation() {
+    let mut solver = CaDiCal::new();
+    let mut checked = solver.checked();
+    checked.add(1).unwrap();
+    assert!(matches!(
+        checked.solve(),
+        Err(Error::ContractViolation {
+            required: State::READY,
+            actual: State::ADDING,
+            ..
+        })
+    ));
+    checked.add(0).unwrap();
+    assert_eq!(checked.solve().unwrap(), Status::SATISFIABLE);
+}
+
+#[test]
+fn test_invalid_literals_are_rejected() {
+    let mut solver = CaDiCal::new();
+    let mut checked = solver.checked();
+    assert!(matches!(
+        checked.assume(0),
+        Err(Error::InvalidLiteral {
+            function: "assume",
+            lit: 0
+        })
+    ));
+    assert!(matches!(
+        checked.add(i32::MIN),
+        Err(Error::InvalidLiteral { .. })
+    ));
+    assert!(matches!(
+        checked.clause(&[1, 0, 2]),
+        Err(Error::InvalidLiteral { lit: 0, .. })
+    ));
+    // nothing of the rejected clause was added
+    assert_eq!(checked.solve().unwrap(), Status::SATISFIABLE);
+}
+
+#[test]
+fn test_melt_requires_frozen_literal() {
+    let mut solver = CaDiCal::new();
+    let mut checked = solver.checked();
+    checked.clause(&[1, 2]).unwrap();
+    assert!(matches!(checked.melt(1), Err(Error::NotFrozen(1))));
+    checked.freeze(1).unwrap();
+    assert!(checked.frozen(1).unwrap());
+    checked.melt(1).unwrap();
+    assert!(!checked.frozen(1).unwrap());
+}
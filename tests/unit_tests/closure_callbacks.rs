@@ -0,0 +1,62 @@
+use super::common::add_pigeonhole_cnf;
+use cadical_sys::{CaDiCal, Status};
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc, Mutex,
+};
+
+#[test]
+fn test_terminator_fn_stops_solve() {
+    let mut solver = CaDiCal::new();
+    let calls = Arc::new(AtomicUsize::new(0));
+    let counter = Arc::clone(&calls);
+    solver.connect_terminator_fn(move || counter.fetch_add(1, Ordering::Relaxed) >= 10);
+    add_pigeonhole_cnf(&mut solver, 7);
+
+    assert_eq!(solver.solve(), Status::UNKNOWN);
+    assert!(calls.load(Ordering::Relaxed) > 10);
+}
+
+#[test]
+fn test_terminator_fn_disconnect() {
+    let mut solver = CaDiCal::new();
+    solver.connect_terminator_fn(|| true);
+    add_pigeonhole_cnf(&mut solver, 5);
+
+    assert!(solver.disconnect_terminator().is_some());
+    assert_eq!(solver.solve(), Status::UNSATISFIABLE);
+}
+
+#[test]
+fn test_learner_fn_receives_whole_clauses() {
+    let mut solver = CaDiCal::new();
+    let learned = Arc::new(Mutex::new(Vec::new()));
+    let sink = Arc::clone(&learned);
+    solver.connect_learner_fn(3, move |clause| sink.lock().unwrap().push(clause.to_vec()));
+    add_pigeonhole_cnf(&mut solver, 5);
+
+    assert_eq!(solver.solve(), Status::UNSATISFIABLE);
+    let learned = learned.lock().unwrap();
+    assert!(!learned.is_empty(), "Learner should receive clauses");
+    for clause in learned.iter() {
+        assert!(
+            clause.len() <= 3,
+            "Clause {clause:?} exceeds the size limit"
+        );
+    }
+}
+
+#[test]
+fn test_fixed_listener_fn_receives_units() {
+    let mut solver = CaDiCal::new();
+    let fixed = Arc::new(Mutex::new(Vec::new()));
+    let sink = Arc::clone(&fixed);
+    solver.connect_fixed_listener_fn(move |lit| sink.lock().unwrap().push(lit.to_dimacs()));
+    solver.clause6(&[1]);
+    solver.clause6(&[-2]);
+
+    assert_eq!(solver.solve(), Status::SATISFIABLE);
+    let mut fixed = fixed.lock().unwrap().clone();
+    fixed.sort_unstable();
+    assert_eq!(fixed, vec![-2, 1]);
+}
@@ -0,0 +1,43 @@
+//! Fixtures shared by the unit tests.
+
+use cadical_sys::{dimacs::Cnf, CaDiCal, Error, Lit};
+use std::fmt::Debug;
+
+pub fn lits(lits: &[i32]) -> Vec<Lit> {
+    Lit::slice_from_dimacs(lits).unwrap().to_vec()
+}
+
+/// The position and message of the parse error of 'result'.
+pub fn parse_error<T: Debug>(result: Result<T, Error>) -> (usize, usize, String) {
+    match result {
+        Err(Error::Parse(error)) => (error.line(), error.column(), error.message().to_string()),
+        result => panic!("expected parse error, got {result:?}"),
+    }
+}
+
+/// The pigeonhole formula for `holes + 1` pigeons, where pigeon 'p' sits in
+/// hole 'h' if 'p * holes + h + 1' is true.  It needs search for more than a
+/// few holes.
+pub fn pigeonhole(holes: i32) -> Cnf {
+    let var = |pigeon: i32, hole: i32| pigeon * holes + hole + 1;
+    let mut cnf = Cnf {
+        vars: holes * (holes + 1),
+        ..Cnf::default()
+    };
+    for pigeon in 0..=holes {
+        let clause: Vec<i32> = (0..holes).map(|hole| var(pigeon, hole)).collect();
+        cnf.clauses.push(lits(&clause));
+    }
+    for hole in 0..holes {
+        for p1 in 0..=holes {
+            for p2 in (p1 + 1)..=holes {
+                cnf.clauses.push(lits(&[-var(p1, hole), -var(p2, hole)]));
+            }
+        }
+    }
+    cnf
+}
+
+pub fn add_pigeonhole_cnf(solver: &mut CaDiCal, holes: i32) {
+    pigeonhole(holes).add_to(solver);
+}
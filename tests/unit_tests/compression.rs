@@ -0,0 +1,200 @@
+use cadical_sys::{CaDiCal, Compression, Error, Status};
+use std::{fs, path::PathBuf};
+
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("cadical_sys_compression_{name}"))
+}
+
+#[test]
+fn test_plain_files_without_codec() {
+    assert_eq!(
+        Compression::from_path(&temp_path("formula.cnf")),
+        Compression::None
+    );
+    let path = temp_path("explicit_plain.cnf");
+    let mut solver = CaDiCal::new();
+    solver.clause2(1, -2);
+    solver
+        .write_dimacs_compressed(&path, Compression::None, 0)
+        .unwrap();
+    assert!(fs::read_to_string(&path).unwrap().starts_with("p cnf 2 1"));
+
+    let mut copy = CaDiCal::new();
+    let mut vars = 0;
+    copy.read_dimacs_compressed(&path, Compression::None, &mut vars, 1)
+        .unwrap();
+    assert_eq!(vars, 2);
+    assert_eq!(copy.solve(), Status::SATISFIABLE);
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_closing_proof_reports_errors() {
+    let path = temp_path("closed.drat");
+    let mut solver = CaDiCal::new();
+    solver
+        .trace_proof_compressed(&path, Compression::None)
+        .unwrap();
+    solver.clause1(1);
+    solver.clause1(-1);
+    assert_eq!(solver.solve(), Status::UNSATISFIABLE);
+    solver.try_close_proof_trace(false).unwrap();
+    assert!(!fs::read(&path).unwrap().is_empty());
+    fs::remove_file(path).unwrap();
+}
+
+/// Writes to '/dev/full' fail, so the encoder cannot finish the proof.
+#[cfg(target_os = "linux")]
+#[test]
+fn test_closing_proof_reports_failed_finish() {
+    let mut solver = CaDiCal::new();
+    solver
+        .trace_proof_compressed("/dev/full", Compression::None)
+        .unwrap();
+    solver.clause1(1);
+    solver.clause1(-1);
+    assert_eq!(solver.solve(), Status::UNSATISFIABLE);
+    assert!(matches!(
+        solver.try_close_proof_trace(false),
+        Err(Error::Io(_))
+    ));
+    // the file is closed only once
+    assert!(solver.try_close_proof_trace(false).is_ok());
+}
+
+#[cfg(feature = "gzip")]
+#[test]
+fn test_gzip_formula_round_trip() {
+    let path = temp_path("formula.cnf.gz");
+    assert_eq!(Compression::from_path(&path), Compression::Gzip);
+    let mut solver = CaDiCal::new();
+    solver.clause3(1, -2, 3);
+    solver.clause1(-1);
+    solver.write_dimacs(&path, 0).unwrap();
+    // gzip magic number
+    assert_eq!(fs::read(&path).unwrap()[..2], [0x1f, 0x8b]);
+
+    let mut copy = CaDiCal::new();
+    let mut vars = 0;
+    copy.read_dimacs2(&path, &mut vars, 1).unwrap();
+    assert_eq!(vars, 3);
+    assert_eq!(copy.solve(), Status::SATISFIABLE);
+    assert_eq!(copy.val(1), -1);
+    fs::remove_file(path).unwrap();
+}
+
+#[cfg(feature = "gzip")]
+#[test]
+fn test_gzip_proof_is_complete_after_drop() {
+    let path = temp_path("proof.drat.gz");
+    let mut solver = CaDiCal::new();
+    solver.try_set("binary".to_string(), 0).unwrap();
+    solver.try_trace_proof2(&path).unwrap();
+    solver.clause2(1, 2);
+    solver.clause2(-1, 2);
+    solver.clause2(1, -2);
+    solver.clause2(-1, -2);
+    assert_eq!(solver.solve(), Status::UNSATISFIABLE);
+    // the compressed stream ends when the solver closes the file
+    drop(solver);
+
+    let decoder = Compression::Gzip
+        .decoder(fs::File::open(&path).unwrap())
+        .unwrap();
+    let proof = std::io::read_to_string(decoder).unwrap();
+    assert!(
+        proof.lines().any(|line| line == "0"),
+        "missing empty clause"
+    );
+    fs::remove_file(path).unwrap();
+}
+
+#[cfg(feature = "gzip")]
+#[test]
+fn test_gzip_file_tracer() {
+    use cadical_sys::proof::{FileTracer, ProofFormat};
+
+    let path = temp_path("tracer.lrat.gz");
+    let mut solver = CaDiCal::new();
+    let tracer = FileTracer::new(&path, ProofFormat::Lrat, false).unwrap();
+    let id = solver.connect_file_tracer(tracer);
+    solver.clause2(1, 2);
+    solver.clause2(-1, 2);
+    solver.clause2(1, -2);
+    solver.clause2(-1, -2);
+    assert_eq!(solver.solve(), Status::UNSATISFIABLE);
+    solver.disconnect_file_tracer(id).unwrap().finish().unwrap();
+
+    let decoder = Compression::Gzip
+        .decoder(fs::File::open(&path).unwrap())
+        .unwrap();
+    let proof = std::io::read_to_string(decoder).unwrap();
+    assert!(
+        proof
+            .lines()
+            .any(|line| line.split(' ').nth(1) == Some("0")),
+        "missing empty clause"
+    );
+    fs::remove_file(path).unwrap();
+}
+
+#[cfg(feature = "xz")]
+#[test]
+fn test_xz_extension_round_trip() {
+    let path = temp_path("solution.ext.xz");
+    let mut solver = CaDiCal::new();
+    solver.clause2(1, 2);
+    solver.clause2(-1, 3);
+    assert_eq!(solver.solve(), Status::SATISFIABLE);
+    solver.write_extension(&path).unwrap();
+
+    let plain = temp_path("solution.ext");
+    solver.write_extension(&plain).unwrap();
+    let mut extension = Vec::new();
+    let mut decoder = Compression::Xz
+        .decoder(fs::File::open(&path).unwrap())
+        .unwrap();
+    std::io::copy(&mut decoder, &mut extension).unwrap();
+    assert_eq!(extension, fs::read(&plain).unwrap());
+    fs::remove_file(path).unwrap();
+    fs::remove_file(plain).unwrap();
+}
+
+#[cfg(feature = "bzip2")]
+#[test]
+fn test_bzip2_inccnf() {
+    let path = temp_path("cubes.icnf.bz2");
+    let mut encoder = Compression::Bzip2
+        .encoder(fs::File::create(&path).unwrap())
+        .unwrap();
+    std::io::Write::write_all(&mut encoder, b"p inccnf\n1 2 0\na -1 0\n").unwrap();
+    encoder.finish().unwrap();
+
+    let mut solver = CaDiCal::new();
+    let mut vars = 0;
+    let mut incremental = false;
+    let mut cubes = Vec::new();
+    solver
+        .read_dimacs4(&path, &mut vars, 1, &mut incremental, &mut cubes)
+        .unwrap();
+    assert!(incremental);
+    assert_eq!(cubes, [-1, 0]);
+    fs::remove_file(path).unwrap();
+}
+
+#[cfg(feature = "zstd")]
+#[test]
+fn test_zstd_explicit_codec() {
+    let path = temp_path("formula.unusual");
+    let mut solver = CaDiCal::new();
+    solver.clause2(-1, -2);
+    solver
+        .write_dimacs_compressed(&path, Compression::Zstd, 4)
+        .unwrap();
+    let mut copy = CaDiCal::new();
+    let mut vars = 0;
+    copy.read_dimacs_compressed(&path, Compression::Zstd, &mut vars, 1)
+        .unwrap();
+    assert_eq!(vars, 4);
+    fs::remove_file(path).unwrap();
+}
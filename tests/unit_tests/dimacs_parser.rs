@@ -0,0 +1,158 @@
+use super::common::{lits, parse_error};
+use cadical_sys::{
+    dimacs::{Cnf, Header, Item, Parser},
+    CaDiCal, Status,
+};
+
+#[test]
+fn test_parser_streams_items_in_order() {
+    let dimacs = "c first\np cnf 3 2\n1 -2\n 3 0\nc between\n-3 0\n";
+    let items: Vec<Item> = Parser::new(dimacs.as_bytes(), 1)
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(
+        items,
+        vec![
+            Item::Comment("first".to_string()),
+            Item::Header(Header {
+                vars: 3,
+                clauses: 2
+            }),
+            Item::Clause(lits(&[1, -2, 3])),
+            Item::Comment("between".to_string()),
+            Item::Clause(lits(&[-3])),
+        ]
+    );
+}
+
+#[test]
+fn test_cnf_round_trip() {
+    let dimacs = "c generated\nc\np cnf 4 3\n1 -2 0\n0\n4 3 -1 0\n";
+    let cnf: Cnf = dimacs.parse().unwrap();
+    assert_eq!(cnf.vars, 4);
+    assert_eq!(cnf.comments, ["generated", ""]);
+    assert_eq!(cnf.clauses, [lits(&[1, -2]), vec![], lits(&[4, 3, -1])]);
+    assert_eq!(cnf.to_string(), dimacs);
+
+    let mut written = Vec::new();
+    cnf.write(&mut written).unwrap();
+    assert_eq!(Cnf::parse(written.as_slice(), 2).unwrap(), cnf);
+}
+
+#[test]
+fn test_errors_have_line_and_column() {
+    assert_eq!(
+        parse_error(Cnf::parse("p cnf 2 1\n1 x 0\n".as_bytes(), 1)),
+        (2, 3, "expected literal or comment".to_string())
+    );
+    assert_eq!(
+        parse_error(Cnf::parse("p cnf 2 1\n1 -0\n".as_bytes(), 1)),
+        (2, 4, "expected non-zero digit after '-'".to_string())
+    );
+    assert_eq!(
+        parse_error(Cnf::parse("p cnf 2 1\n  1 99999999999 0\n".as_bytes(), 0)),
+        (2, 5, "literal too large".to_string())
+    );
+    assert_eq!(
+        parse_error(Cnf::parse("p cnf 2 1\n1 2a 0\n".as_bytes(), 1)),
+        (2, 4, "unexpected character after literal".to_string())
+    );
+    assert_eq!(
+        parse_error(Cnf::parse("1 2 0\n".as_bytes(), 1)),
+        (1, 1, "expected 'c' or 'p cnf' header".to_string())
+    );
+    assert_eq!(
+        parse_error(Cnf::parse("c only comments\n".as_bytes(), 1)),
+        (1, 16, "missing 'p cnf' header".to_string())
+    );
+    assert_eq!(
+        parse_error(Cnf::parse("p cnf 2 1\n1 2\n".as_bytes(), 0)),
+        (2, 4, "last clause without terminating '0'".to_string())
+    );
+}
+
+#[test]
+fn test_strict_levels() {
+    // too many variables and clauses are ignored with strict zero
+    let dimacs = "p cnf 1 1\n1 2 0\n-2 0\n";
+    let cnf = Cnf::parse(dimacs.as_bytes(), 0).unwrap();
+    assert_eq!(cnf.vars, 2);
+    assert_eq!(cnf.clauses.len(), 2);
+
+    assert_eq!(
+        parse_error(Cnf::parse(dimacs.as_bytes(), 1)),
+        (2, 3, "literal 2 exceeds maximum variable 1".to_string())
+    );
+    assert_eq!(
+        parse_error(Cnf::parse("p cnf 2 1\n1 2 0\n-2 0\n".as_bytes(), 1)),
+        (3, 1, "too many clauses, header specifies 1".to_string())
+    );
+    assert_eq!(
+        parse_error(Cnf::parse("p cnf 2 3\n1 2 0\n".as_bytes(), 1)),
+        (2, 6, "2 clauses missing".to_string())
+    );
+
+    // relaxed headers only with strict below two
+    let relaxed = "\np  cnf\t2 1 \n1 2 0\n";
+    assert!(Cnf::parse(relaxed.as_bytes(), 1).is_ok());
+    assert_eq!(
+        parse_error(Cnf::parse(relaxed.as_bytes(), 2)),
+        (1, 1, "expected 'c' or 'p cnf' header".to_string())
+    );
+    assert_eq!(
+        parse_error(Cnf::parse("p  cnf 2 1\n1 2 0\n".as_bytes(), 2)),
+        (1, 3, "expected single space in header".to_string())
+    );
+    assert_eq!(
+        parse_error(Cnf::parse("p cnf 2 1 \n1 2 0\n".as_bytes(), 2)),
+        (1, 10, "trailing white space after header".to_string())
+    );
+}
+
+#[test]
+fn test_parse_error_display() {
+    let error = "p cnf 1 1\n2 0\n".parse::<Cnf>().unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "parse error at line 2 column 1: literal 2 exceeds maximum variable 1"
+    );
+}
+
+#[test]
+fn test_parser_feeds_solver() {
+    let mut solver = CaDiCal::new();
+    let header = Parser::new("p cnf 5 2\n1 2 0\n-1 0\n".as_bytes(), 1)
+        .feed(&mut solver)
+        .unwrap();
+    assert_eq!(
+        header,
+        Header {
+            vars: 5,
+            clauses: 2
+        }
+    );
+    assert_eq!(solver.vars(), 5);
+    assert_eq!(solver.solve(), Status::SATISFIABLE);
+    assert_eq!(solver.val(2), 2);
+}
+
+#[test]
+fn test_cnf_matches_solver_parser() {
+    let cnf = Cnf::parse(
+        std::fs::File::open("./tests/problem.cnf")
+            .map(std::io::BufReader::new)
+            .unwrap(),
+        1,
+    )
+    .unwrap();
+    let mut solver = CaDiCal::new();
+    let mut vars = 0;
+    solver
+        .read_dimacs2("./tests/problem.cnf", &mut vars, 1)
+        .unwrap();
+    assert_eq!(cnf.vars, vars);
+
+    let mut copy = CaDiCal::new();
+    cnf.add_to(&mut copy);
+    assert_eq!(copy.solve(), solver.solve());
+}
@@ -0,0 +1,165 @@
+use cadical_sys::{CaDiCal, Error, Status};
+use std::io::{self, Cursor, Write};
+
+/// Fails every write, as a closed socket would.
+struct BrokenPipe;
+
+impl Write for BrokenPipe {
+    fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+        Err(io::ErrorKind::BrokenPipe.into())
+    }
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn test_read_dimacs_str() {
+    let mut solver = CaDiCal::new();
+    let mut vars = 0;
+    solver
+        .read_dimacs_str("p cnf 3 2\n1 2 -3 0\n-2 3 0\n", &mut vars, 1)
+        .unwrap();
+    assert_eq!(vars, 3);
+    solver.assume(-1);
+    solver.assume(-3);
+    assert_eq!(solver.solve(), Status::UNSATISFIABLE);
+}
+
+#[test]
+fn test_read_dimacs_from_reader() {
+    let file = std::fs::File::open("./tests/problem.cnf").unwrap();
+    let mut solver = CaDiCal::new();
+    let mut vars = 0;
+    solver.read_dimacs_from(file, &mut vars, 1).unwrap();
+    assert_eq!(vars, 3);
+    assert_eq!(solver.solve(), Status::SATISFIABLE);
+}
+
+#[test]
+fn test_read_dimacs_str_reports_parse_errors() {
+    let mut solver = CaDiCal::new();
+    let mut vars = 0;
+    let result = solver.read_dimacs_str("p cnf 2 1\n1 x 0\n", &mut vars, 1);
+    assert!(
+        matches!(&result, Err(Error::Solver(message)) if message.contains("<string>")),
+        "unexpected result {result:?}"
+    );
+
+    // strict headers count their clauses
+    let mut solver = CaDiCal::new();
+    let result = solver.read_dimacs_str("p cnf 2 2\n1 2 0\n", &mut vars, 1);
+    assert!(matches!(result, Err(Error::Solver(_))));
+
+    let mut solver = CaDiCal::new();
+    assert!(solver.read_dimacs_str("", &mut vars, 1).is_err());
+}
+
+#[test]
+fn test_read_inccnf_collects_cubes() {
+    let mut solver = CaDiCal::new();
+    let mut vars = 0;
+    let mut incremental = false;
+    let mut cubes = Vec::new();
+    solver
+        .read_inccnf_from(
+            Cursor::new("p inccnf\n1 2 0\na -1 0\na -2 0\n"),
+            &mut vars,
+            1,
+            &mut incremental,
+            &mut cubes,
+        )
+        .unwrap();
+    assert!(incremental);
+    assert_eq!(cubes, vec![-1, 0, -2, 0]);
+
+    let mut solver = CaDiCal::new();
+    let mut incremental = false;
+    let mut cubes = Vec::new();
+    solver
+        .read_inccnf_str(
+            "p cnf 2 1\n1 2 0\n",
+            &mut vars,
+            1,
+            &mut incremental,
+            &mut cubes,
+        )
+        .unwrap();
+    assert!(!incremental);
+    assert!(cubes.is_empty());
+}
+
+#[test]
+fn test_write_dimacs_to_round_trips() {
+    let mut solver = CaDiCal::new();
+    solver.clause2(1, -2);
+    solver.clause3(2, 3, -4);
+    let mut output = Vec::new();
+    solver.write_dimacs_to(&mut output, 0).unwrap();
+    let dimacs = String::from_utf8(output).unwrap();
+    assert!(
+        dimacs.starts_with("p cnf 4 2\n"),
+        "unexpected header in {dimacs}"
+    );
+    assert!(dimacs.contains("1 -2 0\n"));
+    assert!(dimacs.contains("2 3 -4 0\n"));
+
+    let mut copy = CaDiCal::new();
+    let mut vars = 0;
+    copy.read_dimacs_str(&dimacs, &mut vars, 1).unwrap();
+    assert_eq!(vars, 4);
+    copy.assume(-1);
+    copy.assume(2);
+    assert_eq!(copy.solve(), Status::UNSATISFIABLE);
+}
+
+/// Counts the writes reaching it, each of which would be a system call on a
+/// socket or file.
+#[derive(Default)]
+struct CountingWriter {
+    writes: usize,
+    bytes: Vec<u8>,
+}
+
+impl Write for CountingWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.writes += 1;
+        self.bytes.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn test_write_dimacs_to_buffers_writes() {
+    let mut solver = CaDiCal::new();
+    for lit in 1..=100 {
+        solver.clause2(lit, -(lit + 1));
+    }
+    let mut output = CountingWriter::default();
+    solver.write_dimacs_to(&mut output, 0).unwrap();
+    assert!(output.bytes.starts_with(b"p cnf 101 100\n"));
+    assert_eq!(output.writes, 1);
+}
+
+#[test]
+fn test_write_dimacs_to_respects_min_max_var() {
+    let mut solver = CaDiCal::new();
+    solver.clause1(1);
+    let mut output = Vec::new();
+    solver.write_dimacs_to(&mut output, 7).unwrap();
+    assert!(output.starts_with(b"p cnf 7 1\n"));
+}
+
+#[test]
+fn test_write_dimacs_to_reports_write_errors() {
+    let mut solver = CaDiCal::new();
+    solver.clause2(1, 2);
+    let result = solver.write_dimacs_to(BrokenPipe, 0);
+    assert!(
+        matches!(&result, Err(Error::Io(error)) if error.kind() == io::ErrorKind::BrokenPipe),
+        "unexpected result {result:?}"
+    );
+}
@@ -0,0 +1,284 @@
+use super::common::{lits, pigeonhole};
+use cadical_sys::{
+    dimacs::Cnf,
+    proof::{
+        drat::{self, Proof, Step},
+        DratWriter, LratChecker,
+    },
+    CaDiCal, Error, Lit, ProofTracer, Status,
+};
+use std::{collections::HashMap, fs};
+
+/// All four clauses over '1' and '2'.
+fn square() -> Cnf {
+    "p cnf 2 4\n1 2 0\n1 -2 0\n-1 2 0\n-1 -2 0\n"
+        .parse()
+        .unwrap()
+}
+
+/// All eight clauses over '1', '2' and '3'.
+fn cube() -> Cnf {
+    let mut cnf = Cnf {
+        vars: 3,
+        ..Cnf::default()
+    };
+    for signs in 0..8 {
+        let sign = |bit: i32, var: i32| if signs & bit == 0 { var } else { -var };
+        cnf.clauses
+            .push(lits(&[sign(4, 1), sign(2, 2), sign(1, 3)]));
+    }
+    cnf
+}
+
+fn lrat(refutation: &drat::Refutation) -> String {
+    let mut lrat = Vec::new();
+    refutation.write_lrat(&mut lrat, false).unwrap();
+    String::from_utf8(lrat).unwrap()
+}
+
+/// Replays an ASCII LRAT proof on '`LratChecker`', which only checks RUP
+/// steps.  RAT steps are taken as given once their negative hints are
+/// exactly the clauses containing the negated pivot.
+fn replay(formula: &Cnf, lrat: &str) -> Result<(), Error> {
+    let mut checker = LratChecker::new();
+    let mut clauses: HashMap<u64, Vec<Lit>> = (1..).zip(formula.clauses.clone()).collect();
+    for (&id, clause) in &clauses {
+        checker.add_original_clause(id, false, clause, false);
+    }
+    let mut last = 0;
+    for line in lrat.lines() {
+        let mut numbers = line.split(' ');
+        let id: u64 = numbers.next().unwrap().parse().unwrap();
+        if line.split(' ').nth(1) == Some("d") {
+            for deleted in numbers.skip(1).take_while(|&number| number != "0") {
+                let deleted = deleted.parse().unwrap();
+                clauses.remove(&deleted);
+                checker.delete_clause(deleted, false, &[]);
+            }
+            continue;
+        }
+        let numbers: Vec<i64> = numbers.map(|number| number.parse().unwrap()).collect();
+        let end = numbers.iter().position(|&number| number == 0).unwrap();
+        let clause: Vec<i32> = numbers[..end]
+            .iter()
+            .map(|&lit| i32::try_from(lit).unwrap())
+            .collect();
+        let clause = lits(&clause);
+        let hints = &numbers[end + 1..numbers.len() - 1];
+        let rat = hints.iter().any(|&hint| hint < 0) || hints.is_empty() && !clause.is_empty();
+        if rat {
+            let pivot = !clause[0];
+            let mut candidates: Vec<i64> = clauses
+                .iter()
+                .filter(|(_, other)| other.contains(&pivot))
+                .map(|(&other, _)| -i64::try_from(other).unwrap())
+                .collect();
+            candidates.sort_unstable();
+            let mut negative: Vec<i64> = hints.iter().copied().filter(|&hint| hint < 0).collect();
+            negative.sort_unstable();
+            assert_eq!(negative, candidates, "candidates of RAT lemma {id}");
+            checker.add_original_clause(id, true, &clause, false);
+        } else {
+            let hints: Vec<u64> = hints
+                .iter()
+                .map(|&hint| u64::try_from(hint).unwrap())
+                .collect();
+            checker.add_derived_clause(id, true, &clause, &hints);
+        }
+        clauses.insert(id, clause);
+        last = id;
+    }
+    checker.conclude_unsat(1, &[last]);
+    checker.check()
+}
+
+#[test]
+fn test_parse_and_write() {
+    let text = "c comment\n1 -2 0\nd 1 2 0\n\n  -3 0\n0\n";
+    let proof: Proof = text.parse().unwrap();
+    assert_eq!(
+        proof.steps,
+        [
+            Step::Add(lits(&[1, -2])),
+            Step::Delete(lits(&[1, 2])),
+            Step::Add(lits(&[-3])),
+            Step::Add(vec![]),
+        ]
+    );
+    assert_eq!(proof.to_string(), "1 -2 0\nd 1 2 0\n-3 0\n0\n");
+
+    let mut ascii = Vec::new();
+    proof.write(&mut ascii, false).unwrap();
+    assert_eq!(ascii, proof.to_string().as_bytes());
+    let mut binary = Vec::new();
+    proof.write(&mut binary, true).unwrap();
+    assert_eq!(binary, b"a\x02\x05\x00d\x02\x04\x00a\x07\x00a\x00");
+    assert_eq!(Proof::parse(binary.as_slice()).unwrap(), proof);
+
+    match "1 2\n".parse::<Proof>() {
+        Err(Error::Parse(error)) => {
+            assert_eq!((error.line(), error.column()), (1, 4));
+            assert_eq!(error.message(), "missing terminating '0'");
+        }
+        result => panic!("expected parse error, got {result:?}"),
+    }
+    assert!(matches!("x 1 0\n".parse::<Proof>(), Err(Error::Parse(_))));
+    match Proof::parse(&b"a\x02\x00b\x00"[..]) {
+        Err(Error::Parse(error)) => assert_eq!(error.column(), 4),
+        result => panic!("expected parse error, got {result:?}"),
+    }
+    assert!(Proof::parse(&b"a\x02"[..]).is_err());
+    assert!(Proof::parse(&b"a\x01\x00"[..]).is_err());
+}
+
+#[test]
+fn test_refutation_is_trimmed_and_converted() {
+    // the first lemma is not needed
+    let proof: Proof = "2 1 0\n1 0\n0\n".parse().unwrap();
+    let refutation = drat::check(&square(), &proof).unwrap();
+    assert_eq!(refutation.core(), square());
+    assert_eq!(refutation.trimmed().to_string(), "1 0\n0\n");
+    assert_eq!(lrat(&refutation), "6 1 0 1 2 0\n7 0 6 3 4 0\n");
+
+    let mut binary = Vec::new();
+    refutation.write_lrat(&mut binary, true).unwrap();
+    assert_eq!(
+        binary,
+        b"a\x0c\x02\x00\x02\x04\x00a\x0e\x00\x0c\x06\x08\x00"
+    );
+
+    // a formula refuted by unit propagation needs no lemmas
+    let formula: Cnf = "p cnf 3 4\n1 0\n2 3 0\n-1 -2 0\n-1 -3 0\n".parse().unwrap();
+    let refutation = drat::check(&formula, &Proof::default()).unwrap();
+    assert_eq!(refutation.core().clauses.len(), 4);
+    assert_eq!(refutation.trimmed().to_string(), "0\n");
+}
+
+#[test]
+fn test_deletions() {
+    let proof: Proof = "1 2 0\nd 1 2 -3 0\n1 0\nd 1 0\nd 5 0\n2 0\n0\n"
+        .parse()
+        .unwrap();
+    let refutation = drat::check(&cube(), &proof).unwrap();
+    // deleting the unit '1' is ignored since it is the reason of '1'
+    assert_eq!(
+        refutation.trimmed().to_string(),
+        "1 2 0\nd 1 2 -3 0\n1 0\n2 0\n0\n"
+    );
+    let lrat = lrat(&refutation);
+    assert_eq!(
+        lrat,
+        "9 1 2 0 1 2 0\n9 d 2 0\n11 1 0 9 3 4 0\n14 2 0 11 5 6 0\n15 0 11 14 7 8 0\n"
+    );
+    replay(&cube(), &lrat).unwrap();
+
+    // without the deleted clauses '1 2' is not implied
+    let proof: Proof = "d 1 2 3 0\nd 1 2 -3 0\n1 2 0\n1 0\n2 0\n0\n"
+        .parse()
+        .unwrap();
+    match drat::check(&cube(), &proof) {
+        Err(Error::Proof(error)) => assert_eq!(error.id(), 8 + 3),
+        result => panic!("expected proof error, got {result:?}"),
+    }
+}
+
+#[test]
+fn test_rat_lemmas() {
+    // '3' has no clause to resolve with and is used to derive '1'
+    let proof: Proof = "3 0\n1 -3 0\n0\n".parse().unwrap();
+    let refutation = drat::check(&square(), &proof).unwrap();
+    assert_eq!(
+        lrat(&refutation),
+        "5 3 0 0\n6 1 -3 0 1 2 0\n7 0 5 6 3 4 0\n"
+    );
+
+    // '3' resolves with '-3 1' on the pivot
+    let mut formula = square();
+    formula.clauses.push(lits(&[-3, 1]));
+    let proof: Proof = "3 0\n0\n".parse().unwrap();
+    let refutation = drat::check(&formula, &proof).unwrap();
+    let text = lrat(&refutation);
+    assert_eq!(text, "6 3 0 -5 1 2 0\n7 0 6 5 3 4 0\n");
+    let mut binary = Vec::new();
+    refutation.write_lrat(&mut binary, true).unwrap();
+    assert_eq!(&binary[..9], b"a\x0c\x06\x00\x0b\x02\x04\x00a");
+}
+
+#[test]
+fn test_rat_lemma_after_deletion() {
+    // before '-3 5' is deleted, '3' is not RAT since '5' is not implied
+    let mut formula = square();
+    formula.clauses.push(lits(&[-3, 5]));
+    formula.vars = 5;
+    let proof: Proof = "d -3 5 0\n3 0\n1 -3 0\n0\n".parse().unwrap();
+    let refutation = drat::check(&formula, &proof).unwrap();
+    let mut core = square();
+    core.vars = 5;
+    assert_eq!(refutation.core(), core);
+    let trimmed = refutation.trimmed();
+    assert_eq!(trimmed, proof);
+    drat::check(&formula, &trimmed).unwrap();
+    drat::check(&refutation.core(), &trimmed).unwrap();
+
+    let lrat = lrat(&refutation);
+    assert_eq!(lrat, "5 d 5 0\n7 3 0 0\n8 1 -3 0 1 2 0\n9 0 7 8 3 4 0\n");
+    replay(&formula, &lrat).unwrap();
+}
+
+#[test]
+fn test_invalid_proofs() {
+    let failure = |formula: &Cnf, proof: &str| match drat::check(formula, &proof.parse().unwrap()) {
+        Err(Error::Proof(error)) => (error.id(), error.message().to_string()),
+        result => panic!("expected proof error, got {result:?}"),
+    };
+
+    // resolving with '-1 2' gives '-2 -1', which is not implied
+    let mut formula = square();
+    formula.clauses.pop();
+    assert_eq!(
+        failure(&formula, "-2 0\n0\n"),
+        (4, "lemma is neither RUP nor RAT on -2".to_string())
+    );
+    assert_eq!(
+        failure(&square(), "3 0\n0\n"),
+        (
+            6,
+            "the empty clause is not implied by unit propagation".to_string()
+        )
+    );
+    assert_eq!(failure(&formula, "1 0\n").0, 0);
+
+    // lemmas after the conflict are not checked
+    assert!(drat::check(&square(), &"1 0\n0\n-1 0\n".parse().unwrap()).is_ok());
+}
+
+#[test]
+fn test_proofs_of_solver() {
+    let formula = pigeonhole(2);
+    let path = std::env::temp_dir().join("cadical_sys_drat_checker.drat");
+    let mut solver = CaDiCal::new();
+    assert!(solver.trace_proof2(&path));
+    formula.add_to(&mut solver);
+    assert_eq!(solver.solve(), Status::UNSATISFIABLE);
+    solver.close_proof_trace(false);
+    drop(solver);
+    let proof = Proof::read(&path).unwrap();
+    fs::remove_file(path).unwrap();
+
+    let refutation = drat::check(&formula, &proof).unwrap();
+    let lrat = lrat(&refutation);
+    replay(&formula, &lrat).unwrap();
+    let trimmed = refutation.trimmed();
+    assert!(trimmed.steps.len() <= proof.steps.len());
+    drat::check(&refutation.core(), &trimmed).unwrap();
+
+    // the same run in ASCII from a tracer
+    let mut solver = CaDiCal::new();
+    let mut drat = DratWriter::new(Vec::new(), false);
+    solver.with_proof_tracer(&mut drat, false, false, |solver| {
+        formula.add_to(solver);
+        solver.solve()
+    });
+    let proof = Proof::parse(drat.finish().unwrap().as_slice()).unwrap();
+    drat::check(&formula, &proof).unwrap();
+}
@@ -0,0 +1,141 @@
+use cadical_sys::{CaDiCal, Error, State, Status};
+use std::{io, path::Path};
+
+#[test]
+fn test_read_dimacs_reports_missing_file() {
+    let mut solver = CaDiCal::new();
+    let mut vars = 0;
+    let result = solver.read_dimacs1(
+        "./tests/does_not_exist.cnf",
+        "missing".to_string(),
+        &mut vars,
+        0,
+    );
+    assert!(
+        matches!(&result, Err(Error::Open { path, source })
+            if path == Path::new("./tests/does_not_exist.cnf")
+                && source.kind() == io::ErrorKind::NotFound),
+        "unexpected result {result:?}"
+    );
+
+    let result = solver.read_dimacs2("./tests/does_not_exist.cnf", &mut vars, 0);
+    assert!(matches!(
+        result,
+        Err(Error::Open { source, .. }) if source.kind() == io::ErrorKind::NotFound
+    ));
+}
+
+#[test]
+fn test_read_dimacs_succeeds_without_sentinel() {
+    let mut solver = CaDiCal::new();
+    let mut vars = 0;
+    let result = solver.read_dimacs2("./tests/problem.cnf", &mut vars, 1);
+    assert!(result.is_ok(), "unexpected result {result:?}");
+    assert_eq!(vars, 3);
+    assert_eq!(solver.solve(), Status::SATISFIABLE);
+}
+
+#[test]
+fn test_write_dimacs_reports_unwritable_path() {
+    let mut solver = CaDiCal::new();
+    solver.clause2(1, 2);
+    let result = solver.write_dimacs("/does/not/exist/out.cnf", 0);
+    assert!(matches!(
+        result,
+        Err(Error::Open { source, .. }) if source.kind() == io::ErrorKind::NotFound
+    ));
+}
+
+#[test]
+fn test_write_extension_reports_directory() {
+    let mut solver = CaDiCal::new();
+    solver.clause2(1, 2);
+    let result = solver.write_extension("./tests");
+    assert!(
+        matches!(&result, Err(Error::Open { path, .. }) if path == Path::new("./tests")),
+        "unexpected result {result:?}"
+    );
+}
+
+#[test]
+fn test_trace_proof_reports_unwritable_path() {
+    let mut solver = CaDiCal::new();
+    let result = solver.try_trace_proof1("/does/not/exist/proof", "p".to_string());
+    assert!(matches!(
+        result,
+        Err(Error::Open { source, .. }) if source.kind() == io::ErrorKind::NotFound
+    ));
+    assert!(!solver.trace_proof1("/does/not/exist/proof", "p".to_string()));
+    let result = solver.try_trace_proof2("/does/not/exist/proof");
+    assert!(matches!(
+        result,
+        Err(Error::Open { source, .. }) if source.kind() == io::ErrorKind::NotFound
+    ));
+}
+
+#[test]
+fn test_invalid_names_are_errors() {
+    let mut solver = CaDiCal::new();
+    assert!(solver.try_set("verbose".to_string(), 0).is_ok());
+    assert!(matches!(
+        solver.try_set("no-such-option".to_string(), 1),
+        Err(Error::InvalidOption(name)) if name == "no-such-option"
+    ));
+    assert!(matches!(
+        solver.try_configure("no-such-configuration".to_string()),
+        Err(Error::InvalidConfiguration(_))
+    ));
+    assert!(solver.try_limit("conflicts".to_string(), 10).is_ok());
+    assert!(matches!(
+        solver.try_limit("no-such-limit".to_string(), 1),
+        Err(Error::InvalidLimit(_))
+    ));
+}
+
+#[test]
+fn test_unknown_codes_are_errors() {
+    assert!(matches!(Status::try_from(3), Err(Error::InvalidStatus(3))));
+    assert_eq!(Status::try_from(20).unwrap(), Status::UNSATISFIABLE);
+    assert!(matches!(
+        State::try_from(256),
+        Err(Error::InvalidState(256))
+    ));
+    assert_eq!(State::try_from(2).unwrap(), State::CONFIGURING);
+}
+
+#[test]
+fn test_status_goes_through_try_paths() {
+    let mut solver = CaDiCal::new();
+    assert_eq!(solver.try_status().unwrap(), Status::UNKNOWN);
+    solver.clause2(1, 2);
+    assert_ne!(solver.try_simplify(0).unwrap(), Status::UNSATISFIABLE);
+    assert_eq!(solver.solve(), Status::SATISFIABLE);
+    assert_eq!(solver.try_status().unwrap(), Status::SATISFIABLE);
+    assert_eq!(solver.status(), Status::SATISFIABLE);
+    assert_ne!(solver.checked().simplify(1).unwrap(), Status::UNSATISFIABLE);
+}
+
+#[test]
+fn test_try_solve_returns_callback_panic() {
+    let mut solver = CaDiCal::new();
+    solver.connect_terminator_fn(|| panic!("deadline check failed"));
+    // pigeonhole formula with 7 pigeons and 6 holes, which needs search
+    for pigeon in 0..7 {
+        let clause: Vec<i32> = (1..=6).map(|hole| pigeon * 6 + hole).collect();
+        solver.clause6(&clause);
+    }
+    for hole in 1..=6 {
+        for p1 in 0..7 {
+            for p2 in (p1 + 1)..7 {
+                solver.clause2(-(p1 * 6 + hole), -(p2 * 6 + hole));
+            }
+        }
+    }
+
+    let error = solver.try_solve().unwrap_err();
+    assert!(matches!(error, Error::Panic(_)));
+    assert_eq!(
+        error.to_string(),
+        "call-back panicked: deadline check failed"
+    );
+}
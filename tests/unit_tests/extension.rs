@@ -0,0 +1,112 @@
+use super::common::lits;
+use cadical_sys::{
+    dimacs::Cnf,
+    extension::{Entry, Extension},
+    solution::Solution,
+    CaDiCal, Error, Status,
+};
+use std::fs;
+
+/// A formula where bounded variable elimination removes variables.
+fn formula() -> Cnf {
+    "p cnf 6 7\n1 2 0\n-1 3 0\n-2 3 0\n-3 4 5 0\n-4 6 0\n-5 6 0\n-6 -1 0\n"
+        .parse()
+        .unwrap()
+}
+
+#[test]
+fn test_parse_and_write() {
+    let text = "1 -2 0 1 0\n3 0 0\n-4 5 6 0 -4 5 0\n";
+    let extension: Extension = text.parse().unwrap();
+    assert_eq!(
+        extension.entries,
+        [
+            Entry {
+                clause: lits(&[1, -2]),
+                witness: lits(&[1])
+            },
+            Entry {
+                clause: lits(&[3]),
+                witness: vec![]
+            },
+            Entry {
+                clause: lits(&[-4, 5, 6]),
+                witness: lits(&[-4, 5])
+            },
+        ]
+    );
+    assert_eq!(extension.to_string(), text);
+
+    match "1 2 0\n".parse::<Extension>() {
+        Err(Error::Parse(error)) => {
+            assert_eq!((error.line(), error.column()), (1, 6));
+            assert_eq!(error.message(), "missing terminating '0'");
+        }
+        result => panic!("expected parse error, got {result:?}"),
+    }
+    assert!(matches!(
+        "1 0 2 0 x\n".parse::<Extension>(),
+        Err(Error::Parse(_))
+    ));
+}
+
+#[test]
+fn test_extend_flips_witnesses_in_order() {
+    let extension = Extension {
+        entries: vec![
+            Entry {
+                clause: lits(&[3, -1]),
+                witness: lits(&[3]),
+            },
+            Entry {
+                clause: lits(&[-3, 2]),
+                witness: lits(&[-3]),
+            },
+        ],
+    };
+    // the first entry sets '3' which the second then resets
+    assert_eq!(extension.extend(&lits(&[1, -2])), lits(&[1, -2, -3]));
+    assert_eq!(extension.extend(&lits(&[1, 2])), lits(&[1, 2, 3]));
+    // unassigned variables are false
+    assert_eq!(extension.extend(&[]), lits(&[-1, -2, -3]));
+}
+
+#[test]
+fn test_file_and_traversal_agree() {
+    let mut solver = CaDiCal::new();
+    formula().add_to(&mut solver);
+    solver.simplify(2);
+    let path = std::env::temp_dir().join("cadical_sys_extension_agree.ext");
+    solver.write_extension(&path).unwrap();
+    let read = Extension::read(&path).unwrap();
+    assert_eq!(read, Extension::from_solver(&solver));
+    assert_eq!(fs::read_to_string(&path).unwrap(), read.to_string());
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_reconstruct_model_of_simplified_formula() {
+    let original = formula();
+    let mut solver = CaDiCal::new();
+    original.add_to(&mut solver);
+    assert_eq!(solver.simplify(2), Status::UNKNOWN);
+    let extension = Extension::from_solver(&solver);
+    assert!(!extension.entries.is_empty());
+
+    // solve the simplified formula elsewhere
+    let mut simplified = Vec::new();
+    solver.write_dimacs_to(&mut simplified, 0).unwrap();
+    let simplified = Cnf::parse(simplified.as_slice(), 1).unwrap();
+    let mut other = CaDiCal::new();
+    simplified.add_to(&mut other);
+    let status = other.solve();
+    assert_eq!(status, Status::SATISFIABLE);
+    let partial = Solution::from_solver(&mut other, status).model;
+
+    let solution = Solution {
+        status,
+        model: extension.extend(&partial),
+        comments: Vec::new(),
+    };
+    assert_eq!(solution.falsified(&original), None);
+}
@@ -1,4 +1,4 @@
-use cadical_sys::{CaDiCal, ExternalPropagator, Status};
+use cadical_sys::{CaDiCal, ExternalPropagator, Lit, Status};
 
 struct MyExternalPropagator {}
 
@@ -11,29 +11,29 @@ impl ExternalPropagator for MyExternalPropagator {
         false
     }
 
-    fn notify_assignment(&mut self, _: &[i32]) {}
+    fn notify_assignment(&mut self, _: &[Lit]) {}
     fn notify_new_decision_level(&mut self) {}
     fn notify_backtrack(&mut self, _: usize) {
         todo!()
     }
-    fn cb_check_found_model(&mut self, _: &[i32]) -> bool {
+    fn cb_check_found_model(&mut self, _: &[Lit]) -> bool {
         true // sat
     }
     fn cb_has_external_clause(&mut self, _: &mut bool) -> bool {
         false
     }
-    fn cb_add_external_clause_lit(&mut self) -> i32 {
+    fn cb_add_external_clause_lit(&mut self) -> Option<Lit> {
         todo!()
     }
-    fn cb_decide(&mut self) -> i32 {
-        0
+    fn cb_decide(&mut self) -> Option<Lit> {
+        None
     }
 
-    fn cb_propagate(&mut self) -> i32 {
-        0
+    fn cb_propagate(&mut self) -> Option<Lit> {
+        None
     }
 
-    fn cb_add_reason_clause_lit(&mut self, _propagated_lit: i32) -> i32 {
+    fn cb_add_reason_clause_lit(&mut self, _propagated_lit: Lit) -> Option<Lit> {
         todo!()
     }
 }
@@ -49,8 +49,7 @@ fn add_sat_test_cnf(solver: &mut CaDiCal) {
 fn test_external_propagator_connect_and_disconnect() {
     let mut solver = CaDiCal::new();
     let mut propagator = MyExternalPropagator {};
-    solver.connect_external_propagator(&mut propagator);
-    solver.disconnect_external_propagator();
+    solver.with_external_propagator(&mut propagator, |_| {});
 }
 
 #[test]
@@ -67,8 +66,7 @@ fn test_external_propagator_flow_disconnect_without_connecting() {
 fn test_external_propagator_flow_solve_after_connect_disconnect() {
     let mut solver = CaDiCal::new();
     let mut propagator = MyExternalPropagator {};
-    solver.connect_external_propagator(&mut propagator);
-    solver.disconnect_external_propagator();
+    solver.with_external_propagator(&mut propagator, |_| {});
 
     add_sat_test_cnf(&mut solver);
     let result = solver.solve();
@@ -80,9 +78,9 @@ fn test_external_propagator_solve_after_connect() {
     let mut solver = CaDiCal::new();
     let mut propagator = MyExternalPropagator {};
 
-    solver.connect_external_propagator(&mut propagator);
-
-    add_sat_test_cnf(&mut solver);
-    let result = solver.solve();
+    let result = solver.with_external_propagator(&mut propagator, |solver| {
+        add_sat_test_cnf(solver);
+        solver.solve()
+    });
     assert_eq!(result, Status::SATISFIABLE);
 }
@@ -0,0 +1,116 @@
+use super::common::add_pigeonhole_cnf;
+use cadical_sys::{
+    proof::{FileTracer, ProofFormat, StatTracer},
+    CaDiCal, Error, Status,
+};
+use std::{fs, path::PathBuf};
+
+/// Three pigeons in two holes.
+fn pigeons(solver: &mut CaDiCal) -> Status {
+    add_pigeonhole_cnf(solver, 2);
+    solver.solve()
+}
+
+fn proof_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("cadical_sys_file_tracer_{name}"))
+}
+
+#[test]
+fn test_drat_and_lrat_files() {
+    let drat = proof_path("ascii.drat");
+    let lrat = proof_path("ascii.lrat");
+    for (path, format) in [(&drat, ProofFormat::Drat), (&lrat, ProofFormat::Lrat)] {
+        let mut solver = CaDiCal::new();
+        let tracer = FileTracer::new(path, format, false).unwrap();
+        assert_eq!(tracer.format(), format);
+        let id = solver.connect_file_tracer(tracer);
+        assert_eq!(pigeons(&mut solver), Status::UNSATISFIABLE);
+        let statistics = solver.proof_statistics(id).unwrap();
+        assert_eq!(statistics.original, 9);
+        assert!(statistics.derived > 0);
+
+        let tracer = solver.disconnect_file_tracer(id).unwrap();
+        assert_eq!(tracer.statistics(), statistics);
+        drop(tracer);
+        assert!(solver.disconnect_file_tracer(id).is_none());
+    }
+
+    let proof = fs::read_to_string(&drat).unwrap();
+    assert!(proof.lines().any(|line| line == "0"));
+    let proof = fs::read_to_string(&lrat).unwrap();
+    let empty = proof
+        .lines()
+        .find(|line| line.split(' ').nth(1) == Some("0"))
+        .unwrap();
+    // the empty clause has antecedents
+    assert!(!empty.ends_with(" 0 0"));
+    fs::remove_file(drat).unwrap();
+    fs::remove_file(lrat).unwrap();
+}
+
+#[test]
+fn test_all_formats_write_proofs() {
+    for format in [ProofFormat::Drat, ProofFormat::Lrat, ProofFormat::Frat] {
+        for binary in [false, true] {
+            let path = proof_path(&format!("{format:?}_{binary}"));
+            // the file is completed when the solver drops the tracer
+            let mut solver = CaDiCal::new();
+            solver.connect_file_tracer(FileTracer::new(&path, format, binary).unwrap());
+            assert_eq!(pigeons(&mut solver), Status::UNSATISFIABLE);
+            drop(solver);
+            let proof = fs::read(&path).unwrap();
+            assert!(!proof.is_empty(), "{format:?} binary {binary}");
+            fs::remove_file(path).unwrap();
+        }
+    }
+}
+
+#[test]
+fn test_frat_file_finalizes_clauses() {
+    let path = proof_path("finalized.frat");
+    let mut solver = CaDiCal::new();
+    let id = solver.connect_file_tracer(FileTracer::new(&path, ProofFormat::Frat, false).unwrap());
+    assert_eq!(pigeons(&mut solver), Status::UNSATISFIABLE);
+    let tracer = solver.disconnect_file_tracer(id).unwrap();
+    let statistics = tracer.statistics();
+    tracer.finish().unwrap();
+
+    let proof = fs::read_to_string(&path).unwrap();
+    let count = |tag: &str| proof.lines().filter(|line| line.starts_with(tag)).count();
+    assert_eq!(count("o "), 9);
+    assert_eq!(u64::try_from(count("f ")).unwrap(), statistics.finalized);
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_stat_tracer_counts_steps() {
+    let mut solver = CaDiCal::new();
+    let id = solver.connect_stat_tracer(StatTracer::new(), true);
+    assert_eq!(pigeons(&mut solver), Status::UNSATISFIABLE);
+    let statistics = solver.proof_statistics(id).unwrap();
+    assert_eq!(statistics.original, 9);
+    assert!(statistics.derived > 0);
+
+    let tracer = solver.disconnect_stat_tracer(id).unwrap();
+    assert_eq!(tracer.statistics(), statistics);
+    assert_eq!(solver.proof_statistics(id), None);
+    assert_eq!(StatTracer::new().statistics().derived, 0);
+}
+
+#[test]
+fn test_file_tracer_errors() {
+    let path = std::env::temp_dir().join("cadical_sys_missing_dir/proof.drat");
+    assert!(matches!(
+        FileTracer::new(&path, ProofFormat::Drat, false),
+        Err(Error::Open { .. })
+    ));
+    // these are only written by 'trace_proof', nothing is created for them
+    for format in [ProofFormat::Veripb, ProofFormat::Idrup, ProofFormat::Lidrup] {
+        let path = proof_path(&format!("unsupported_{format:?}"));
+        assert!(matches!(
+            FileTracer::new(&path, format, false),
+            Err(Error::UnsupportedFormat(unsupported)) if unsupported == format
+        ));
+        assert!(!path.exists());
+    }
+}
@@ -0,0 +1,83 @@
+use cadical_sys::{CaDiCal, Error, Status};
+use std::{fs, path::PathBuf};
+
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("cadical_sys_files_{name}"))
+}
+
+#[test]
+fn test_path_buf_arguments() {
+    let cnf = temp_path("round_trip.cnf");
+    let mut solver = CaDiCal::new();
+    solver.clause2(1, -2);
+    solver.clause1(2);
+    solver.write_dimacs(&cnf, 0).unwrap();
+
+    let mut copy = CaDiCal::new();
+    let mut vars = 0;
+    copy.read_dimacs2(&cnf, &mut vars, 1).unwrap();
+    assert_eq!(vars, 2);
+    assert_eq!(copy.solve(), Status::SATISFIABLE);
+    assert_eq!(copy.val(1), 1);
+
+    let extension = temp_path("round_trip.ext");
+    copy.write_extension(extension.as_path()).unwrap();
+    assert!(extension.exists());
+
+    fs::remove_file(cnf).unwrap();
+    fs::remove_file(extension).unwrap();
+}
+
+#[test]
+fn test_trace_proof_file_outlives_tracing() {
+    let proof = temp_path("trace_proof1.drat");
+    let mut solver = CaDiCal::new();
+    solver
+        .try_trace_proof1(&proof, "proof".to_string())
+        .unwrap();
+    solver.clause1(1);
+    solver.clause1(-1);
+    assert_eq!(solver.solve(), Status::UNSATISFIABLE);
+    // the file stays open until the solver is dropped
+    drop(solver);
+
+    let contents = fs::read(&proof).unwrap();
+    assert!(
+        !contents.is_empty(),
+        "proof should contain the empty clause"
+    );
+    fs::remove_file(proof).unwrap();
+}
+
+#[test]
+fn test_paths_with_nul_bytes_are_rejected() {
+    let mut solver = CaDiCal::new();
+    let mut vars = 0;
+    let result = solver.read_dimacs2("bad\0name.cnf", &mut vars, 0);
+    assert!(matches!(
+        result,
+        Err(Error::Open { source, .. }) if source.kind() == std::io::ErrorKind::InvalidInput
+    ));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_non_utf8_paths() {
+    use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
+
+    let mut name = temp_path("").into_os_string();
+    name.push(OsStr::from_bytes(b"non_utf8_\xff.cnf"));
+    let path = PathBuf::from(name);
+    assert!(path.to_str().is_none());
+
+    let mut solver = CaDiCal::new();
+    solver.clause2(-1, 2);
+    solver.write_dimacs(&path, 0).unwrap();
+
+    let mut copy = CaDiCal::new();
+    let mut vars = 0;
+    copy.read_dimacs1(&path, "non-utf8".to_string(), &mut vars, 1)
+        .unwrap();
+    assert_eq!(vars, 2);
+    fs::remove_file(path).unwrap();
+}
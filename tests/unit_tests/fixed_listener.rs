@@ -1,4 +1,4 @@
-use cadical_sys::{CaDiCal, FixedAssignmentListener, Status};
+use cadical_sys::{CaDiCal, FixedAssignmentListener, Lit, Status};
 
 struct MyFixedListener {
     fixed_assignments: Vec<i32>,
@@ -17,8 +17,8 @@ impl MyFixedListener {
 }
 
 impl FixedAssignmentListener for MyFixedListener {
-    fn notify_fixed_assignment(&mut self, lit: i32) {
-        self.fixed_assignments.push(lit);
+    fn notify_fixed_assignment(&mut self, lit: Lit) {
+        self.fixed_assignments.push(lit.to_dimacs());
     }
 }
 
@@ -48,16 +48,14 @@ fn add_fixed_assignment_cnf(solver: &mut CaDiCal) {
 fn test_fixed_listener_connect_and_disconnect() {
     let mut solver = CaDiCal::new();
     let mut fixed_listener = MyFixedListener::new();
-    solver.connect_fixed_listener(&mut fixed_listener);
-    solver.disconnect_fixed_listener();
+    solver.with_fixed_listener(&mut fixed_listener, |_| {});
 }
 
 #[test]
 fn test_fixed_listener_flow_solve_after_connect_disconnect() {
     let mut solver = CaDiCal::new();
     let mut fixed_listener = MyFixedListener::new();
-    solver.connect_fixed_listener(&mut fixed_listener);
-    solver.disconnect_fixed_listener();
+    solver.with_fixed_listener(&mut fixed_listener, |_| {});
 
     add_sat_test_cnf(&mut solver);
     let result = solver.solve();
@@ -69,10 +67,10 @@ fn test_fixed_listener_solve_after_connect() {
     let mut solver = CaDiCal::new();
     let mut fixed_listener = MyFixedListener::new();
 
-    solver.connect_fixed_listener(&mut fixed_listener);
-
-    add_sat_test_cnf(&mut solver);
-    let result = solver.solve();
+    let result = solver.with_fixed_listener(&mut fixed_listener, |solver| {
+        add_sat_test_cnf(solver);
+        solver.solve()
+    });
     assert_eq!(result, Status::SATISFIABLE);
     let assignments = fixed_listener.get_fixed_assignments();
     assert!(
@@ -86,10 +84,10 @@ fn test_fixed_listener_receives_assignments() {
     let mut solver = CaDiCal::new();
     let mut fixed_listener = MyFixedListener::new();
 
-    solver.connect_fixed_listener(&mut fixed_listener);
-    add_fixed_assignment_cnf(&mut solver);
-
-    let result = solver.solve();
+    let result = solver.with_fixed_listener(&mut fixed_listener, |solver| {
+        add_fixed_assignment_cnf(solver);
+        solver.solve()
+    });
     assert_eq!(result, Status::UNSATISFIABLE);
 
     // Check that the listener received some fixed assignments
@@ -105,10 +103,10 @@ fn test_fixed_listener_unsat_case() {
     let mut solver = CaDiCal::new();
     let mut fixed_listener = MyFixedListener::new();
 
-    solver.connect_fixed_listener(&mut fixed_listener);
-    add_unsat_test_cnf(&mut solver);
-
-    let result = solver.solve();
+    let result = solver.with_fixed_listener(&mut fixed_listener, |solver| {
+        add_unsat_test_cnf(solver);
+        solver.solve()
+    });
     assert_eq!(result, Status::UNSATISFIABLE);
 
     let assignments = fixed_listener.get_fixed_assignments();
@@ -127,8 +125,7 @@ fn test_fixed_listener_disconnect_prevents_notifications() {
     let mut solver = CaDiCal::new();
     let mut fixed_listener = MyFixedListener::new();
 
-    solver.connect_fixed_listener(&mut fixed_listener);
-    solver.disconnect_fixed_listener();
+    solver.with_fixed_listener(&mut fixed_listener, |_| {});
 
     add_fixed_assignment_cnf(&mut solver);
     let result = solver.solve();
@@ -148,12 +145,11 @@ fn test_fixed_listener_reconnect() {
     let mut fixed_listener = MyFixedListener::new();
 
     // Connect, disconnect, then reconnect
-    solver.connect_fixed_listener(&mut fixed_listener);
-    solver.disconnect_fixed_listener();
-    solver.connect_fixed_listener(&mut fixed_listener);
-
-    add_fixed_assignment_cnf(&mut solver);
-    let result = solver.solve();
+    solver.with_fixed_listener(&mut fixed_listener, |_| {});
+    let result = solver.with_fixed_listener(&mut fixed_listener, |solver| {
+        add_fixed_assignment_cnf(solver);
+        solver.solve()
+    });
     assert_eq!(result, Status::UNSATISFIABLE);
 
     // Should receive assignments after reconnection
@@ -169,10 +165,8 @@ fn test_fixed_listener_empty_cnf() {
     let mut solver = CaDiCal::new();
     let mut fixed_listener = MyFixedListener::new();
 
-    solver.connect_fixed_listener(&mut fixed_listener);
-
     // Solve with no clauses (empty CNF)
-    let result = solver.solve();
+    let result = solver.with_fixed_listener(&mut fixed_listener, CaDiCal::solve);
     assert_eq!(result, Status::SATISFIABLE);
 
     // Empty CNF should not produce fixed assignments
@@ -188,13 +182,13 @@ fn test_fixed_listener_unit_clauses() {
     let mut solver = CaDiCal::new();
     let mut fixed_listener = MyFixedListener::new();
 
-    solver.connect_fixed_listener(&mut fixed_listener);
-
-    // Add unit clauses which should result in fixed assignments
-    solver.clause6(&[1]);
-    solver.clause6(&[-2]);
+    let result = solver.with_fixed_listener(&mut fixed_listener, |solver| {
+        // Add unit clauses which should result in fixed assignments
+        solver.clause6(&[1]);
+        solver.clause6(&[-2]);
 
-    let result = solver.solve();
+        solver.solve()
+    });
     assert_eq!(result, Status::SATISFIABLE);
 
     let assignments = fixed_listener.get_fixed_assignments();
@@ -0,0 +1,117 @@
+use super::common::lits;
+use cadical_sys::{
+    dimacs::{CubeOutcome, CubeRunner, IncrementalCnf, Item, Parser},
+    CaDiCal, Error, Status,
+};
+
+const ICNF: &str = "c cubes\np inccnf\n1 2 0\n-1 3 0\na -2 0\na -3 -2 0\na 1 0\n";
+
+#[test]
+fn test_parse_inccnf() {
+    let icnf: IncrementalCnf = ICNF.parse().unwrap();
+    assert_eq!(icnf.vars, 3);
+    assert_eq!(icnf.comments, ["cubes"]);
+    assert_eq!(icnf.clauses, [lits(&[1, 2]), lits(&[-1, 3])]);
+    assert_eq!(icnf.cubes, [lits(&[-2]), lits(&[-3, -2]), lits(&[1])]);
+    assert_eq!(icnf.to_string(), ICNF);
+}
+
+#[test]
+fn test_cubes_in_cnf_files() {
+    let mut parser = Parser::incremental("p cnf 2 1\n1 2 0\na -1 0\n".as_bytes(), 1);
+    let items: Vec<Item> = parser.by_ref().collect::<Result<_, _>>().unwrap();
+    assert_eq!(items.last(), Some(&Item::Cube(lits(&[-1]))));
+    assert!(parser.is_incremental());
+
+    // plain parsers and clauses do not accept cubes
+    let error = Parser::new("p cnf 2 1\na -1 0\n".as_bytes(), 1)
+        .find_map(Result::err)
+        .unwrap();
+    assert!(matches!(error, Error::Parse(error) if error.column() == 1));
+    let error = "p inccnf\n1 a 2 0\n".parse::<IncrementalCnf>().unwrap_err();
+    assert!(matches!(error, Error::Parse(error) if error.message() == "cube within clause"));
+    let error = "p inccnf\na 1\n".parse::<IncrementalCnf>().unwrap_err();
+    assert!(
+        matches!(error, Error::Parse(error) if error.message() == "last cube without terminating '0'")
+    );
+}
+
+#[test]
+fn test_split_cubes() {
+    assert_eq!(
+        IncrementalCnf::split_cubes(&[1, -2, 0, 0, 3, 0]),
+        Some(vec![lits(&[1, -2]), vec![], lits(&[3])])
+    );
+    assert_eq!(IncrementalCnf::split_cubes(&[]), Some(vec![]));
+    assert_eq!(IncrementalCnf::split_cubes(&[1, 2]), None);
+}
+
+#[test]
+fn test_runner_reports_each_cube() {
+    let icnf: IncrementalCnf = ICNF.parse().unwrap();
+    let mut solver = CaDiCal::new();
+    let results: Vec<_> = icnf.runner(&mut solver).collect::<Result<_, _>>().unwrap();
+    assert_eq!(results.len(), 3);
+    assert_eq!(
+        results
+            .iter()
+            .map(|result| result.index)
+            .collect::<Vec<_>>(),
+        [0, 1, 2]
+    );
+
+    // '-2' forces '1' and thus '3'
+    match &results[0].outcome {
+        CubeOutcome::Satisfiable { model } => assert_eq!(model, &lits(&[1, -2, 3])),
+        outcome => panic!("unexpected outcome {outcome:?}"),
+    }
+    assert_eq!(results[1].status(), Status::UNSATISFIABLE);
+    match &results[1].outcome {
+        CubeOutcome::Unsatisfiable { failed } => {
+            assert!(!failed.is_empty());
+            assert!(failed.iter().all(|lit| [-3, -2].contains(&lit.to_dimacs())));
+        }
+        outcome => panic!("unexpected outcome {outcome:?}"),
+    }
+    assert_eq!(results[2].status(), Status::SATISFIABLE);
+}
+
+#[test]
+fn test_runner_stops_at_first_sat() {
+    let icnf: IncrementalCnf = "p inccnf\n1 2 0\na -1 -2 0\na -1 0\na 1 0\n"
+        .parse()
+        .unwrap();
+    let mut solver = CaDiCal::new();
+    let statuses: Vec<Status> = icnf
+        .runner(&mut solver)
+        .stop_at_first_sat(true)
+        .map(|result| result.unwrap().status())
+        .collect();
+    assert_eq!(statuses, [Status::UNSATISFIABLE, Status::SATISFIABLE]);
+}
+
+#[test]
+fn test_runner_on_solver_cubes() {
+    let mut solver = CaDiCal::new();
+    let mut vars = 0;
+    let mut incremental = false;
+    let mut cubes = Vec::new();
+    solver
+        .read_inccnf_str(ICNF, &mut vars, 1, &mut incremental, &mut cubes)
+        .unwrap();
+    assert!(incremental);
+    let cubes = IncrementalCnf::split_cubes(&cubes).unwrap();
+    assert_eq!(cubes, ICNF.parse::<IncrementalCnf>().unwrap().cubes);
+
+    let statuses: Vec<Status> = CubeRunner::new(&mut solver, &cubes)
+        .map(|result| result.unwrap().status())
+        .collect();
+    assert_eq!(
+        statuses,
+        [
+            Status::SATISFIABLE,
+            Status::UNSATISFIABLE,
+            Status::SATISFIABLE
+        ]
+    );
+}
@@ -0,0 +1,160 @@
+use cadical_sys::{CaDiCal, Error, Lit, State, Status, Var};
+use std::{
+    panic::{catch_unwind, AssertUnwindSafe},
+    sync::{Arc, Mutex},
+};
+
+#[test]
+fn test_var_construction() {
+    assert_eq!(Var::new(0), None);
+    assert_eq!(Var::new(-3), None);
+    let var = Var::new(3).unwrap();
+    assert_eq!(var.index(), 3);
+    assert_eq!(var.positive().to_dimacs(), 3);
+    assert_eq!(var.negative().to_dimacs(), -3);
+    assert_eq!(var.lit(true), var.positive());
+    assert_eq!(var.lit(false), var.negative());
+    assert_eq!(var.to_string(), "3");
+}
+
+#[test]
+fn test_lit_dimacs_round_trip() {
+    assert_eq!(Lit::from_dimacs(0), None);
+    assert_eq!(Lit::from_dimacs(i32::MIN), None);
+    for dimacs in [1, -1, 42, -42, i32::MAX, -i32::MAX] {
+        let lit = Lit::from_dimacs(dimacs).unwrap();
+        assert_eq!(lit.to_dimacs(), dimacs);
+        assert_eq!(i32::from(lit), dimacs);
+        assert_eq!(Lit::try_from(dimacs).unwrap(), lit);
+        assert_eq!(lit.to_string(), dimacs.to_string());
+    }
+}
+
+#[test]
+fn test_lit_negation_and_var() {
+    let lit = Lit::from_dimacs(-5).unwrap();
+    assert!(lit.is_negative());
+    assert!(!lit.is_positive());
+    assert_eq!((!lit).to_dimacs(), 5);
+    assert_eq!(!!lit, lit);
+    assert_eq!(lit.var(), Var::new(5).unwrap());
+    assert_eq!((!lit).var(), lit.var());
+}
+
+#[test]
+fn test_lit_try_from_rejects_invalid() {
+    for dimacs in [0, i32::MIN] {
+        let error = Lit::try_from(dimacs).unwrap_err();
+        assert!(matches!(
+            error,
+            Error::InvalidLiteral { lit, .. } if lit == dimacs
+        ));
+    }
+}
+
+#[test]
+fn test_lit_slices() {
+    let lits = Lit::slice_from_dimacs(&[1, -2, 3]).unwrap();
+    assert_eq!(lits.len(), 3);
+    assert!(lits[1].is_negative());
+    assert_eq!(Lit::slice_to_dimacs(lits), &[1, -2, 3]);
+    assert_eq!(Lit::slice_from_dimacs(&[1, 0, 3]), None);
+}
+
+#[test]
+fn test_solver_accepts_lits() {
+    let a = Var::new(1).unwrap();
+    let b = Var::new(2).unwrap();
+
+    let mut solver = CaDiCal::new();
+    solver.clause6(&[a.positive(), b.positive()]);
+    solver.clause2(a.negative(), b.positive());
+    solver.assume(b.negative());
+    assert_eq!(solver.solve(), Status::UNSATISFIABLE);
+    assert!(solver.failed(b.negative()));
+
+    assert_eq!(solver.solve(), Status::SATISFIABLE);
+    assert_eq!(solver.val(b.positive()), 2);
+    solver.freeze(a.positive());
+    assert!(solver.frozen(a.negative()));
+    solver.melt(a.positive());
+}
+
+#[test]
+fn test_checked_accepts_lits() {
+    let a = Var::new(1).unwrap();
+    let mut solver = CaDiCal::new();
+    let mut checked = solver.checked();
+    checked.clause(&[a.negative()]).unwrap();
+    assert_eq!(checked.solve().unwrap(), Status::SATISFIABLE);
+    assert_eq!(checked.val(a.positive()).unwrap(), -1);
+    assert!(!checked.value(a.positive()).unwrap());
+}
+
+#[test]
+fn test_fixed_listener_receives_lits() {
+    let mut solver = CaDiCal::new();
+    let fixed = Arc::new(Mutex::new(Vec::new()));
+    let sink = Arc::clone(&fixed);
+    solver.connect_fixed_listener_fn(move |lit| sink.lock().unwrap().push(lit));
+    solver.clause1(-4);
+
+    assert_eq!(solver.solve(), Status::SATISFIABLE);
+    let four = Var::new(4).unwrap();
+    assert_eq!(*fixed.lock().unwrap(), vec![four.negative()]);
+}
+
+#[test]
+fn test_raw_invalid_literals_panic_before_solver() {
+    let mut solver = CaDiCal::new();
+    let calls: [&dyn Fn(&mut CaDiCal); 5] = [
+        &|solver| solver.clause2(1, 0),
+        &|solver| solver.add(i32::MIN),
+        &|solver| solver.assume(0),
+        &|solver| solver.freeze(i32::MIN),
+        // variables are positive
+        &|solver| solver.add_observed_var(-3),
+    ];
+    for call in calls {
+        let caught = catch_unwind(AssertUnwindSafe(|| call(&mut solver))).unwrap_err();
+        let message = caught.downcast_ref::<String>().unwrap();
+        assert!(message.contains("invalid literal"), "{message}");
+    }
+
+    // nothing reached the solver, and '0' still terminates clauses
+    assert_eq!(solver.state(), State::CONFIGURING);
+    solver.add(-1);
+    solver.add(0);
+    assert_eq!(solver.solve(), Status::SATISFIABLE);
+    assert_eq!(solver.val(1), -1);
+    assert!(solver.value(-1));
+    assert!(!solver.value(1));
+}
+
+#[test]
+fn test_typed_view_takes_lits() {
+    let a = Var::new(1).unwrap();
+    let b = Var::new(2).unwrap();
+
+    let mut solver = CaDiCal::new();
+    let mut typed = solver.typed();
+    typed.clause(&[a.positive(), b.positive()]);
+    typed.clause(&[a.negative(), b.positive()]);
+    typed.freeze(a.positive());
+    assert!(typed.frozen(a.negative()));
+    typed.assume(b.negative());
+    assert_eq!(solver.solve(), Status::UNSATISFIABLE);
+    assert!(solver.typed().failed(b.negative()));
+
+    solver.typed().constrain(&[b.negative()]);
+    assert_eq!(solver.solve(), Status::UNSATISFIABLE);
+    assert!(solver.constraint_failed());
+
+    solver.typed().phase(a.negative());
+    assert_eq!(solver.solve(), Status::SATISFIABLE);
+    let mut typed = solver.typed();
+    assert_eq!(typed.val(b.negative()), b.positive());
+    assert!(typed.value(b.positive()));
+    typed.melt(a.positive());
+    typed.unphase(a.negative());
+}
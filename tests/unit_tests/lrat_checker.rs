@@ -0,0 +1,174 @@
+use super::common::{add_pigeonhole_cnf, lits};
+use cadical_sys::{proof::LratChecker, CaDiCal, Error, Lit, ProofTracer, Status};
+
+/// Adds the four clauses over '1' and '2'.
+fn originals(checker: &mut LratChecker) {
+    checker.add_original_clause(1, false, &lits(&[1, 2]), false);
+    checker.add_original_clause(2, false, &lits(&[1, -2]), false);
+    checker.add_original_clause(3, false, &lits(&[-1, 2]), false);
+    checker.add_original_clause(4, false, &lits(&[-1, -2]), false);
+}
+
+#[test]
+fn test_valid_chain_is_verified() {
+    let mut checker = LratChecker::new();
+    originals(&mut checker);
+    checker.add_derived_clause(5, true, &lits(&[1]), &[1, 2]);
+    checker.delete_clause(1, false, &lits(&[1, 2]));
+    checker.add_derived_clause(6, true, &[], &[5, 3, 4]);
+    assert!(!checker.verified());
+    checker.conclude_unsat(1, &[6]);
+    assert!(checker.verified());
+    assert!(checker.check().is_ok());
+}
+
+#[test]
+fn test_invalid_steps_are_reported() {
+    let failure = |step: &dyn Fn(&mut LratChecker)| {
+        let mut checker = LratChecker::new();
+        originals(&mut checker);
+        step(&mut checker);
+        checker.conclude_unsat(1, &[6]);
+        assert!(!checker.verified());
+        let error = checker.error().unwrap().clone();
+        match checker.check() {
+            Err(Error::Proof(reported)) => assert_eq!(reported, error),
+            result => panic!("expected proof error, got {result:?}"),
+        }
+        (error.id(), error.message().to_string())
+    };
+
+    // nothing is assigned when deriving the empty clause
+    let (id, message) = failure(&|checker| {
+        checker.add_derived_clause(5, true, &[], &[1, 2]);
+    });
+    assert_eq!((id, message.as_str()), (5, "antecedent 1 is not unit"));
+
+    let (id, message) = failure(&|checker| {
+        checker.add_derived_clause(5, true, &lits(&[1]), &[3, 1]);
+    });
+    assert_eq!((id, message.as_str()), (5, "antecedent 3 is satisfied"));
+
+    let (id, message) = failure(&|checker| {
+        checker.add_derived_clause(5, true, &lits(&[1]), &[1]);
+    });
+    assert_eq!(
+        (id, message.as_str()),
+        (5, "antecedents do not lead to a conflict")
+    );
+
+    let (id, message) = failure(&|checker| {
+        checker.delete_clause(1, false, &lits(&[1, 2]));
+        checker.add_derived_clause(5, true, &lits(&[1]), &[1, 2]);
+    });
+    assert_eq!((id, message.as_str()), (5, "antecedent 1 is unknown"));
+
+    let (id, _) = failure(&|checker| checker.delete_clause(9, false, &[]));
+    assert_eq!(id, 9);
+
+    let (id, _) = failure(&|checker| {
+        checker.add_derived_clause(5, true, &lits(&[1]), &[]);
+    });
+    assert_eq!(id, 5);
+
+    // the conclusion must name the empty clause
+    let (id, message) = failure(&|checker| {
+        checker.add_derived_clause(6, true, &lits(&[1]), &[1, 2]);
+    });
+    assert_eq!(
+        (id, message.as_str()),
+        (
+            6,
+            "concluding clause is neither empty nor an assumption clause"
+        )
+    );
+
+    // nothing concluded yet
+    let mut checker = LratChecker::new();
+    originals(&mut checker);
+    assert!(checker.error().is_none());
+    assert!(matches!(checker.check(), Err(Error::Proof(error)) if error.id() == 0));
+}
+
+#[test]
+fn test_assumption_clauses() {
+    let mut checker = LratChecker::new();
+    originals(&mut checker);
+    checker.add_assumption(Lit::from_dimacs(-1).unwrap());
+    checker.add_assumption_clause(5, &lits(&[1]), &[1, 2]);
+    checker.conclude_unsat(2, &[5]);
+    assert!(checker.check().is_ok());
+
+    // '-1' is no longer assumed
+    checker.reset_assumptions();
+    checker.add_assumption_clause(6, &lits(&[1]), &[1, 2]);
+    assert_eq!(checker.error().unwrap().id(), 6);
+}
+
+#[test]
+fn test_constraint_clauses_contain_constraint_literals() {
+    let mut checker = LratChecker::new();
+    originals(&mut checker);
+    checker.add_constraint(&lits(&[2]));
+    checker.add_assumption_clause(5, &lits(&[2]), &[1, 3]);
+    assert!(checker.error().is_none());
+
+    // the negation of a constraint literal is not in the constraint
+    let mut checker = LratChecker::new();
+    originals(&mut checker);
+    checker.add_constraint(&lits(&[-2]));
+    checker.add_assumption_clause(5, &lits(&[2]), &[1, 3]);
+    assert_eq!(checker.error().unwrap().id(), 5);
+}
+
+#[test]
+fn test_solver_proof_is_verified() {
+    let mut solver = CaDiCal::new();
+    let mut checker = LratChecker::new();
+    let status = solver.with_proof_tracer(&mut checker, true, false, |solver| {
+        add_pigeonhole_cnf(solver, 2);
+        let status = solver.solve();
+        solver.conclude();
+        status
+    });
+    assert_eq!(status, Status::UNSATISFIABLE);
+    checker.check().unwrap();
+
+    // without antecedents derivations cannot be checked
+    let mut solver = CaDiCal::new();
+    let mut checker = LratChecker::new();
+    solver.with_proof_tracer(&mut checker, false, false, |solver| {
+        add_pigeonhole_cnf(solver, 2);
+        solver.solve()
+    });
+    assert!(checker.error().is_some());
+}
+
+#[test]
+fn test_failed_assumptions_are_verified() {
+    let mut solver = CaDiCal::new();
+    let mut checker = LratChecker::new();
+    solver.with_proof_tracer(&mut checker, true, false, |solver| {
+        solver.clause2(-1, 2);
+        solver.clause2(-2, 3);
+        solver.assume(1);
+        solver.assume(-3);
+        assert_eq!(solver.solve(), Status::UNSATISFIABLE);
+        solver.conclude();
+        assert!(solver.failed(1));
+    });
+    checker.check().unwrap();
+}
+
+#[test]
+fn test_satisfiable_conclusion_is_not_verified() {
+    let mut solver = CaDiCal::new();
+    let mut checker = LratChecker::new();
+    solver.with_proof_tracer(&mut checker, true, false, |solver| {
+        solver.clause2(-1, 2);
+        assert_eq!(solver.solve(), Status::SATISFIABLE);
+        solver.conclude();
+    });
+    assert!(!checker.verified());
+    assert!(checker.error().is_none());
+}
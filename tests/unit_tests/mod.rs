@@ -1,3 +1,28 @@
+mod aiger;
+mod builder;
+mod checked;
+mod closure_callbacks;
+mod common;
+mod compression;
+mod dimacs_parser;
+mod dimacs_streams;
+mod drat_checker;
+mod errors;
+mod extension;
 mod external_propagaor;
+mod file_tracers;
+mod files;
 mod fixed_listener;
+mod inccnf;
+mod lit;
+mod lrat_checker;
+mod opb;
+mod owned_callbacks;
+mod panic_safety;
 mod proof_tracer;
+mod proof_writers;
+mod scoped_callbacks;
+mod solution;
+mod state;
+mod terminate_handle;
+mod wcnf;
@@ -0,0 +1,217 @@
+use super::common::parse_error;
+use cadical_sys::{
+    opb::{Constraint, Opb, Relation, Term},
+    CaDiCal, Lit, Status,
+};
+
+fn term(coefficient: i64, lits: &[i32]) -> Term {
+    Term {
+        coefficient,
+        lits: Lit::slice_from_dimacs(lits).unwrap().to_vec(),
+    }
+}
+
+/// Whether 'assignment' of the problem variables satisfies the constraints
+/// by evaluating them directly.
+fn satisfies(opb: &Opb, assignment: u32) -> bool {
+    let val = |lit: Lit| ((assignment >> (lit.var().index() - 1)) & 1 == 1) == lit.is_positive();
+    opb.constraints.iter().all(|constraint| {
+        let sum: i64 = constraint
+            .terms
+            .iter()
+            .filter(|term| term.lits.iter().all(|&lit| val(lit)))
+            .map(|term| term.coefficient)
+            .sum();
+        match constraint.relation {
+            Relation::AtLeast => sum >= constraint.degree,
+            Relation::AtMost => sum <= constraint.degree,
+            Relation::Equal => sum == constraint.degree,
+        }
+    })
+}
+
+#[test]
+fn test_parse_opb() {
+    let text = "* #variable= 3 #constraint= 2\nmin: +1 x1 -2 x2 x3 ;\n+1 x1 +2 ~x2\n >= 1 ;\n-3 x3 x1 = -3;\n";
+    let opb: Opb = text.parse().unwrap();
+    assert_eq!(opb.comments, ["#variable= 3 #constraint= 2"]);
+    assert_eq!(opb.vars(), 3);
+    assert_eq!(opb.var("x3").unwrap().index(), 3);
+    assert_eq!(opb.name(opb.var("x2").unwrap()), Some("x2"));
+    assert_eq!(opb.var("x4"), None);
+    assert_eq!(opb.objective, Some(vec![term(1, &[1]), term(-2, &[2, 3])]));
+    assert_eq!(
+        opb.constraints,
+        [
+            Constraint {
+                terms: vec![term(1, &[1]), term(2, &[-2])],
+                relation: Relation::AtLeast,
+                degree: 1,
+            },
+            Constraint {
+                terms: vec![term(-3, &[3, 1])],
+                relation: Relation::Equal,
+                degree: -3,
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_opb_errors() {
+    assert_eq!(
+        parse_error("+1 x1 >= 1\n+1 x2 >= 1 ;\n".parse::<Opb>()),
+        (2, 1, "expected ';'".to_string())
+    );
+    assert_eq!(
+        parse_error("+1 x1 +2 >= 1 ;\n".parse::<Opb>()),
+        (1, 10, "expected variable".to_string())
+    );
+    assert_eq!(
+        parse_error("+1 1x >= 1 ;\n".parse::<Opb>()),
+        (1, 4, "expected variable".to_string())
+    );
+    assert_eq!(
+        parse_error("+1 x1 ;\n".parse::<Opb>()),
+        (1, 7, "expected term or relation".to_string())
+    );
+    assert_eq!(
+        parse_error("+1 x1 > 1 ;\n".parse::<Opb>()),
+        (1, 7, "expected '>='".to_string())
+    );
+    assert_eq!(
+        parse_error("max: +1 x1 ;\n".parse::<Opb>()),
+        (1, 1, "expected 'min:'".to_string())
+    );
+    assert_eq!(
+        parse_error("min: +1 x1 ;\nmin: +1 x2 ;\n".parse::<Opb>()),
+        (2, 1, "second objective".to_string())
+    );
+    assert_eq!(
+        parse_error("+1 x1 >= 99999999999999999999 ;\n".parse::<Opb>()),
+        (1, 10, "degree too large".to_string())
+    );
+    assert_eq!(
+        parse_error("+9223372036854775807 x1 +1 x2 >= 1 ;\n".parse::<Opb>()),
+        (1, 1, "coefficients too large".to_string())
+    );
+}
+
+#[test]
+fn test_encoding_matches_constraints() {
+    for text in [
+        "+1 x1 +2 x2 +3 x3 >= 3 ;",
+        "+2 x1 +3 ~x2 +1 x3 +4 x4 = 5 ;",
+        "+5 x1 +3 x2 +3 x3 +1 x4 <= 4 ;",
+        "+3 x1 x2 x3 -2 x2 +1 ~x4 = 1 ;",
+        "+1 x1 +1 x2 +1 x3 +1 x4 +1 x5 = 2 ;\n-1 x1 -1 ~x5 >= -1 ;",
+    ] {
+        let opb: Opb = text.parse().unwrap();
+        let mut solver = CaDiCal::new();
+        assert_eq!(opb.add_to(&mut solver), None);
+        for assignment in 0..1u32 << opb.vars() {
+            for var in 1..=opb.vars() {
+                let positive = (assignment >> (var - 1)) & 1 == 1;
+                solver.assume(if positive { var } else { -var });
+            }
+            let expected = if satisfies(&opb, assignment) {
+                Status::SATISFIABLE
+            } else {
+                Status::UNSATISFIABLE
+            };
+            assert_eq!(solver.solve(), expected, "{text} under {assignment:b}");
+        }
+    }
+}
+
+#[test]
+fn test_infeasible_and_trivial_constraints() {
+    let opb: Opb = "+1 x1 +1 x2 >= 3 ;\n".parse().unwrap();
+    let mut solver = CaDiCal::new();
+    opb.add_to(&mut solver);
+    assert_eq!(solver.solve(), Status::UNSATISFIABLE);
+
+    let opb: Opb = "+1 x1 -1 x2 >= -1 ;\n".parse().unwrap();
+    let mut solver = CaDiCal::new();
+    opb.add_to(&mut solver);
+    assert_eq!(solver.vars(), 2);
+    assert_eq!(solver.solve(), Status::SATISFIABLE);
+}
+
+#[test]
+fn test_objective_value() {
+    let problem = "min: +3 x1 +2 x2 +4 x3 x4 ;\n+1 x1 +1 x2 +1 x3 >= 2 ;\n+1 x4 >= 1 ;\n";
+    let opb: Opb = problem.parse().unwrap();
+    let mut solver = CaDiCal::new();
+    let objective = opb.add_to(&mut solver).unwrap();
+    assert_eq!(objective.terms.len(), 3);
+    assert_eq!(solver.solve(), Status::SATISFIABLE);
+    assert!(objective.value(&mut solver) >= 5);
+
+    // the optimum is '5' with 'x1' and 'x2'
+    for (bound, expected) in [(5, Status::SATISFIABLE), (4, Status::UNSATISFIABLE)] {
+        let bounded = format!("{problem}+3 x1 +2 x2 +4 x3 x4 <= {bound} ;\n");
+        let opb: Opb = bounded.parse().unwrap();
+        let mut solver = CaDiCal::new();
+        let objective = opb.add_to(&mut solver).unwrap();
+        assert_eq!(solver.solve(), expected);
+        if expected == Status::SATISFIABLE {
+            assert_eq!(objective.value(&mut solver), 5);
+            assert_eq!(solver.val(opb.var("x2").unwrap().positive()), 2);
+        }
+    }
+}
+
+#[test]
+fn test_large_distinct_coefficients() {
+    // far too many distinct partial sums for a decision diagram
+    let mut seed = 7u64;
+    let coefficients: Vec<i64> = (0..60)
+        .map(|_| {
+            seed = seed.wrapping_mul(6_364_136_223_846_793_005).wrapping_add(1);
+            1_000_000_000 + i64::try_from(seed >> 40).unwrap()
+        })
+        .collect();
+    let terms: Vec<String> = coefficients
+        .iter()
+        .enumerate()
+        .map(|(i, c)| format!("+{c} x{}", i + 1))
+        .collect();
+    let terms = terms.join(" ");
+    let half = coefficients.iter().sum::<i64>() / 2;
+    let problem = format!(
+        "{terms} >= {half} ;\n{terms} <= {} ;\n",
+        half + 3_000_000_000
+    );
+    let opb: Opb = problem.parse().unwrap();
+    let mut solver = CaDiCal::new();
+    opb.add_to(&mut solver);
+
+    let sum = |solver: &mut CaDiCal| -> i64 {
+        (1..=60)
+            .filter(|&var| solver.value(var))
+            .map(|var| coefficients[usize::try_from(var - 1).unwrap()])
+            .sum()
+    };
+    assert_eq!(solver.solve(), Status::SATISFIABLE);
+    let model = sum(&mut solver);
+    assert!(model >= half && model <= half + 3_000_000_000);
+
+    // complete assignments are accepted exactly if they satisfy both
+    for _ in 0..50 {
+        seed = seed.wrapping_mul(6_364_136_223_846_793_005).wrapping_add(1);
+        let mut expected = 0;
+        for (i, &coefficient) in coefficients.iter().enumerate() {
+            let var = i32::try_from(i + 1).unwrap();
+            if seed >> (i + 2) & 1 == 1 {
+                expected += coefficient;
+                solver.assume(var);
+            } else {
+                solver.assume(-var);
+            }
+        }
+        let satisfied = expected >= half && expected <= half + 3_000_000_000;
+        let status = solver.solve();
+        assert_eq!(status == Status::SATISFIABLE, satisfied);
+    }
+}
@@ -0,0 +1,187 @@
+use cadical_sys::{CaDiCal, FixedAssignmentListener, Lit, ProofTracer, Status, Terminator};
+
+struct AlwaysTerminate;
+
+impl Terminator for AlwaysTerminate {
+    fn terminated(&mut self) -> bool {
+        true
+    }
+}
+
+#[derive(Default)]
+struct RecordingListener {
+    fixed: Vec<i32>,
+}
+
+impl FixedAssignmentListener for RecordingListener {
+    fn notify_fixed_assignment(&mut self, lit: Lit) {
+        self.fixed.push(lit.to_dimacs());
+    }
+}
+
+/// Records the clauses of a run, without antecedents, which only some
+/// tracers ask for.
+#[derive(Default, Debug, PartialEq)]
+struct RecordingTracer {
+    original: Vec<(u64, Vec<i32>)>,
+    derived: Vec<(u64, Vec<i32>)>,
+    deleted: Vec<u64>,
+    unsat: bool,
+}
+
+impl ProofTracer for RecordingTracer {
+    fn add_original_clause(&mut self, id: u64, _: bool, clause: &[Lit], _: bool) {
+        self.original
+            .push((id, Lit::slice_to_dimacs(clause).to_vec()));
+    }
+    fn add_derived_clause(&mut self, id: u64, _: bool, clause: &[Lit], _: &[u64]) {
+        self.derived
+            .push((id, Lit::slice_to_dimacs(clause).to_vec()));
+    }
+    fn delete_clause(&mut self, id: u64, _: bool, _: &[Lit]) {
+        self.deleted.push(id);
+    }
+    fn weaken_minus(&mut self, _: u64, _: &[Lit]) {}
+    fn strengthen(&mut self, _: u64) {}
+    fn finalize_clause(&mut self, _: u64, _: &[Lit]) {}
+    fn add_assumption(&mut self, _: Lit) {}
+    fn add_constraint(&mut self, _: &[Lit]) {}
+    fn reset_assumptions(&mut self) {}
+    fn add_assumption_clause(&mut self, _: u64, _: &[Lit], _: &[u64]) {}
+    fn conclude_sat(&mut self, _: i32, _: &[Lit]) {}
+    fn conclude_unsat(&mut self, _: i32, _: &[u64]) {
+        self.unsat = true;
+    }
+    fn conclude_unknown(&mut self, _: &[Lit]) {}
+}
+
+/// Owns a solver together with its call-backs, which the borrowed forms
+/// cannot express.
+struct Service {
+    solver: CaDiCal,
+}
+
+impl Service {
+    fn new() -> Self {
+        let mut solver = CaDiCal::new();
+        solver.connect_terminator(Box::new(AlwaysTerminate));
+        Self { solver }
+    }
+}
+
+#[test]
+fn test_owned_terminator_lives_with_solver() {
+    let mut service = Service::new();
+    for pigeon in 0..8 {
+        let clause: Vec<i32> = (0..7).map(|hole| pigeon * 7 + hole + 1).collect();
+        service.solver.clause6(&clause);
+    }
+    for hole in 0..7 {
+        for p1 in 0..8 {
+            for p2 in (p1 + 1)..8 {
+                service
+                    .solver
+                    .clause2(-(p1 * 7 + hole + 1), -(p2 * 7 + hole + 1));
+            }
+        }
+    }
+
+    assert_eq!(service.solver.solve(), Status::UNKNOWN);
+    let terminator = service.solver.disconnect_terminator().unwrap();
+    assert!(terminator.downcast::<AlwaysTerminate>().is_ok());
+    assert_eq!(service.solver.solve(), Status::UNSATISFIABLE);
+}
+
+#[test]
+fn test_disconnect_without_connection_returns_none() {
+    let mut solver = CaDiCal::new();
+    assert!(solver.disconnect_terminator().is_none());
+    assert!(solver.disconnect_learner().is_none());
+    assert!(solver.disconnect_fixed_listener().is_none());
+    assert!(solver.disconnect_external_propagator().is_none());
+    // ids of another solver are not connected here
+    let id = CaDiCal::new().connect_proof_tracer1(Box::<RecordingTracer>::default(), false, false);
+    assert!(solver.disconnect_proof_tracer1(id).is_none());
+}
+
+#[test]
+fn test_borrowed_connection_hands_nothing_back() {
+    let mut solver = CaDiCal::new();
+    let mut terminator = AlwaysTerminate;
+    let returned = solver.with_terminator(&mut terminator, CaDiCal::disconnect_terminator);
+    assert!(returned.is_none());
+}
+
+#[test]
+fn test_owned_fixed_listener_is_handed_back() {
+    let mut solver = CaDiCal::new();
+    solver.connect_fixed_listener(Box::<RecordingListener>::default());
+    solver.clause6(&[1]);
+    solver.clause6(&[-2]);
+    assert_eq!(solver.solve(), Status::SATISFIABLE);
+
+    let listener = solver.disconnect_fixed_listener().unwrap();
+    let listener = listener.downcast::<RecordingListener>().ok().unwrap();
+    assert_eq!(listener.fixed, [1, -2]);
+    assert!(solver.disconnect_fixed_listener().is_none());
+}
+
+#[test]
+fn test_owned_proof_tracer_is_handed_back() {
+    let mut solver = CaDiCal::new();
+    let id = solver.connect_proof_tracer1(Box::<RecordingTracer>::default(), false, false);
+    solver.clause6(&[1, 2]);
+    solver.clause6(&[-1]);
+    assert_eq!(solver.solve(), Status::SATISFIABLE);
+
+    let tracer = solver.disconnect_proof_tracer1(id).unwrap();
+    // the box is handed back as it was connected, not boxed again
+    assert!(!tracer.is::<Box<RecordingTracer>>());
+    let Err(tracer) = tracer.downcast::<Box<RecordingTracer>>() else {
+        panic!("tracer handed back boxed twice");
+    };
+    let tracer = tracer.downcast::<RecordingTracer>().ok().unwrap();
+    assert_eq!(tracer.original, [(1, vec![1, 2]), (2, vec![-1])]);
+    assert!(solver.disconnect_proof_tracer1(id).is_none());
+}
+
+#[test]
+fn test_proof_tracers_are_disconnected_individually() {
+    let mut solver = CaDiCal::new();
+    let first = solver.connect_proof_tracer1(Box::<RecordingTracer>::default(), false, false);
+    let second = solver.connect_proof_tracer1(Box::<RecordingTracer>::default(), true, false);
+    assert!(first < second);
+    assert_eq!(solver.proof_tracers(), [first, second]);
+    solver.clause6(&[1, 2]);
+    solver.clause6(&[-1, 2]);
+    solver.clause6(&[1, -2]);
+    solver.clause6(&[-1, -2]);
+    assert_eq!(solver.solve(), Status::UNSATISFIABLE);
+    solver.conclude();
+
+    let first_tracer = solver.disconnect_proof_tracer1(first).unwrap();
+    assert_eq!(solver.proof_tracers(), [second]);
+    assert!(solver.disconnect_proof_tracer1(first).is_none());
+    // the second tracer still sees clauses added after the first left
+    solver.clause6(&[3]);
+    let second_tracer = solver.disconnect_proof_tracer1(second).unwrap();
+    assert!(solver.proof_tracers().is_empty());
+
+    let first_tracer = first_tracer.downcast::<RecordingTracer>().ok().unwrap();
+    let mut second_tracer = second_tracer.downcast::<RecordingTracer>().ok().unwrap();
+    assert_eq!(first_tracer.original.len(), 4);
+    assert!(!first_tracer.derived.is_empty());
+    assert!(first_tracer.unsat);
+    assert_eq!(second_tracer.original.pop().unwrap().1, [3]);
+    assert_eq!(first_tracer, second_tracer);
+}
+
+#[test]
+fn test_solver_drops_owned_callbacks() {
+    let mut solver = CaDiCal::new();
+    solver.connect_terminator(Box::new(AlwaysTerminate));
+    solver.connect_fixed_listener(Box::<RecordingListener>::default());
+    solver.connect_proof_tracer1(Box::<RecordingTracer>::default(), true, false);
+    solver.clause6(&[1]);
+    drop(solver);
+}
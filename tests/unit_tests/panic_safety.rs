@@ -0,0 +1,162 @@
+use super::common::add_pigeonhole_cnf;
+use cadical_sys::{CaDiCal, ClauseIterator, ExternalPropagator, Learner, Lit, ProofTracer, Status};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> &str {
+    payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or_default()
+}
+
+struct PanickingLearner {
+    calls: usize,
+}
+
+impl Learner for PanickingLearner {
+    fn learning(&mut self, _: i32) -> bool {
+        self.calls += 1;
+        panic!("learner failed");
+    }
+    fn learn(&mut self, _: Option<Lit>) {}
+}
+
+struct BacktrackingPropagator;
+
+impl ExternalPropagator for BacktrackingPropagator {
+    fn notify_assignment(&mut self, _: &[Lit]) {}
+    fn notify_new_decision_level(&mut self) {}
+    fn notify_backtrack(&mut self, _: usize) {
+        todo!()
+    }
+    fn cb_check_found_model(&mut self, _: &[Lit]) -> bool {
+        true
+    }
+    fn cb_has_external_clause(&mut self, _: &mut bool) -> bool {
+        false
+    }
+    fn cb_add_external_clause_lit(&mut self) -> Option<Lit> {
+        None
+    }
+}
+
+struct PanickingIterator;
+
+impl ClauseIterator for PanickingIterator {
+    fn clause(&mut self, _: &[Lit]) -> bool {
+        panic!("iterator failed");
+    }
+}
+
+#[test]
+fn test_terminator_panic_is_raised_by_solve() {
+    let mut solver = CaDiCal::new();
+    solver.connect_terminator_fn(|| panic!("terminator failed"));
+    add_pigeonhole_cnf(&mut solver, 5);
+
+    let caught = catch_unwind(AssertUnwindSafe(|| solver.solve())).unwrap_err();
+    assert_eq!(panic_message(&*caught), "terminator failed");
+
+    solver.disconnect_terminator();
+    assert_eq!(solver.solve(), Status::UNSATISFIABLE);
+}
+
+#[test]
+fn test_learner_panic_stops_search_once() {
+    let mut solver = CaDiCal::new();
+    let mut learner = PanickingLearner { calls: 0 };
+    add_pigeonhole_cnf(&mut solver, 6);
+
+    let caught = catch_unwind(AssertUnwindSafe(|| {
+        solver.with_learner(&mut learner, CaDiCal::solve)
+    }))
+    .unwrap_err();
+    assert_eq!(panic_message(&*caught), "learner failed");
+    assert_eq!(learner.calls, 1, "No call-back should run after a panic");
+
+    assert_eq!(solver.solve(), Status::UNSATISFIABLE);
+}
+
+#[test]
+fn test_propagator_panic_is_raised_by_solve() {
+    let mut solver = CaDiCal::new();
+    let mut propagator = BacktrackingPropagator;
+    add_pigeonhole_cnf(&mut solver, 5);
+
+    let caught = catch_unwind(AssertUnwindSafe(|| {
+        solver.with_external_propagator(&mut propagator, |solver| {
+            for var in 1..=30 {
+                solver.add_observed_var(var);
+            }
+            solver.solve()
+        })
+    }))
+    .unwrap_err();
+    assert_eq!(panic_message(&*caught), "not yet implemented");
+}
+
+#[test]
+fn test_iterator_panic_is_raised_by_traversal() {
+    let mut solver = CaDiCal::new();
+    solver.clause2(1, 2);
+
+    let caught = catch_unwind(AssertUnwindSafe(|| {
+        solver.traverse_clauses(&mut PanickingIterator)
+    }))
+    .unwrap_err();
+    assert_eq!(panic_message(&*caught), "iterator failed");
+}
+
+/// Panics on the first original clause.
+#[derive(Default)]
+struct PanickingTracer {
+    calls: usize,
+}
+
+impl ProofTracer for PanickingTracer {
+    fn add_original_clause(&mut self, _: u64, _: bool, _: &[Lit], _: bool) {
+        self.calls += 1;
+        panic!("tracer failed");
+    }
+    fn add_derived_clause(&mut self, _: u64, _: bool, _: &[Lit], _: &[u64]) {}
+    fn delete_clause(&mut self, _: u64, _: bool, _: &[Lit]) {}
+    fn weaken_minus(&mut self, _: u64, _: &[Lit]) {}
+    fn strengthen(&mut self, _: u64) {}
+    fn finalize_clause(&mut self, _: u64, _: &[Lit]) {}
+    fn add_assumption(&mut self, _: Lit) {}
+    fn add_constraint(&mut self, _: &[Lit]) {}
+    fn reset_assumptions(&mut self) {}
+    fn add_assumption_clause(&mut self, _: u64, _: &[Lit], _: &[u64]) {}
+    fn conclude_sat(&mut self, _: i32, _: &[Lit]) {}
+    fn conclude_unsat(&mut self, _: i32, _: &[u64]) {}
+    fn conclude_unknown(&mut self, _: &[Lit]) {}
+}
+
+#[test]
+fn test_tracer_panic_is_raised_by_add() {
+    let mut solver = CaDiCal::new();
+    let mut tracer = PanickingTracer::default();
+    let caught = catch_unwind(AssertUnwindSafe(|| {
+        solver.with_proof_tracer(&mut tracer, false, false, |solver| {
+            solver.clause2(1, 2);
+            unreachable!("the clause raises the panic");
+        });
+    }))
+    .unwrap_err();
+    assert_eq!(panic_message(&*caught), "tracer failed");
+    assert_eq!(tracer.calls, 1);
+
+    // nothing is left parked to stop the next search
+    solver.clause1(-1);
+    assert_eq!(solver.solve(), Status::SATISFIABLE);
+
+    // the same for an owned tracer, which stays connected
+    let mut solver = CaDiCal::new();
+    let id = solver.connect_proof_tracer1(Box::<PanickingTracer>::default(), false, false);
+    solver.add(1);
+    let caught = catch_unwind(AssertUnwindSafe(|| solver.add(0))).unwrap_err();
+    assert_eq!(panic_message(&*caught), "tracer failed");
+    assert!(solver.disconnect_proof_tracer1(id).is_some());
+    assert_eq!(solver.solve(), Status::SATISFIABLE);
+}
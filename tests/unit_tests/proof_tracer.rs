@@ -1,4 +1,4 @@
-use cadical_sys::{CaDiCal, ProofTracer, Status};
+use cadical_sys::{CaDiCal, Lit, ProofTracer, Status};
 
 /// Test implementation of `ProofTracer` that tracks all method calls
 struct TestProofTracer {
@@ -106,57 +106,72 @@ impl TestProofTracer {
 }
 
 impl ProofTracer for TestProofTracer {
-    fn add_original_clause(&mut self, id: u64, redundant: bool, clause: &[i32], restored: bool) {
-        self.original_clauses
-            .push((id, redundant, clause.to_vec(), restored));
+    fn add_original_clause(&mut self, id: u64, redundant: bool, clause: &[Lit], restored: bool) {
+        self.original_clauses.push((
+            id,
+            redundant,
+            Lit::slice_to_dimacs(clause).to_vec(),
+            restored,
+        ));
     }
 
     fn add_derived_clause(
         &mut self,
         id: u64,
         redundant: bool,
-        clause: &[i32],
+        clause: &[Lit],
         antecedents: &[u64],
     ) {
-        self.derived_clauses
-            .push((id, redundant, clause.to_vec(), antecedents.to_vec()));
+        self.derived_clauses.push((
+            id,
+            redundant,
+            Lit::slice_to_dimacs(clause).to_vec(),
+            antecedents.to_vec(),
+        ));
     }
 
-    fn delete_clause(&mut self, id: u64, redundant: bool, clause: &[i32]) {
-        self.deleted_clauses.push((id, redundant, clause.to_vec()));
+    fn delete_clause(&mut self, id: u64, redundant: bool, clause: &[Lit]) {
+        self.deleted_clauses
+            .push((id, redundant, Lit::slice_to_dimacs(clause).to_vec()));
     }
 
-    fn weaken_minus(&mut self, id: u64, clause: &[i32]) {
-        self.weakened_clauses.push((id, clause.to_vec()));
+    fn weaken_minus(&mut self, id: u64, clause: &[Lit]) {
+        self.weakened_clauses
+            .push((id, Lit::slice_to_dimacs(clause).to_vec()));
     }
 
     fn strengthen(&mut self, id: u64) {
         self.strengthened_clauses.push(id);
     }
 
-    fn finalize_clause(&mut self, id: u64, clause: &[i32]) {
-        self.finalized_clauses.push((id, clause.to_vec()));
+    fn finalize_clause(&mut self, id: u64, clause: &[Lit]) {
+        self.finalized_clauses
+            .push((id, Lit::slice_to_dimacs(clause).to_vec()));
     }
 
-    fn add_assumption(&mut self, lit: i32) {
-        self.assumptions.push(lit);
+    fn add_assumption(&mut self, lit: Lit) {
+        self.assumptions.push(lit.to_dimacs());
     }
 
-    fn add_constraint(&mut self, clause: &[i32]) {
-        self.constraints.push(clause.to_vec());
+    fn add_constraint(&mut self, clause: &[Lit]) {
+        self.constraints.push(Lit::slice_to_dimacs(clause).to_vec());
     }
 
     fn reset_assumptions(&mut self) {
         self.assumption_resets += 1;
     }
 
-    fn add_assumption_clause(&mut self, id: u64, clause: &[i32], antecedents: &[u64]) {
-        self.assumption_clauses
-            .push((id, clause.to_vec(), antecedents.to_vec()));
+    fn add_assumption_clause(&mut self, id: u64, clause: &[Lit], antecedents: &[u64]) {
+        self.assumption_clauses.push((
+            id,
+            Lit::slice_to_dimacs(clause).to_vec(),
+            antecedents.to_vec(),
+        ));
     }
 
-    fn conclude_sat(&mut self, conclusion_type: i32, model: &[i32]) {
-        self.sat_conclusions.push((conclusion_type, model.to_vec()));
+    fn conclude_sat(&mut self, conclusion_type: i32, model: &[Lit]) {
+        self.sat_conclusions
+            .push((conclusion_type, Lit::slice_to_dimacs(model).to_vec()));
     }
 
     fn conclude_unsat(&mut self, conclusion_type: i32, clause_ids: &[u64]) {
@@ -164,8 +179,9 @@ impl ProofTracer for TestProofTracer {
             .push((conclusion_type, clause_ids.to_vec()));
     }
 
-    fn conclude_unknown(&mut self, trail: &[i32]) {
-        self.unknown_conclusions.push(trail.to_vec());
+    fn conclude_unknown(&mut self, trail: &[Lit]) {
+        self.unknown_conclusions
+            .push(Lit::slice_to_dimacs(trail).to_vec());
     }
 }
 
@@ -189,26 +205,47 @@ fn add_unit_clauses_cnf(solver: &mut CaDiCal) {
     solver.clause6(&[3]);
 }
 
+/// The literals of the original clauses seen by the tracer
+fn original_literals(tracer: &TestProofTracer) -> Vec<Vec<i32>> {
+    tracer
+        .get_original_clauses()
+        .iter()
+        .map(|(_, _, clause, _)| clause.clone())
+        .collect()
+}
+
+/// The clauses of '`add_sat_test_cnf`'
+fn sat_test_cnf() -> Vec<Vec<i32>> {
+    vec![vec![-1, 3], vec![-2, 3], vec![1, 2, -3]]
+}
+
+/// Checks that the tracer did not see any proof step
+fn assert_nothing_traced(tracer: &TestProofTracer) {
+    assert!(tracer.get_original_clauses().is_empty());
+    assert!(tracer.get_derived_clauses().is_empty());
+    assert!(tracer.get_deleted_clauses().is_empty());
+    assert!(tracer.get_assumptions().is_empty());
+}
+
 #[test]
 fn test_proof_tracer_connect_and_disconnect() {
     let mut solver = CaDiCal::new();
     let mut proof_tracer = TestProofTracer::new();
-    solver.connect_proof_tracer1(&mut proof_tracer, true);
-    solver.disconnect_proof_tracer1();
+    solver.with_proof_tracer(&mut proof_tracer, true, false, |_| {});
+    assert_nothing_traced(&proof_tracer);
 }
 
 #[test]
 fn test_proof_tracer_flow_solve_after_connect_disconnect() {
     let mut solver = CaDiCal::new();
     let mut proof_tracer = TestProofTracer::new();
-    solver.connect_proof_tracer1(&mut proof_tracer, true);
-    solver.disconnect_proof_tracer1();
+    solver.with_proof_tracer(&mut proof_tracer, true, false, |_| {});
 
     // Add clauses after disconnecting the tracer
     add_sat_test_cnf(&mut solver);
     let result = solver.solve();
     assert_eq!(result, Status::SATISFIABLE);
-    solver.disconnect_proof_tracer1();
+    assert!(solver.proof_tracers().is_empty());
 }
 
 #[test]
@@ -217,17 +254,19 @@ fn test_proof_tracer_sat_case() {
     let mut proof_tracer = TestProofTracer::new();
 
     // Connect tracer before adding any clauses
-    solver.connect_proof_tracer1(&mut proof_tracer, true);
-    add_sat_test_cnf(&mut solver);
+    solver.with_proof_tracer(&mut proof_tracer, true, false, |solver| {
+        add_sat_test_cnf(solver);
 
-    // Just test that we can add clauses without crashing
-    // The actual solving might cause issues due to the unsafe pointer operations
-    let result = solver.solve();
-    assert_eq!(result, Status::SATISFIABLE);
-    solver.disconnect_proof_tracer1();
+        let result = solver.solve();
+        assert_eq!(result, Status::SATISFIABLE);
+    });
 
-    // Note: We can't safely check the tracer state here due to the unsafe pointer operations
-    // The proof tracer functionality is complex and requires careful lifetime management
+    assert_eq!(original_literals(&proof_tracer), sat_test_cnf());
+    assert!(proof_tracer
+        .get_original_clauses()
+        .iter()
+        .all(|&(_, redundant, _, restored)| !redundant && !restored));
+    assert!(proof_tracer.get_unsat_conclusions().is_empty());
 }
 
 #[test]
@@ -236,15 +275,21 @@ fn test_proof_tracer_unsat_case() {
     let mut proof_tracer = TestProofTracer::new();
 
     // Connect tracer before adding any clauses
-    solver.connect_proof_tracer1(&mut proof_tracer, true);
-    add_unsat_test_cnf(&mut solver);
+    solver.with_proof_tracer(&mut proof_tracer, true, false, |solver| {
+        add_unsat_test_cnf(solver);
 
-    let result = solver.solve();
-    assert_eq!(result, Status::UNSATISFIABLE);
-    solver.disconnect_proof_tracer1();
+        let result = solver.solve();
+        assert_eq!(result, Status::UNSATISFIABLE);
+    });
 
-    // Note: We can't safely check the tracer state here due to the unsafe pointer operations
-    // The proof tracer functionality is complex and requires careful lifetime management
+    assert_eq!(original_literals(&proof_tracer), [vec![1], vec![-1]]);
+    // the empty clause follows from both units
+    let empty = proof_tracer
+        .get_derived_clauses()
+        .iter()
+        .find(|(_, _, clause, _)| clause.is_empty())
+        .expect("empty clause derived");
+    assert!(!empty.3.is_empty());
 }
 
 #[test]
@@ -253,15 +298,17 @@ fn test_proof_tracer_unit_clauses() {
     let mut proof_tracer = TestProofTracer::new();
 
     // Connect tracer before adding any clauses
-    solver.connect_proof_tracer1(&mut proof_tracer, true);
-    add_unit_clauses_cnf(&mut solver);
+    solver.with_proof_tracer(&mut proof_tracer, true, false, |solver| {
+        add_unit_clauses_cnf(solver);
 
-    let result = solver.solve();
-    assert_eq!(result, Status::SATISFIABLE);
-    solver.disconnect_proof_tracer1();
+        let result = solver.solve();
+        assert_eq!(result, Status::SATISFIABLE);
+    });
 
-    // Note: We can't safely check the tracer state here due to the unsafe pointer operations
-    // The proof tracer functionality is complex and requires careful lifetime management
+    assert_eq!(
+        original_literals(&proof_tracer),
+        [vec![1], vec![-2], vec![3]]
+    );
 }
 
 #[test]
@@ -270,16 +317,14 @@ fn test_proof_tracer_disconnect_prevents_notifications() {
     let mut proof_tracer = TestProofTracer::new();
 
     // Connect and immediately disconnect tracer
-    solver.connect_proof_tracer1(&mut proof_tracer, true);
-    solver.disconnect_proof_tracer1();
+    solver.with_proof_tracer(&mut proof_tracer, true, false, |_| {});
 
     // Add clauses after disconnecting
     add_sat_test_cnf(&mut solver);
     let result = solver.solve();
     assert_eq!(result, Status::SATISFIABLE);
 
-    // Note: We can't safely check the tracer state here due to the unsafe pointer operations
-    // The proof tracer functionality is complex and requires careful lifetime management
+    assert_nothing_traced(&proof_tracer);
 }
 
 #[test]
@@ -288,17 +333,15 @@ fn test_proof_tracer_reconnect() {
     let mut proof_tracer = TestProofTracer::new();
 
     // Connect, disconnect, then reconnect before adding clauses
-    solver.connect_proof_tracer1(&mut proof_tracer, true);
-    solver.disconnect_proof_tracer1();
-    solver.connect_proof_tracer1(&mut proof_tracer, true);
-
-    add_sat_test_cnf(&mut solver);
-    let result = solver.solve();
-    assert_eq!(result, Status::SATISFIABLE);
-    solver.disconnect_proof_tracer1();
-
-    // Note: We can't safely check the tracer state here due to the unsafe pointer operations
-    // The proof tracer functionality is complex and requires careful lifetime management
+    solver.with_proof_tracer(&mut proof_tracer, true, false, |_| {});
+    solver.with_proof_tracer(&mut proof_tracer, true, false, |solver| {
+        add_sat_test_cnf(solver);
+        let result = solver.solve();
+        assert_eq!(result, Status::SATISFIABLE);
+    });
+
+    // only the second connection saw clauses
+    assert_eq!(original_literals(&proof_tracer), sat_test_cnf());
 }
 
 #[test]
@@ -307,15 +350,13 @@ fn test_proof_tracer_empty_cnf() {
     let mut proof_tracer = TestProofTracer::new();
 
     // Connect tracer before solving
-    solver.connect_proof_tracer1(&mut proof_tracer, true);
+    solver.with_proof_tracer(&mut proof_tracer, true, false, |solver| {
+        // Solve with no clauses (empty CNF)
+        let result = solver.solve();
+        assert_eq!(result, Status::SATISFIABLE);
+    });
 
-    // Solve with no clauses (empty CNF)
-    let result = solver.solve();
-    assert_eq!(result, Status::SATISFIABLE);
-    solver.disconnect_proof_tracer1();
-
-    // Note: We can't safely check the tracer state here due to the unsafe pointer operations
-    // The proof tracer functionality is complex and requires careful lifetime management
+    assert_nothing_traced(&proof_tracer);
 }
 
 #[test]
@@ -324,15 +365,18 @@ fn test_proof_tracer_with_assumptions() {
     let mut proof_tracer = TestProofTracer::new();
 
     // Connect tracer before adding clauses
-    solver.connect_proof_tracer1(&mut proof_tracer, true);
-    add_sat_test_cnf(&mut solver);
-
-    // Add assumptions
-    solver.assume(1);
-    solver.assume(2);
-    let result = solver.solve();
-    assert_eq!(result, Status::SATISFIABLE);
-    solver.disconnect_proof_tracer1();
+    solver.with_proof_tracer(&mut proof_tracer, true, false, |solver| {
+        add_sat_test_cnf(solver);
+
+        // Add assumptions
+        solver.assume(1);
+        solver.assume(2);
+        let result = solver.solve();
+        assert_eq!(result, Status::SATISFIABLE);
+    });
+
+    assert_eq!(original_literals(&proof_tracer), sat_test_cnf());
+    assert_eq!(proof_tracer.get_assumptions(), [1, 2]);
 }
 
 #[test]
@@ -341,14 +385,22 @@ fn test_proof_tracer_antecedents_parameter() {
     let mut proof_tracer = TestProofTracer::new();
 
     // Test with antecedents = true, connect before adding clauses
-    solver.connect_proof_tracer1(&mut proof_tracer, true);
-    add_sat_test_cnf(&mut solver);
-    let result = solver.solve();
-    assert_eq!(result, Status::SATISFIABLE);
-
-    // Clear and test with antecedents = false
+    solver.with_proof_tracer(&mut proof_tracer, true, false, |solver| {
+        add_sat_test_cnf(solver);
+        let result = solver.solve();
+        assert_eq!(result, Status::SATISFIABLE);
+    });
+    assert_eq!(original_literals(&proof_tracer), sat_test_cnf());
+
+    // Clear and test with antecedents = false on a new solver
     proof_tracer.clear();
-    solver.disconnect_proof_tracer1();
+    let mut solver = CaDiCal::new();
+    solver.with_proof_tracer(&mut proof_tracer, false, false, |solver| {
+        add_sat_test_cnf(solver);
+        let result = solver.solve();
+        assert_eq!(result, Status::SATISFIABLE);
+    });
+    assert_eq!(original_literals(&proof_tracer), sat_test_cnf());
 }
 
 #[test]
@@ -357,13 +409,21 @@ fn test_proof_tracer_multiple_solves() {
     let mut proof_tracer = TestProofTracer::new();
 
     // Connect tracer before adding clauses
-    solver.connect_proof_tracer1(&mut proof_tracer, true);
-
-    // First solve
-    add_sat_test_cnf(&mut solver);
-    let result1 = solver.solve();
-    assert_eq!(result1, Status::SATISFIABLE);
-    solver.disconnect_proof_tracer1();
+    solver.with_proof_tracer(&mut proof_tracer, true, false, |solver| {
+        // First solve
+        add_sat_test_cnf(solver);
+        let result1 = solver.solve();
+        assert_eq!(result1, Status::SATISFIABLE);
+
+        // Second solve with a unit
+        solver.clause6(&[-3]);
+        let result2 = solver.solve();
+        assert_eq!(result2, Status::SATISFIABLE);
+    });
+
+    let mut expected = sat_test_cnf();
+    expected.push(vec![-3]);
+    assert_eq!(original_literals(&proof_tracer), expected);
 }
 
 #[test]
@@ -372,19 +432,22 @@ fn test_proof_tracer_reset_assumptions() {
     let mut proof_tracer = TestProofTracer::new();
 
     // Connect tracer before adding clauses
-    solver.connect_proof_tracer1(&mut proof_tracer, true);
-    add_sat_test_cnf(&mut solver);
-
-    // Add assumptions and solve
-    solver.assume(1);
-    let result1 = solver.solve();
-    assert_eq!(result1, Status::SATISFIABLE);
-
-    // Reset assumptions
-    solver.reset_assumptions();
-    let result2 = solver.solve();
-    assert_eq!(result2, Status::SATISFIABLE);
-    solver.disconnect_proof_tracer1();
+    solver.with_proof_tracer(&mut proof_tracer, true, false, |solver| {
+        add_sat_test_cnf(solver);
+
+        // Add assumptions and solve
+        solver.assume(1);
+        let result1 = solver.solve();
+        assert_eq!(result1, Status::SATISFIABLE);
+
+        // Reset assumptions
+        solver.reset_assumptions();
+        let result2 = solver.solve();
+        assert_eq!(result2, Status::SATISFIABLE);
+    });
+
+    assert_eq!(proof_tracer.get_assumptions(), [1]);
+    assert!(proof_tracer.get_assumption_resets() > 0);
 }
 
 // ============================================================================
@@ -725,12 +788,12 @@ fn test_proof_tracer_validation_sat_case() {
     let mut proof_tracer = TestProofTracer::new();
 
     // Connect tracer before adding clauses
-    solver.connect_proof_tracer1(&mut proof_tracer, true);
-    add_sat_test_cnf(&mut solver);
+    solver.with_proof_tracer(&mut proof_tracer, true, false, |solver| {
+        add_sat_test_cnf(solver);
 
-    let result = solver.solve();
-    assert_eq!(result, Status::SATISFIABLE);
-    solver.disconnect_proof_tracer1();
+        let result = solver.solve();
+        assert_eq!(result, Status::SATISFIABLE);
+    });
 
     // Validate the proof trace
     validate_proof_trace(&proof_tracer);
@@ -742,12 +805,12 @@ fn test_proof_tracer_validation_unsat_case() {
     let mut proof_tracer = TestProofTracer::new();
 
     // Connect tracer before adding clauses
-    solver.connect_proof_tracer1(&mut proof_tracer, true);
-    add_unsat_test_cnf(&mut solver);
+    solver.with_proof_tracer(&mut proof_tracer, true, false, |solver| {
+        add_unsat_test_cnf(solver);
 
-    let result = solver.solve();
-    assert_eq!(result, Status::UNSATISFIABLE);
-    solver.disconnect_proof_tracer1();
+        let result = solver.solve();
+        assert_eq!(result, Status::UNSATISFIABLE);
+    });
 
     // Validate the proof trace
     validate_proof_trace(&proof_tracer);
@@ -759,12 +822,12 @@ fn test_proof_tracer_validation_unit_clauses() {
     let mut proof_tracer = TestProofTracer::new();
 
     // Connect tracer before adding clauses
-    solver.connect_proof_tracer1(&mut proof_tracer, true);
-    add_unit_clauses_cnf(&mut solver);
+    solver.with_proof_tracer(&mut proof_tracer, true, false, |solver| {
+        add_unit_clauses_cnf(solver);
 
-    let result = solver.solve();
-    assert_eq!(result, Status::SATISFIABLE);
-    solver.disconnect_proof_tracer1();
+        let result = solver.solve();
+        assert_eq!(result, Status::SATISFIABLE);
+    });
 
     // Validate the proof trace
     validate_proof_trace(&proof_tracer);
@@ -776,15 +839,15 @@ fn test_proof_tracer_validation_with_assumptions() {
     let mut proof_tracer = TestProofTracer::new();
 
     // Connect tracer before adding clauses
-    solver.connect_proof_tracer1(&mut proof_tracer, true);
-    add_sat_test_cnf(&mut solver);
+    solver.with_proof_tracer(&mut proof_tracer, true, false, |solver| {
+        add_sat_test_cnf(solver);
 
-    // Add assumptions
-    solver.assume(1);
-    solver.assume(2);
-    let result = solver.solve();
-    assert_eq!(result, Status::SATISFIABLE);
-    solver.disconnect_proof_tracer1();
+        // Add assumptions
+        solver.assume(1);
+        solver.assume(2);
+        let result = solver.solve();
+        assert_eq!(result, Status::SATISFIABLE);
+    });
 
     // Validate the proof trace
     validate_proof_trace(&proof_tracer);
@@ -796,19 +859,18 @@ fn test_proof_tracer_validation_complex_cnf() {
     let mut proof_tracer = TestProofTracer::new();
 
     // Connect tracer before adding clauses
-    solver.connect_proof_tracer1(&mut proof_tracer, true);
-
-    // Add a more complex CNF that should generate derived clauses
-    solver.clause6(&[2, 4, -6]);
-    solver.clause6(&[3, 5]);
-    solver.clause6(&[-4, -5]);
-    solver.clause6(&[-2, -5]);
-    solver.clause6(&[6, -5]);
-    solver.clause6(&[-3]);
-
-    let result = solver.solve();
-    assert!(result == Status::UNSATISFIABLE);
-    solver.disconnect_proof_tracer1();
+    solver.with_proof_tracer(&mut proof_tracer, true, false, |solver| {
+        // Add a more complex CNF that should generate derived clauses
+        solver.clause6(&[2, 4, -6]);
+        solver.clause6(&[3, 5]);
+        solver.clause6(&[-4, -5]);
+        solver.clause6(&[-2, -5]);
+        solver.clause6(&[6, -5]);
+        solver.clause6(&[-3]);
+
+        let result = solver.solve();
+        assert!(result == Status::UNSATISFIABLE);
+    });
 
     // Validate the proof trace
     validate_proof_trace(&proof_tracer);
@@ -821,58 +883,57 @@ fn test_proof_tracer_validation_pigeonhole() {
     let mut proof_tracer = TestProofTracer::new();
 
     // Connect tracer before adding clauses
-    solver.connect_proof_tracer1(&mut proof_tracer, true);
-
-    solver.clause6(&[1, 2, 3, 4]);
-    solver.clause6(&[5, 6, 7, 8]);
-    solver.clause6(&[9, 10, 11, 12]);
-    solver.clause6(&[13, 14, 15, 16]);
-    solver.clause6(&[17, 18, 19, 20]);
-    solver.clause6(&[-1, -5]);
-    solver.clause6(&[-1, -9]);
-    solver.clause6(&[-1, -13]);
-    solver.clause6(&[-1, -17]);
-    solver.clause6(&[-5, -9]);
-    solver.clause6(&[-5, -13]);
-    solver.clause6(&[-5, -17]);
-    solver.clause6(&[-9, -13]);
-    solver.clause6(&[-9, -17]);
-    solver.clause6(&[-13, -17]);
-    solver.clause6(&[-2, -6]);
-    solver.clause6(&[-2, -10]);
-    solver.clause6(&[-2, -14]);
-    solver.clause6(&[-2, -18]);
-    solver.clause6(&[-6, -10]);
-    solver.clause6(&[-6, -14]);
-    solver.clause6(&[-6, -18]);
-    solver.clause6(&[-10, -14]);
-    solver.clause6(&[-10, -18]);
-    solver.clause6(&[-14, -18]);
-    solver.clause6(&[-3, -7]);
-    solver.clause6(&[-3, -11]);
-    solver.clause6(&[-3, -15]);
-    solver.clause6(&[-3, -19]);
-    solver.clause6(&[-7, -11]);
-    solver.clause6(&[-7, -15]);
-    solver.clause6(&[-7, -19]);
-    solver.clause6(&[-11, -15]);
-    solver.clause6(&[-11, -19]);
-    solver.clause6(&[-15, -19]);
-    solver.clause6(&[-4, -8]);
-    solver.clause6(&[-4, -12]);
-    solver.clause6(&[-4, -16]);
-    solver.clause6(&[-4, -20]);
-    solver.clause6(&[-8, -12]);
-    solver.clause6(&[-8, -16]);
-    solver.clause6(&[-8, -20]);
-    solver.clause6(&[-12, -16]);
-    solver.clause6(&[-12, -20]);
-    solver.clause6(&[-16, -20]);
-
-    let result = solver.solve();
-    // Pigeonhole principle: 5 pigeons cannot fit in 4 holes
-    assert_eq!(result, Status::UNSATISFIABLE);
-    solver.disconnect_proof_tracer1();
+    solver.with_proof_tracer(&mut proof_tracer, true, false, |solver| {
+        solver.clause6(&[1, 2, 3, 4]);
+        solver.clause6(&[5, 6, 7, 8]);
+        solver.clause6(&[9, 10, 11, 12]);
+        solver.clause6(&[13, 14, 15, 16]);
+        solver.clause6(&[17, 18, 19, 20]);
+        solver.clause6(&[-1, -5]);
+        solver.clause6(&[-1, -9]);
+        solver.clause6(&[-1, -13]);
+        solver.clause6(&[-1, -17]);
+        solver.clause6(&[-5, -9]);
+        solver.clause6(&[-5, -13]);
+        solver.clause6(&[-5, -17]);
+        solver.clause6(&[-9, -13]);
+        solver.clause6(&[-9, -17]);
+        solver.clause6(&[-13, -17]);
+        solver.clause6(&[-2, -6]);
+        solver.clause6(&[-2, -10]);
+        solver.clause6(&[-2, -14]);
+        solver.clause6(&[-2, -18]);
+        solver.clause6(&[-6, -10]);
+        solver.clause6(&[-6, -14]);
+        solver.clause6(&[-6, -18]);
+        solver.clause6(&[-10, -14]);
+        solver.clause6(&[-10, -18]);
+        solver.clause6(&[-14, -18]);
+        solver.clause6(&[-3, -7]);
+        solver.clause6(&[-3, -11]);
+        solver.clause6(&[-3, -15]);
+        solver.clause6(&[-3, -19]);
+        solver.clause6(&[-7, -11]);
+        solver.clause6(&[-7, -15]);
+        solver.clause6(&[-7, -19]);
+        solver.clause6(&[-11, -15]);
+        solver.clause6(&[-11, -19]);
+        solver.clause6(&[-15, -19]);
+        solver.clause6(&[-4, -8]);
+        solver.clause6(&[-4, -12]);
+        solver.clause6(&[-4, -16]);
+        solver.clause6(&[-4, -20]);
+        solver.clause6(&[-8, -12]);
+        solver.clause6(&[-8, -16]);
+        solver.clause6(&[-8, -20]);
+        solver.clause6(&[-12, -16]);
+        solver.clause6(&[-12, -20]);
+        solver.clause6(&[-16, -20]);
+
+        let result = solver.solve();
+        // Pigeonhole principle: 5 pigeons cannot fit in 4 holes
+        assert_eq!(result, Status::UNSATISFIABLE);
+    });
 
     // pigeonhole so theres going to be a lot of derived clauses
     assert!(proof_tracer.get_derived_clauses().len() > 5);
@@ -887,12 +948,11 @@ fn test_proof_tracer_validation_empty_cnf() {
     let mut proof_tracer = TestProofTracer::new();
 
     // Connect tracer before solving
-    solver.connect_proof_tracer1(&mut proof_tracer, true);
-
-    // Solve with no clauses (empty CNF)
-    let result = solver.solve();
-    assert_eq!(result, Status::SATISFIABLE);
-    solver.disconnect_proof_tracer1();
+    solver.with_proof_tracer(&mut proof_tracer, true, false, |solver| {
+        // Solve with no clauses (empty CNF)
+        let result = solver.solve();
+        assert_eq!(result, Status::SATISFIABLE);
+    });
 
     // Validate the proof trace (should be mostly empty)
     validate_proof_trace(&proof_tracer);
@@ -904,22 +964,20 @@ fn test_proof_tracer_validation_multiple_solves() {
     let mut proof_tracer = TestProofTracer::new();
 
     // Connect tracer before adding clauses
-    solver.connect_proof_tracer1(&mut proof_tracer, true);
-
-    // First solve
-    add_sat_test_cnf(&mut solver);
-    let result1 = solver.solve();
-    assert_eq!(result1, Status::SATISFIABLE);
-
-    // Add more clauses and solve again
-    solver.clause6(&[4, 5, 6]);
-    solver.clause6(&[-4]);
-    solver.clause6(&[-5]);
-    solver.clause6(&[-6]);
-    let result2 = solver.solve();
-    assert_eq!(result2, Status::UNSATISFIABLE);
-
-    solver.disconnect_proof_tracer1();
+    solver.with_proof_tracer(&mut proof_tracer, true, false, |solver| {
+        // First solve
+        add_sat_test_cnf(solver);
+        let result1 = solver.solve();
+        assert_eq!(result1, Status::SATISFIABLE);
+
+        // Add more clauses and solve again
+        solver.clause6(&[4, 5, 6]);
+        solver.clause6(&[-4]);
+        solver.clause6(&[-5]);
+        solver.clause6(&[-6]);
+        let result2 = solver.solve();
+        assert_eq!(result2, Status::UNSATISFIABLE);
+    });
 
     // Validate the proof trace
     validate_proof_trace(&proof_tracer);
@@ -931,19 +989,19 @@ fn test_proof_tracer_validation_assumption_resets() {
     let mut proof_tracer = TestProofTracer::new();
 
     // Connect tracer before adding clauses
-    solver.connect_proof_tracer1(&mut proof_tracer, true);
-    add_sat_test_cnf(&mut solver);
+    solver.with_proof_tracer(&mut proof_tracer, true, false, |solver| {
+        add_sat_test_cnf(solver);
 
-    // Add assumptions and solve
-    solver.assume(1);
-    let result1 = solver.solve();
-    assert_eq!(result1, Status::SATISFIABLE);
+        // Add assumptions and solve
+        solver.assume(1);
+        let result1 = solver.solve();
+        assert_eq!(result1, Status::SATISFIABLE);
 
-    // Reset assumptions
-    solver.reset_assumptions();
-    let result2 = solver.solve();
-    assert_eq!(result2, Status::SATISFIABLE);
-    solver.disconnect_proof_tracer1();
+        // Reset assumptions
+        solver.reset_assumptions();
+        let result2 = solver.solve();
+        assert_eq!(result2, Status::SATISFIABLE);
+    });
 
     // Validate the proof trace
     validate_proof_trace(&proof_tracer);
@@ -960,11 +1018,11 @@ fn test_proof_tracer_validation_antecedents_parameter() {
     // Test with antecedents = true
     let mut solver1 = CaDiCal::new();
     let mut proof_tracer1 = TestProofTracer::new();
-    solver1.connect_proof_tracer1(&mut proof_tracer1, true);
-    add_sat_test_cnf(&mut solver1);
-    let result = solver1.solve();
-    assert_eq!(result, Status::SATISFIABLE);
-    solver1.disconnect_proof_tracer1();
+    solver1.with_proof_tracer(&mut proof_tracer1, true, false, |solver1| {
+        add_sat_test_cnf(solver1);
+        let result = solver1.solve();
+        assert_eq!(result, Status::SATISFIABLE);
+    });
 
     // Validate the proof trace
     validate_proof_trace(&proof_tracer1);
@@ -972,11 +1030,11 @@ fn test_proof_tracer_validation_antecedents_parameter() {
     // Test with antecedents = false using a new solver
     let mut solver2 = CaDiCal::new();
     let mut proof_tracer2 = TestProofTracer::new();
-    solver2.connect_proof_tracer1(&mut proof_tracer2, false);
-    add_sat_test_cnf(&mut solver2);
-    let result = solver2.solve();
-    assert_eq!(result, Status::SATISFIABLE);
-    solver2.disconnect_proof_tracer1();
+    solver2.with_proof_tracer(&mut proof_tracer2, false, false, |solver2| {
+        add_sat_test_cnf(solver2);
+        let result = solver2.solve();
+        assert_eq!(result, Status::SATISFIABLE);
+    });
 
     // Validate the proof trace
     validate_proof_trace(&proof_tracer2);
@@ -1157,12 +1215,12 @@ fn test_proof_tracer_validation_comprehensive_sat() {
     let mut solver = CaDiCal::new();
     let mut proof_tracer = TestProofTracer::new();
 
-    solver.connect_proof_tracer1(&mut proof_tracer, true);
-    add_sat_test_cnf(&mut solver);
+    solver.with_proof_tracer(&mut proof_tracer, true, false, |solver| {
+        add_sat_test_cnf(solver);
 
-    let result = solver.solve();
-    assert_eq!(result, Status::SATISFIABLE);
-    solver.disconnect_proof_tracer1();
+        let result = solver.solve();
+        assert_eq!(result, Status::SATISFIABLE);
+    });
 
     // Run comprehensive validation
     validate_proof_trace_comprehensive(&proof_tracer);
@@ -1173,12 +1231,12 @@ fn test_proof_tracer_validation_comprehensive_unsat() {
     let mut solver = CaDiCal::new();
     let mut proof_tracer = TestProofTracer::new();
 
-    solver.connect_proof_tracer1(&mut proof_tracer, true);
-    add_unsat_test_cnf(&mut solver);
+    solver.with_proof_tracer(&mut proof_tracer, true, false, |solver| {
+        add_unsat_test_cnf(solver);
 
-    let result = solver.solve();
-    assert_eq!(result, Status::UNSATISFIABLE);
-    solver.disconnect_proof_tracer1();
+        let result = solver.solve();
+        assert_eq!(result, Status::UNSATISFIABLE);
+    });
 
     // Run comprehensive validation
     validate_proof_trace_comprehensive(&proof_tracer);
@@ -1189,17 +1247,16 @@ fn test_proof_tracer_validation_large_cnf() {
     let mut solver = CaDiCal::new();
     let mut proof_tracer = TestProofTracer::new();
 
-    solver.connect_proof_tracer1(&mut proof_tracer, true);
+    solver.with_proof_tracer(&mut proof_tracer, true, false, |solver| {
+        // Add a larger CNF to test memory efficiency
+        for i in 1..=20 {
+            solver.clause6(&[i, i + 1, i + 2]);
+            solver.clause6(&[-i, -(i + 1), -(i + 2)]);
+        }
 
-    // Add a larger CNF to test memory efficiency
-    for i in 1..=20 {
-        solver.clause6(&[i, i + 1, i + 2]);
-        solver.clause6(&[-i, -(i + 1), -(i + 2)]);
-    }
-
-    let result = solver.solve();
-    assert!(result == Status::SATISFIABLE || result == Status::UNSATISFIABLE);
-    solver.disconnect_proof_tracer1();
+        let result = solver.solve();
+        assert!(result == Status::SATISFIABLE || result == Status::UNSATISFIABLE);
+    });
 
     // Run comprehensive validation
     validate_proof_trace_comprehensive(&proof_tracer);
@@ -1210,31 +1267,29 @@ fn test_proof_tracer_validation_mixed_operations() {
     let mut solver = CaDiCal::new();
     let mut proof_tracer = TestProofTracer::new();
 
-    solver.connect_proof_tracer1(&mut proof_tracer, true);
-
-    // Add clauses
-    add_sat_test_cnf(&mut solver);
+    solver.with_proof_tracer(&mut proof_tracer, true, false, |solver| {
+        // Add clauses
+        add_sat_test_cnf(solver);
 
-    // Add assumptions
-    solver.assume(1);
-    solver.assume(2);
+        // Add assumptions
+        solver.assume(1);
+        solver.assume(2);
 
-    // Solve
-    let result1 = solver.solve();
-    assert_eq!(result1, Status::SATISFIABLE);
+        // Solve
+        let result1 = solver.solve();
+        assert_eq!(result1, Status::SATISFIABLE);
 
-    // Reset assumptions
-    solver.reset_assumptions();
+        // Reset assumptions
+        solver.reset_assumptions();
 
-    // Add more clauses
-    solver.clause6(&[4, 5, 6]);
-    solver.clause6(&[-4, -5, -6]);
+        // Add more clauses
+        solver.clause6(&[4, 5, 6]);
+        solver.clause6(&[-4, -5, -6]);
 
-    // Solve again
-    let result2 = solver.solve();
-    assert!(result2 == Status::SATISFIABLE || result2 == Status::UNSATISFIABLE);
-
-    solver.disconnect_proof_tracer1();
+        // Solve again
+        let result2 = solver.solve();
+        assert!(result2 == Status::SATISFIABLE || result2 == Status::UNSATISFIABLE);
+    });
 
     // Run comprehensive validation
     validate_proof_trace_comprehensive(&proof_tracer);
@@ -1246,8 +1301,7 @@ fn test_proof_tracer_validation_edge_case_empty_trace() {
     let mut proof_tracer = TestProofTracer::new();
 
     // Connect and immediately disconnect without adding clauses
-    solver.connect_proof_tracer1(&mut proof_tracer, true);
-    solver.disconnect_proof_tracer1();
+    solver.with_proof_tracer(&mut proof_tracer, true, false, |_| {});
 }
 
 #[test]
@@ -1255,14 +1309,13 @@ fn test_proof_tracer_validation_edge_case_single_clause() {
     let mut solver = CaDiCal::new();
     let mut proof_tracer = TestProofTracer::new();
 
-    solver.connect_proof_tracer1(&mut proof_tracer, true);
-
-    // Add only one clause
-    solver.clause6(&[1]);
+    solver.with_proof_tracer(&mut proof_tracer, true, false, |solver| {
+        // Add only one clause
+        solver.clause6(&[1]);
 
-    let result = solver.solve();
-    assert_eq!(result, Status::SATISFIABLE);
-    solver.disconnect_proof_tracer1();
+        let result = solver.solve();
+        assert_eq!(result, Status::SATISFIABLE);
+    });
 
     // Run comprehensive validation
     validate_proof_trace_comprehensive(&proof_tracer);
@@ -1273,17 +1326,16 @@ fn test_proof_tracer_validation_edge_case_contradictory_clauses() {
     let mut solver = CaDiCal::new();
     let mut proof_tracer = TestProofTracer::new();
 
-    solver.connect_proof_tracer1(&mut proof_tracer, true);
+    solver.with_proof_tracer(&mut proof_tracer, true, false, |solver| {
+        // Add contradictory clauses
+        solver.clause6(&[1, 2]);
+        solver.clause6(&[-1, 2]);
+        solver.clause6(&[1, -2]);
+        solver.clause6(&[-1, -2]);
 
-    // Add contradictory clauses
-    solver.clause6(&[1, 2]);
-    solver.clause6(&[-1, 2]);
-    solver.clause6(&[1, -2]);
-    solver.clause6(&[-1, -2]);
-
-    let result = solver.solve();
-    assert_eq!(result, Status::UNSATISFIABLE);
-    solver.disconnect_proof_tracer1();
+        let result = solver.solve();
+        assert_eq!(result, Status::UNSATISFIABLE);
+    });
 
     // Run comprehensive validation
     validate_proof_trace_comprehensive(&proof_tracer);
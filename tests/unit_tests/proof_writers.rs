@@ -0,0 +1,160 @@
+use super::common::{add_pigeonhole_cnf, lits};
+use cadical_sys::{
+    proof::{DratWriter, FratWriter, LratWriter},
+    CaDiCal, ProofTracer, Status,
+};
+
+/// Traces a small refutation: two originals, a derived unit, a deletion and
+/// the empty clause.
+fn trace(tracer: &mut impl ProofTracer) {
+    tracer.add_original_clause(1, false, &lits(&[1, 2]), false);
+    tracer.add_original_clause(2, false, &lits(&[1, -2]), false);
+    tracer.add_original_clause(3, false, &lits(&[-1, 70]), false);
+    tracer.add_original_clause(4, false, &lits(&[-1, -70]), false);
+    tracer.add_derived_clause(5, true, &lits(&[1]), &[1, 2]);
+    tracer.delete_clause(1, false, &lits(&[1, 2]));
+    tracer.delete_clause(2, false, &lits(&[1, -2]));
+    tracer.add_derived_clause(6, true, &[], &[5, 3, 4]);
+    tracer.finalize_clause(6, &[]);
+    tracer.conclude_unsat(1, &[6]);
+}
+
+#[test]
+fn test_drat_writer() {
+    let mut drat = DratWriter::new(Vec::new(), false);
+    trace(&mut drat);
+    let proof = drat.finish().unwrap();
+    assert_eq!(
+        String::from_utf8(proof).unwrap(),
+        "1 0\nd 1 2 0\nd 1 -2 0\n0\n"
+    );
+
+    let mut drat = DratWriter::new(Vec::new(), true);
+    trace(&mut drat);
+    assert_eq!(
+        drat.finish().unwrap(),
+        b"a\x02\x00d\x02\x04\x00d\x02\x05\x00a\x00"
+    );
+}
+
+#[test]
+fn test_lrat_writer() {
+    let mut lrat = LratWriter::new(Vec::new(), false);
+    trace(&mut lrat);
+    let proof = lrat.finish().unwrap();
+    assert_eq!(
+        String::from_utf8(proof).unwrap(),
+        "5 1 0 1 2 0\n5 d 1 2 0\n6 0 5 3 4 0\n"
+    );
+
+    let mut lrat = LratWriter::new(Vec::new(), true);
+    trace(&mut lrat);
+    assert_eq!(
+        lrat.finish().unwrap(),
+        b"a\x0a\x02\x00\x02\x04\x00d\x02\x04\x00a\x0c\x00\x0a\x06\x08\x00"
+    );
+
+    // deletions after the last addition are written by 'finish'
+    let mut lrat = LratWriter::new(Vec::new(), false);
+    lrat.add_original_clause(1, false, &lits(&[1]), false);
+    lrat.delete_clause(1, false, &lits(&[1]));
+    assert_eq!(lrat.finish().unwrap(), b"1 d 1 0\n");
+}
+
+#[test]
+fn test_frat_writer() {
+    let mut frat = FratWriter::new(Vec::new(), false);
+    trace(&mut frat);
+    let proof = frat.finish().unwrap();
+    assert_eq!(
+        String::from_utf8(proof).unwrap(),
+        "o 1 1 2 0\no 2 1 -2 0\no 3 -1 70 0\no 4 -1 -70 0\n\
+         a 5 1 0 l 1 2 0\nd 1 1 2 0\nd 2 1 -2 0\na 6 0 l 5 3 4 0\nf 6 0\n"
+    );
+
+    let mut frat = FratWriter::new(Vec::new(), true);
+    frat.add_original_clause(1, false, &lits(&[-70]), false);
+    frat.add_derived_clause(2, true, &lits(&[1]), &[]);
+    frat.add_derived_clause(200, true, &[], &[1, 2]);
+    assert_eq!(
+        frat.finish().unwrap(),
+        b"o\x02\x8d\x01\x00a\x04\x02\x00a\x90\x03\x00l\x02\x04\x00"
+    );
+}
+
+#[test]
+fn test_write_errors_are_kept() {
+    struct Full;
+
+    impl std::io::Write for Full {
+        fn write(&mut self, _: &[u8]) -> std::io::Result<usize> {
+            Err(std::io::ErrorKind::WriteZero.into())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let mut drat = DratWriter::new(Full, false);
+    trace(&mut drat);
+    assert!(matches!(drat.finish(), Err(cadical_sys::Error::Io(_))));
+}
+
+#[test]
+fn test_lrat_proof_of_solver() {
+    let mut solver = CaDiCal::new();
+    let mut lrat = LratWriter::new(Vec::new(), false);
+    let mut drat = DratWriter::new(Vec::new(), false);
+    let pigeons = |solver: &mut CaDiCal| {
+        add_pigeonhole_cnf(solver, 2);
+        solver.solve()
+    };
+    // both tracers see the same run
+    let status = solver.with_proof_tracer(&mut lrat, true, false, |solver| {
+        solver.with_proof_tracer(&mut drat, false, false, pigeons)
+    });
+    assert_eq!(status, Status::UNSATISFIABLE);
+    assert!(solver.proof_tracers().is_empty());
+    let lrat = String::from_utf8(lrat.finish().unwrap()).unwrap();
+    // every addition has antecedents and the last one is the empty clause
+    let additions: Vec<&str> = lrat.lines().filter(|line| !line.contains(" d ")).collect();
+    assert!(additions.iter().all(|line| !line.ends_with(" 0 0")));
+    let empty = additions.last().unwrap().split(' ').nth(1);
+    assert_eq!(empty, Some("0"));
+
+    let drat = String::from_utf8(drat.finish().unwrap()).unwrap();
+    assert!(drat.lines().any(|line| line == "0"));
+    assert_eq!(
+        drat.lines().filter(|line| !line.starts_with("d ")).count(),
+        additions.len()
+    );
+}
+
+#[test]
+fn test_frat_proof_of_solver_is_finalized() {
+    let mut solver = CaDiCal::new();
+    let mut frat = FratWriter::new(Vec::new(), false);
+    let status = solver.with_proof_tracer(&mut frat, true, true, |solver| {
+        add_pigeonhole_cnf(solver, 2);
+        solver.solve()
+    });
+    assert_eq!(status, Status::UNSATISFIABLE);
+    let frat = String::from_utf8(frat.finish().unwrap()).unwrap();
+    let ids = |tag: &str| -> Vec<String> {
+        let lines = frat.lines().map(|line| line.split(' ').collect::<Vec<_>>());
+        let lines = lines.filter(|step| step[0] == tag);
+        lines.map(|step| step[1].to_string()).collect()
+    };
+    let added = [ids("o"), ids("a")].concat();
+    assert_eq!(ids("o").len(), 9);
+
+    // every finalized clause was added before, the empty clause included
+    let finalized = ids("f");
+    assert!(finalized.iter().all(|id| added.contains(id)));
+    let empty = frat
+        .lines()
+        .find(|line| line.starts_with("a ") && line.split(' ').nth(2) == Some("0"));
+    let empty = empty.unwrap().split(' ').nth(1).unwrap();
+    assert!(finalized.iter().any(|id| id == empty));
+}
@@ -0,0 +1,96 @@
+use super::common::add_pigeonhole_cnf;
+use cadical_sys::{CaDiCal, Status, Terminator};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+struct CountingTerminator {
+    calls: usize,
+    terminate: bool,
+}
+
+impl CountingTerminator {
+    fn new(terminate: bool) -> Self {
+        Self {
+            calls: 0,
+            terminate,
+        }
+    }
+}
+
+impl Terminator for CountingTerminator {
+    fn terminated(&mut self) -> bool {
+        self.calls += 1;
+        self.terminate
+    }
+}
+
+#[test]
+fn test_terminator_stops_solve_inside_scope() {
+    let mut solver = CaDiCal::new();
+    let mut terminator = CountingTerminator::new(true);
+    add_pigeonhole_cnf(&mut solver, 6);
+
+    let result = solver.with_terminator(&mut terminator, CaDiCal::solve);
+
+    assert_eq!(result, Status::UNKNOWN);
+    assert!(terminator.calls > 0);
+}
+
+#[test]
+fn test_terminator_is_disconnected_after_scope() {
+    let mut solver = CaDiCal::new();
+    let mut terminator = CountingTerminator::new(true);
+    add_pigeonhole_cnf(&mut solver, 5);
+
+    solver.with_terminator(&mut terminator, |_| {});
+
+    assert_eq!(solver.solve(), Status::UNSATISFIABLE);
+    assert_eq!(terminator.calls, 0);
+}
+
+#[test]
+fn test_terminator_is_disconnected_after_panic() {
+    let mut solver = CaDiCal::new();
+    let mut terminator = CountingTerminator::new(true);
+    add_pigeonhole_cnf(&mut solver, 5);
+
+    let caught = catch_unwind(AssertUnwindSafe(|| {
+        solver.with_terminator(&mut terminator, |_| panic!("user code failed"));
+    }));
+    assert!(caught.is_err());
+
+    assert_eq!(solver.solve(), Status::UNSATISFIABLE);
+    assert_eq!(terminator.calls, 0);
+}
+
+#[test]
+fn test_swapped_solver_does_not_reach_released_terminator() {
+    let mut solver = CaDiCal::new();
+    let mut escaped = CaDiCal::new();
+    add_pigeonhole_cnf(&mut solver, 5);
+
+    {
+        let mut terminator = CountingTerminator::new(true);
+        solver.with_terminator(&mut terminator, |solver| {
+            std::mem::swap(solver, &mut escaped);
+        });
+    }
+
+    // The escaped solver still holds the wrapper, which must now be inert.
+    assert_eq!(escaped.solve(), Status::UNSATISFIABLE);
+}
+
+#[test]
+fn test_nested_scopes_replace_terminator() {
+    let mut solver = CaDiCal::new();
+    let mut outer = CountingTerminator::new(false);
+    let mut inner = CountingTerminator::new(true);
+    add_pigeonhole_cnf(&mut solver, 6);
+
+    let result = solver.with_terminator(&mut outer, |solver| {
+        solver.with_terminator(&mut inner, CaDiCal::solve)
+    });
+
+    assert_eq!(result, Status::UNKNOWN);
+    assert_eq!(outer.calls, 0);
+    assert!(inner.calls > 0);
+}
@@ -0,0 +1,145 @@
+use super::common::parse_error;
+use cadical_sys::{
+    dimacs::Cnf,
+    solution::{Solution, LINE_WIDTH},
+    CaDiCal, Error, Lit, Status, Var,
+};
+use std::fs;
+
+#[test]
+fn test_write_results() {
+    let mut solver = CaDiCal::new();
+    solver.clause2(1, 2);
+    solver.clause2(-1, 2);
+    solver.clause2(1, -2);
+    solver.clause2(-1, -2);
+    let status = solver.solve();
+    let solution = Solution::from_solver(&mut solver, status);
+    assert_eq!(solution.to_string(), "s UNSATISFIABLE\n");
+
+    let solution = Solution {
+        status: Status::UNKNOWN,
+        model: Vec::new(),
+        comments: vec!["interrupted".to_string()],
+    };
+    assert_eq!(solution.to_string(), "c interrupted\ns UNKNOWN\n");
+}
+
+#[test]
+fn test_values_are_wrapped() {
+    let mut solver = CaDiCal::new();
+    for var in 1..=100 {
+        solver.clause1(if var % 3 == 0 { -var } else { var });
+    }
+    let status = solver.solve();
+    let solution = Solution::from_solver(&mut solver, status);
+    assert_eq!(solution.model.len(), 100);
+    assert_eq!(solution.value(Var::new(3).unwrap()), Some(false));
+
+    let text = solution.to_string();
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(lines[0], "s SATISFIABLE");
+    assert!(lines.len() > 3);
+    assert!(lines[1..]
+        .iter()
+        .all(|line| line.starts_with("v ") && line.len() <= LINE_WIDTH));
+    assert!(text.ends_with(" 0\n"));
+    assert_eq!(text.parse::<Solution>().unwrap(), solution);
+
+    let mut narrow = Vec::new();
+    solution.write_wrapped(&mut narrow, 9).unwrap();
+    let narrow = String::from_utf8(narrow).unwrap();
+    assert!(narrow.starts_with("s SATISFIABLE\nv 1 2 -3\nv 4 5 -6\n"));
+    assert!(narrow.lines().skip(1).all(|line| line.len() <= 9));
+    assert_eq!(narrow.parse::<Solution>().unwrap(), solution);
+}
+
+#[test]
+fn test_parse_solution() {
+    let solution: Solution = "c by another solver\ns SATISFIABLE\nv 1 -2\nv  3\t\nv 0\n"
+        .parse()
+        .unwrap();
+    assert_eq!(solution.status, Status::SATISFIABLE);
+    assert_eq!(solution.comments, ["by another solver"]);
+    assert_eq!(solution.model, Lit::slice_from_dimacs(&[1, -2, 3]).unwrap());
+    let unsat: Solution = "s UNSATISFIABLE\n".parse().unwrap();
+    assert_eq!(unsat.status, Status::UNSATISFIABLE);
+}
+
+#[test]
+fn test_solution_errors() {
+    assert_eq!(
+        parse_error("c nothing\n".parse::<Solution>()),
+        (1, 10, "missing status line".to_string())
+    );
+    assert_eq!(
+        parse_error("s SAT\n".parse::<Solution>()),
+        (
+            1,
+            3,
+            "expected 'SATISFIABLE', 'UNSATISFIABLE' or 'UNKNOWN'".to_string()
+        )
+    );
+    assert_eq!(
+        parse_error("s UNSATISFIABLE\ns SATISFIABLE\n".parse::<Solution>()),
+        (2, 1, "second status line".to_string())
+    );
+    assert_eq!(
+        parse_error("s UNKNOWN\nv 1 0\n".parse::<Solution>()),
+        (2, 1, "values without 's SATISFIABLE'".to_string())
+    );
+    assert_eq!(
+        parse_error("s SATISFIABLE\nv 1 -1 0\n".parse::<Solution>()),
+        (2, 5, "variable 1 assigned twice".to_string())
+    );
+    assert_eq!(
+        parse_error("s SATISFIABLE\nv 1 0 2\n".parse::<Solution>()),
+        (2, 7, "value after terminating '0'".to_string())
+    );
+    assert_eq!(
+        parse_error("s SATISFIABLE\nv 1 2\n".parse::<Solution>()),
+        (2, 6, "missing terminating '0'".to_string())
+    );
+    assert_eq!(
+        parse_error("x\n".parse::<Solution>()),
+        (1, 1, "expected 'c', 's' or 'v'".to_string())
+    );
+}
+
+#[test]
+fn test_verify_model() {
+    let cnf: Cnf = "p cnf 3 2\n1 -2 0\n2 3 0\n".parse().unwrap();
+    let good: Solution = "s SATISFIABLE\nv 1 2 -3 0\n".parse().unwrap();
+    let bad: Solution = "s SATISFIABLE\nv -1 2 -3 0\n".parse().unwrap();
+    let partial: Solution = "s SATISFIABLE\nv 1 0\n".parse().unwrap();
+    assert_eq!(good.falsified(&cnf), None);
+    assert_eq!(bad.falsified(&cnf), Some(0));
+    assert_eq!(partial.falsified(&cnf), Some(1));
+
+    let path = std::env::temp_dir().join("cadical_sys_solution_verify.cnf");
+    fs::write(&path, cnf.to_string()).unwrap();
+    assert!(good.verify(&path).unwrap());
+    assert!(!bad.verify(&path).unwrap());
+    let unknown = Solution {
+        status: Status::UNKNOWN,
+        ..good.clone()
+    };
+    assert!(!unknown.verify(&path).unwrap());
+    fs::remove_file(&path).unwrap();
+    assert!(matches!(good.verify(&path), Err(Error::Open { .. })));
+}
+
+#[test]
+fn test_verify_own_model() {
+    let mut solver = CaDiCal::new();
+    let mut vars = 0;
+    solver
+        .read_dimacs2("./tests/problem.cnf", &mut vars, 1)
+        .unwrap();
+    let status = solver.solve();
+    let solution = Solution::from_solver(&mut solver, status);
+    assert_eq!(
+        solution.verify("./tests/problem.cnf").unwrap(),
+        status == Status::SATISFIABLE
+    );
+}
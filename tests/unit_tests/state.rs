@@ -0,0 +1,81 @@
+use cadical_sys::{CaDiCal, State, Status};
+
+#[test]
+fn test_combined_states_contain_their_parts() {
+    assert_eq!(
+        State::CONFIGURING | State::STEADY | State::SATISFIED | State::UNSATISFIED,
+        State::READY
+    );
+    assert_eq!(State::READY | State::ADDING, State::VALID);
+    assert_eq!(State::INITIALIZING | State::DELETING, State::INVALID);
+    assert!(State::VALID.contains(State::READY));
+    assert!(State::VALID.contains(State::ADDING));
+    assert!(!State::READY.contains(State::ADDING));
+    assert!(!State::READY.contains(State::VALID));
+    assert_eq!(State::VALID & State::INVALID, State::empty());
+}
+
+#[test]
+fn test_ready_and_valid() {
+    for state in [
+        State::CONFIGURING,
+        State::STEADY,
+        State::SATISFIED,
+        State::UNSATISFIED,
+    ] {
+        assert!(state.is_ready(), "{state} should be ready");
+        assert!(state.is_valid(), "{state} should be valid");
+    }
+    assert!(!State::ADDING.is_ready());
+    assert!(State::ADDING.is_valid());
+    for state in [State::INITIALIZING, State::SOLVING, State::DELETING] {
+        assert!(!state.is_ready(), "{state} should not be ready");
+        assert!(!state.is_valid(), "{state} should not be valid");
+    }
+}
+
+#[test]
+fn test_from_bits() {
+    assert_eq!(
+        State::from_bits(3),
+        Some(State::INITIALIZING | State::CONFIGURING)
+    );
+    assert_eq!(State::from_bits(256), None);
+    assert_eq!(State::from_bits_retain(256).bits(), 256);
+    assert_eq!(i32::from(State::VALID), 110);
+}
+
+#[test]
+fn test_display() {
+    assert_eq!(State::STEADY.to_string(), "STEADY");
+    assert_eq!(State::READY.to_string(), "READY");
+    assert_eq!(State::VALID.to_string(), "VALID");
+    assert_eq!(
+        (State::STEADY | State::ADDING).to_string(),
+        "STEADY | ADDING"
+    );
+    assert_eq!(
+        (State::INVALID | State::CONFIGURING).to_string(),
+        "INVALID | CONFIGURING"
+    );
+    assert_eq!(
+        State::from_bits_retain(4 | 256).to_string(),
+        "STEADY | 0x100"
+    );
+    assert_eq!(State::empty().to_string(), "0");
+    assert_eq!(format!("{:?}", State::ADDING), "State(ADDING)");
+}
+
+#[test]
+fn test_solver_state_transitions() {
+    let mut solver = CaDiCal::new();
+    assert_eq!(solver.state(), State::CONFIGURING);
+    solver.add(1);
+    assert_eq!(solver.state(), State::ADDING);
+    assert!(!solver.state().is_ready());
+    solver.add(0);
+    assert_eq!(solver.state(), State::STEADY);
+    assert_eq!(solver.solve(), Status::SATISFIABLE);
+    assert_eq!(solver.state(), State::SATISFIED);
+    assert!(State::READY.contains(solver.state()));
+}
@@ -0,0 +1,116 @@
+use super::common::add_pigeonhole_cnf;
+use cadical_sys::{CaDiCal, Status, TerminateHandle, Terminator};
+use std::{
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    thread,
+    time::Duration,
+};
+
+fn assert_send<T: Send>() {}
+fn assert_send_sync_clone<T: Send + Sync + Clone>() {}
+
+#[test]
+fn test_solver_and_handle_are_thread_safe() {
+    assert_send::<CaDiCal>();
+    assert_send_sync_clone::<TerminateHandle>();
+}
+
+#[test]
+fn test_solver_moves_to_worker_thread() {
+    let mut solver = CaDiCal::new();
+    solver.connect_terminator_fn(|| false);
+    add_pigeonhole_cnf(&mut solver, 5);
+
+    let worker = thread::spawn(move || {
+        let status = solver.solve();
+        (solver, status)
+    });
+    let (solver, status) = worker.join().unwrap();
+    assert_eq!(status, Status::UNSATISFIABLE);
+    assert_eq!(solver.status(), Status::UNSATISFIABLE);
+}
+
+#[test]
+fn test_handle_stops_solve_on_other_thread() {
+    let mut solver = CaDiCal::new();
+    // far too hard to be solved before the request arrives
+    add_pigeonhole_cnf(&mut solver, 12);
+    let handle = solver.terminate_handle();
+
+    let worker = thread::spawn(move || solver.solve());
+    while !worker.is_finished() {
+        handle.terminate();
+        thread::sleep(Duration::from_millis(1));
+    }
+    assert_eq!(worker.join().unwrap(), Status::UNKNOWN);
+}
+
+#[test]
+fn test_handle_works_next_to_connected_terminator() {
+    let mut solver = CaDiCal::new();
+    solver.connect_terminator_fn(|| false);
+    add_pigeonhole_cnf(&mut solver, 12);
+    let handle = solver.terminate_handle();
+
+    thread::scope(|scope| {
+        let worker = scope.spawn(|| solver.solve());
+        while !worker.is_finished() {
+            handle.terminate();
+            thread::sleep(Duration::from_millis(1));
+        }
+        assert_eq!(worker.join().unwrap(), Status::UNKNOWN);
+    });
+}
+
+#[test]
+fn test_request_is_withdrawn_after_stopped_call() {
+    let mut solver = CaDiCal::new();
+    add_pigeonhole_cnf(&mut solver, 5);
+
+    solver.terminate_handle().terminate();
+    assert_eq!(solver.solve(), Status::UNKNOWN);
+    assert_eq!(solver.solve(), Status::UNSATISFIABLE);
+}
+
+/// Counts its calls where the test can watch them.
+struct Counter(Arc<AtomicU64>);
+
+impl Terminator for Counter {
+    fn terminated(&mut self) -> bool {
+        self.0.fetch_add(1, Ordering::Relaxed);
+        false
+    }
+}
+
+#[test]
+fn test_solver_swapped_out_of_scope_is_cut_off() {
+    let calls = Arc::new(AtomicU64::new(0));
+    let mut counter = Counter(Arc::clone(&calls));
+    let mut solver = CaDiCal::new();
+    let (handle, worker) = solver.with_terminator(&mut counter, |solver| {
+        // the scoped solver escapes to a thread which calls the terminator
+        let mut escaped = std::mem::take(solver);
+        add_pigeonhole_cnf(&mut escaped, 12);
+        let handle = escaped.terminate_handle();
+        let worker = thread::spawn(move || escaped.solve());
+        while calls.load(Ordering::Relaxed) == 0 {
+            thread::sleep(Duration::from_millis(1));
+        }
+        (handle, worker)
+    });
+
+    // the scope ended, so the terminator is free again and no longer called
+    let seen = calls.load(Ordering::Relaxed);
+    assert!(!counter.terminated());
+    thread::sleep(Duration::from_millis(20));
+    assert_eq!(calls.load(Ordering::Relaxed), seen + 1);
+
+    while !worker.is_finished() {
+        handle.terminate();
+        thread::sleep(Duration::from_millis(1));
+    }
+    assert_eq!(worker.join().unwrap(), Status::UNKNOWN);
+}
@@ -0,0 +1,121 @@
+use super::common::{lits, parse_error};
+use cadical_sys::{
+    wcnf::{Format, SoftClause, Wcnf},
+    CaDiCal, Error, Status,
+};
+
+fn soft(weight: u64, clause: &[i32]) -> SoftClause {
+    SoftClause {
+        weight,
+        lits: lits(clause),
+    }
+}
+
+#[test]
+fn test_evaluation_format() {
+    let text = "c soft and hard\nh 1 2 0\nh -3\n 0\n4 -1 0\n1 -2 3 0\n";
+    let wcnf: Wcnf = text.parse().unwrap();
+    assert_eq!(wcnf.vars, 3);
+    assert_eq!(wcnf.comments, ["soft and hard"]);
+    assert_eq!(wcnf.hard, [lits(&[1, 2]), lits(&[-3])]);
+    assert_eq!(wcnf.soft, [soft(4, &[-1]), soft(1, &[-2, 3])]);
+    assert_eq!(wcnf.total_weight(), Some(5));
+    assert_eq!(
+        wcnf.to_string(),
+        "c soft and hard\nh 1 2 0\nh -3 0\n4 -1 0\n1 -2 3 0\n"
+    );
+}
+
+#[test]
+fn test_classic_format() {
+    let text = "c classic\np wcnf 4 4 10\n10 1 2 0\n3 -1 0\n12 -2 0\n9 4 0\n";
+    let wcnf: Wcnf = text.parse().unwrap();
+    assert_eq!(wcnf.vars, 4);
+    assert_eq!(wcnf.hard, [lits(&[1, 2]), lits(&[-2])]);
+    assert_eq!(wcnf.soft, [soft(3, &[-1]), soft(9, &[4])]);
+
+    let mut written = Vec::new();
+    wcnf.write(&mut written, Format::Classic).unwrap();
+    assert_eq!(
+        String::from_utf8(written.clone()).unwrap(),
+        "c classic\np wcnf 4 4 13\n13 1 2 0\n13 -2 0\n3 -1 0\n9 4 0\n"
+    );
+    assert_eq!(Wcnf::parse(written.as_slice(), 2).unwrap(), wcnf);
+
+    // without 'top' every clause is soft
+    let wcnf: Wcnf = "p wcnf 2 2\n5 1 0\n7 -2 0\n".parse().unwrap();
+    assert!(wcnf.hard.is_empty());
+    assert_eq!(wcnf.soft, [soft(5, &[1]), soft(7, &[-2])]);
+}
+
+#[test]
+fn test_formats_round_trip() {
+    let wcnf: Wcnf = "h 1 -2 0\n2 2 0\n3 -1 0\n".parse().unwrap();
+    for format in [Format::Classic, Format::Evaluation] {
+        let mut written = Vec::new();
+        wcnf.write(&mut written, format).unwrap();
+        assert_eq!(Wcnf::parse(written.as_slice(), 1).unwrap(), wcnf);
+    }
+}
+
+#[test]
+fn test_wcnf_errors() {
+    assert_eq!(
+        parse_error(Wcnf::parse("h 1 0\n0 2 0\n".as_bytes(), 1)),
+        (2, 1, "weight must be positive".to_string())
+    );
+    assert_eq!(
+        parse_error(Wcnf::parse("h 1 x 0\n".as_bytes(), 1)),
+        (1, 5, "expected literal or comment".to_string())
+    );
+    assert_eq!(
+        parse_error(Wcnf::parse("x 1 0\n".as_bytes(), 1)),
+        (1, 1, "expected 'h', weight or comment".to_string())
+    );
+    assert_eq!(
+        parse_error(Wcnf::parse("p wcnf 2 1 5\nh 1 0\n".as_bytes(), 1)),
+        (2, 1, "expected weight or comment".to_string())
+    );
+    assert_eq!(
+        parse_error(Wcnf::parse("h1 0\n".as_bytes(), 1)),
+        (1, 2, "unexpected character after 'h'".to_string())
+    );
+    assert_eq!(
+        parse_error(Wcnf::parse("h 1 2\n".as_bytes(), 1)),
+        (1, 6, "last clause without terminating '0'".to_string())
+    );
+    assert_eq!(
+        parse_error(Wcnf::parse("p wcnf 1 1 5\n5 2 0\n".as_bytes(), 1)),
+        (2, 3, "literal 2 exceeds maximum variable 1".to_string())
+    );
+    assert_eq!(
+        parse_error(Wcnf::parse("p wcnf 2 1 5\n5 2 0\n1 1 0\n".as_bytes(), 1)),
+        (3, 1, "too many clauses, header specifies 1".to_string())
+    );
+    assert_eq!(
+        parse_error(Wcnf::parse("p wcnf 2 3 5\n5 2 0\n".as_bytes(), 1)),
+        (2, 6, "2 clauses missing".to_string())
+    );
+    assert!(Wcnf::parse("p wcnf 1 3 5\n5 2 0\n".as_bytes(), 0).is_ok());
+}
+
+#[test]
+fn test_classic_top_overflow() {
+    let wcnf: Wcnf = format!("{} 1 0\n1 -1 0\n", u64::MAX).parse().unwrap();
+    assert_eq!(wcnf.total_weight(), None);
+    assert!(matches!(
+        wcnf.write(Vec::new(), Format::Classic),
+        Err(Error::Io(_))
+    ));
+    assert!(wcnf.write(Vec::new(), Format::Evaluation).is_ok());
+}
+
+#[test]
+fn test_add_hard_clauses() {
+    let wcnf: Wcnf = "h 1 2 0\nh -1 0\n5 -2 0\n1 3 0\n".parse().unwrap();
+    let mut solver = CaDiCal::new();
+    wcnf.add_hard(&mut solver);
+    assert_eq!(solver.vars(), 3);
+    assert_eq!(solver.solve(), Status::SATISFIABLE);
+    assert_eq!(solver.val(2), 2);
+}